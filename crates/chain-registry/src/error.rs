@@ -20,11 +20,24 @@ define_error! {
             [ TraceError<JoinError> ]
             |e| { format_args!("Error when joining task: {}", e.task) },
 
+        InvalidSource
+            { location: String }
+            |e| { format_args!("Chain registry not found at: {}, expected a URL, a directory or a .tar(.gz) archive", e.location) },
+
+        IoError
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format_args!("Error when reading file: {:?}", e.path) },
+
         JsonParseError
             { chain_name: String }
             [ TraceError <serde_json::Error> ]
             |e| { format_args!("Error when parsing JSON for chain {}", e.chain_name) },
 
+        MissingResource
+            { path: PathBuf, archive: PathBuf }
+            |e| { format_args!("Resource {:?} not found in archive {:?}", e.path, e.archive) },
+
         NoAssetFound
             { chain_name: String }
             |e| { format_args!("No asset found for chain: {}", e.chain_name) },
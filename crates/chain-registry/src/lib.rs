@@ -6,3 +6,4 @@ pub mod fetchable;
 pub mod formatter;
 pub mod paths;
 pub mod querier;
+pub mod source;
@@ -1,6 +1,8 @@
 //! Contains traits to query nodes of a given chain from their APIs.
 //! Contains struct to perform a health check on a gRPC/WebSocket endpoint and
 //! to retrieve the `max_block_size` from a RPC endpoint.
+//! The endpoints which answer are ranked by how far they lag behind the latest block,
+//! by latency, and by whether they support what Hermes needs from them.
 
 use std::fmt::Debug;
 use std::str::FromStr;
@@ -9,10 +11,19 @@ use async_trait::async_trait;
 use futures::{stream::FuturesUnordered, StreamExt};
 use http::Uri;
 use tokio::time::timeout;
-use tokio::time::Duration;
-use tracing::{debug, info};
-
-use ibc_proto::cosmos::bank::v1beta1::query_client::QueryClient;
+use tokio::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use ibc_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient, GetLatestBlockRequest,
+};
+use ibc_proto::cosmos::staking::v1beta1::{
+    query_client::QueryClient as StakingQueryClient, QueryParamsRequest,
+};
+use ibc_proto::ibc::core::client::v1::{
+    query_client::QueryClient as IbcClientQueryClient, QueryClientParamsRequest,
+};
+use tendermint::node::info::TxIndexStatus;
 use tendermint_rpc::{Client, SubscriptionClient, Url, WebSocketClient};
 
 use crate::error::RegistryError;
@@ -23,14 +34,17 @@ pub trait QueryTypes {
     /// `QueryInput` represents the data needed to query a node. It is typically a URL
     type QueryInput: Debug + Send;
     /// `QueryOutput` represents the data returned by your query
-    type QueryOutput;
+    type QueryOutput: Send;
     /// `QueryOutput` represents the error returned when a query fails
-    type QueryError;
+    type QueryError: Send;
 }
 
 #[async_trait]
 /// `QueryContext` represents the basic expectations for a query
 pub trait QueryContext: QueryTypes {
+    /// Time after which an endpoint which has not answered is skipped by [`QueryContext::query_ranked`]
+    const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
     /// Return an error specific to the query which is retured when `query_healthy` fails
     ///
     /// # Arguments
@@ -69,12 +83,92 @@ pub trait QueryContext: QueryTypes {
 
         Err(Self::query_error(chain_name))
     }
+
+    /// Sort the outputs of the endpoints which answered, from the best endpoint to the worst.
+    /// By default, the endpoints are left in the order in which they answered.
+    fn rank(_outputs: &mut [Self::QueryOutput]) {}
+
+    /// Query every endpoint from a list of urls concurrently and return the outputs of all
+    /// the ones which answered, from the best endpoint to the worst, see [`QueryContext::rank`].
+    /// The endpoints which do not answer within [`QueryContext::PROBE_TIMEOUT`] are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_name` - A string that holds the name of a chain
+    /// * `urls` - A vector of urls to query
+    async fn query_ranked(
+        chain_name: String,
+        urls: Vec<Self::QueryInput>,
+    ) -> Result<Vec<Self::QueryOutput>, Self::QueryError> {
+        info!("Probing every endpoint of chain {chain_name}");
+        debug!("Probing the following endpoints: {urls:?}");
+
+        let futures: FuturesUnordered<_> = urls
+            .into_iter()
+            .map(|url| async move {
+                let endpoint = format!("{url:?}");
+
+                match timeout(Self::PROBE_TIMEOUT, Self::query(url)).await {
+                    Ok(result) => result.ok(),
+                    Err(_) => {
+                        warn!(
+                            "Skipping endpoint {endpoint} which did not answer within {:?}",
+                            Self::PROBE_TIMEOUT
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let mut outputs: Vec<_> = futures
+            .filter_map(|output| async move { output })
+            .collect()
+            .await;
+
+        if outputs.is_empty() {
+            return Err(Self::query_error(chain_name));
+        }
+
+        Self::rank(&mut outputs);
+
+        Ok(outputs)
+    }
+}
+
+/// Maximum number of blocks an endpoint can lag behind the most up-to-date endpoint
+/// of the same chain before being ranked after all the endpoints which are up-to-date.
+const MAX_HEIGHT_LAG: u64 = 5;
+
+/// Sort endpoints by whether they support what Hermes needs, then by how far they lag
+/// behind the most up-to-date endpoint, if more than [`MAX_HEIGHT_LAG`] blocks, then by latency.
+fn rank_endpoints<T>(
+    endpoints: &mut [T],
+    supported: impl Fn(&T) -> bool,
+    latest_height: impl Fn(&T) -> u64,
+    latency: impl Fn(&T) -> Duration,
+) {
+    let max_height = endpoints
+        .iter()
+        .map(&latest_height)
+        .max()
+        .unwrap_or_default();
+
+    endpoints.sort_by_key(|endpoint| {
+        let lag = max_height - latest_height(endpoint);
+        let lag = if lag > MAX_HEIGHT_LAG { lag } else { 0 };
+
+        (!supported(endpoint), lag, latency(endpoint))
+    });
 }
 
 // ----------------- RPC ------------------
 
-/// `SimpleHermesRpcQuerier` retrieves `HermesConfigData` by querying a list of RPC endpoints through their WebSocket API
-/// and returns the result of the first endpoint to answer.
+/// Number of blocks over which the average block time of a chain is measured
+const BLOCK_TIME_SAMPLE: u64 = 100;
+
+/// `SimpleHermesRpcQuerier` retrieves `HermesConfigData` by querying a list of RPC endpoints through their WebSocket API.
+/// The endpoints are ranked by whether they index transactions, by latest height and by latency.
 pub struct SimpleHermesRpcQuerier;
 
 /// Data which must be retrieved from RPC endpoints for Hermes
@@ -83,8 +177,12 @@ pub struct HermesConfigData {
     pub rpc_address: Url,
     pub max_block_size: u64,
     pub websocket: Url,
-    // max_block_time should also be retrieved from the RPC
-    // however it looks like it is not in the genesis file anymore
+    /// Average time between the last blocks, if the node still stores them
+    pub block_time: Option<Duration>,
+    pub latest_height: u64,
+    pub latency: Duration,
+    /// Whether the node indexes transactions, which Hermes needs to query packet events
+    pub tx_indexing: bool,
 }
 
 /// Expected Input, Output and Error to query an RPC endpoint
@@ -101,6 +199,16 @@ impl QueryContext for SimpleHermesRpcQuerier {
         RegistryError::no_healthy_rpc(chain_name)
     }
 
+    /// Rank the endpoints which index transactions first
+    fn rank(outputs: &mut [HermesConfigData]) {
+        rank_endpoints(
+            outputs,
+            |data| data.tx_indexing,
+            |data| data.latest_height,
+            |data| data.latency,
+        )
+    }
+
     /// Convert the RPC url to a WebSocket url, query the endpoint, return the data from the RPC.
     async fn query(rpc: Self::QueryInput) -> Result<Self::QueryOutput, Self::QueryError> {
         let websocket_addr = SimpleWebSocketFormatter::parse_or_build_address(rpc.as_str())?;
@@ -117,6 +225,21 @@ impl QueryContext for SimpleHermesRpcQuerier {
 
         let driver_handle = tokio::spawn(driver.run());
 
+        let start = Instant::now();
+
+        let status = client
+            .status()
+            .await
+            .map_err(|e| RegistryError::rpc_status_error(websocket_addr.to_string(), e))?;
+
+        let latency = start.elapsed();
+
+        if status.sync_info.catching_up {
+            return Err(RegistryError::rpc_syncing_error(websocket_addr.to_string()));
+        }
+
+        let latest_height = status.sync_info.latest_block_height.value();
+
         let latest_consensus_params = match client.latest_consensus_params().await {
             Ok(response) => response.consensus_params.block.max_bytes,
             Err(e) => {
@@ -127,6 +250,19 @@ impl QueryContext for SimpleHermesRpcQuerier {
             }
         };
 
+        // Older blocks may have been pruned from the node, in which case the block time is unknown
+        let sampled_height = latest_height.saturating_sub(BLOCK_TIME_SAMPLE).max(1);
+
+        let block_time = match client.block(sampled_height as u32).await {
+            Ok(response) if sampled_height < latest_height => status
+                .sync_info
+                .latest_block_time
+                .duration_since(response.block.header.time)
+                .ok()
+                .map(|elapsed| elapsed / (latest_height - sampled_height) as u32),
+            _ => None,
+        };
+
         client.close().map_err(|e| {
             RegistryError::websocket_conn_close_error(websocket_addr.to_string(), e)
         })?;
@@ -141,20 +277,36 @@ impl QueryContext for SimpleHermesRpcQuerier {
                 .map_err(|e| RegistryError::tendermint_url_parse_error(rpc, e))?,
             max_block_size: latest_consensus_params,
             websocket: websocket_addr,
+            block_time,
+            latest_height,
+            latency,
+            tx_indexing: status.node_info.other.tx_index == TxIndexStatus::On,
         })
     }
 }
 
 // ----------------- GRPC ------------------
 
-/// `GrpcHealthCheckQuerier` connects to a list of gRPC endpoints
-/// and returns the URL of the first one to answer.
+/// `GrpcHealthCheckQuerier` connects to a list of gRPC endpoints and queries their latest block.
+/// The endpoints are ranked by whether they serve the IBC services, by latest height and by latency.
 pub struct GrpcHealthCheckQuerier;
 
+/// Data retrieved from gRPC endpoints to rank them
+#[derive(Clone, Debug)]
+pub struct GrpcEndpointData {
+    pub grpc_address: Url,
+    pub latest_height: u64,
+    pub latency: Duration,
+    /// Whether the node serves the gRPC services of the IBC modules
+    pub ibc_services: bool,
+    /// Unbonding period of the chain, as set in the parameters of its staking module
+    pub unbonding_period: Option<Duration>,
+}
+
 /// Expected Input and Output to query a GRPC endpoint
 impl QueryTypes for GrpcHealthCheckQuerier {
     type QueryInput = Uri;
-    type QueryOutput = Url;
+    type QueryOutput = GrpcEndpointData;
     type QueryError = RegistryError;
 }
 
@@ -165,7 +317,17 @@ impl QueryContext for GrpcHealthCheckQuerier {
         RegistryError::no_healthy_grpc(chain_name)
     }
 
-    /// Query the endpoint and return the GRPC url
+    /// Rank the endpoints which serve the IBC services first
+    fn rank(outputs: &mut [GrpcEndpointData]) {
+        rank_endpoints(
+            outputs,
+            |data| data.ibc_services,
+            |data| data.latest_height,
+            |data| data.latency,
+        )
+    }
+
+    /// Query the latest block, the IBC client parameters and the staking parameters from the endpoint
     async fn query(uri: Self::QueryInput) -> Result<Self::QueryOutput, Self::QueryError> {
         let tendermint_url = uri
             .to_string()
@@ -174,10 +336,142 @@ impl QueryContext for GrpcHealthCheckQuerier {
 
         info!("Querying gRPC server at {tendermint_url}");
 
-        QueryClient::connect(uri)
+        let mut client = ServiceClient::connect(uri.clone())
             .await
             .map_err(|_| RegistryError::unable_to_connect_with_grpc())?;
 
-        Ok(tendermint_url)
+        let start = Instant::now();
+
+        let latest_block = client
+            .get_latest_block(GetLatestBlockRequest {})
+            .await
+            .map_err(|_| RegistryError::unable_to_connect_with_grpc())?
+            .into_inner();
+
+        let latency = start.elapsed();
+
+        let latest_height = latest_block
+            .block
+            .and_then(|block| block.header)
+            .map_or(0, |header| header.height as u64);
+
+        let ibc_services = match IbcClientQueryClient::connect(uri.clone()).await {
+            Ok(mut client) => client
+                .client_params(QueryClientParamsRequest {})
+                .await
+                .is_ok(),
+            Err(_) => false,
+        };
+
+        let unbonding_period = match StakingQueryClient::connect(uri).await {
+            Ok(mut client) => client
+                .params(QueryParamsRequest {})
+                .await
+                .ok()
+                .and_then(|response| response.into_inner().params)
+                .and_then(|params| params.unbonding_time)
+                .map(|unbonding| Duration::new(unbonding.seconds as u64, unbonding.nanos as u32)),
+            Err(_) => None,
+        };
+
+        Ok(GrpcEndpointData {
+            grpc_address: tendermint_url,
+            latest_height,
+            latency,
+            ibc_services,
+            unbonding_period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers with the given number of milliseconds after waiting for that long.
+    struct DelayedQuerier;
+
+    impl QueryTypes for DelayedQuerier {
+        type QueryInput = u64;
+        type QueryOutput = u64;
+        type QueryError = RegistryError;
+    }
+
+    #[async_trait]
+    impl QueryContext for DelayedQuerier {
+        const PROBE_TIMEOUT: Duration = Duration::from_millis(200);
+
+        fn query_error(chain_name: String) -> RegistryError {
+            RegistryError::no_healthy_rpc(chain_name)
+        }
+
+        async fn query(delay_ms: u64) -> Result<u64, RegistryError> {
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            Ok(delay_ms)
+        }
+    }
+
+    #[tokio::test]
+    async fn endpoints_timing_out_are_skipped() {
+        let start = Instant::now();
+        let outputs = DelayedQuerier::query_ranked("chain".to_string(), vec![60_000, 10, 50])
+            .await
+            .unwrap();
+
+        assert_eq!(outputs, vec![10, 50]);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        assert!(
+            DelayedQuerier::query_ranked("chain".to_string(), vec![60_000])
+                .await
+                .is_err()
+        );
+    }
+
+    fn endpoint(
+        name: &'static str,
+        supported: bool,
+        height: u64,
+        latency_ms: u64,
+    ) -> (&'static str, bool, u64, Duration) {
+        (name, supported, height, Duration::from_millis(latency_ms))
+    }
+
+    fn rank(mut endpoints: Vec<(&'static str, bool, u64, Duration)>) -> Vec<&'static str> {
+        rank_endpoints(&mut endpoints, |e| e.1, |e| e.2, |e| e.3);
+        endpoints.into_iter().map(|e| e.0).collect()
+    }
+
+    #[test]
+    fn up_to_date_endpoints_ranked_by_latency() {
+        let ranked = rank(vec![
+            endpoint("slow", true, 100, 300),
+            endpoint("fast", true, 98, 50),
+            endpoint("medium", true, 100, 100),
+        ]);
+
+        assert_eq!(ranked, vec!["fast", "medium", "slow"]);
+    }
+
+    #[test]
+    fn lagging_endpoints_ranked_last() {
+        let ranked = rank(vec![
+            endpoint("pruned", true, 10, 10),
+            endpoint("lagging", true, 90, 10),
+            endpoint("synced", true, 100, 200),
+        ]);
+
+        assert_eq!(ranked, vec!["synced", "lagging", "pruned"]);
+    }
+
+    #[test]
+    fn unsupported_endpoints_ranked_last() {
+        let ranked = rank(vec![
+            endpoint("unsupported", false, 100, 10),
+            endpoint("lagging", true, 50, 300),
+            endpoint("synced", true, 100, 200),
+        ]);
+
+        assert_eq!(ranked, vec!["synced", "lagging", "unsupported"]);
     }
 }
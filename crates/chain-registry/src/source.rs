@@ -0,0 +1,280 @@
+//! Locations from which the resources of the chain registry can be read
+use crate::{
+    constants::{DEFAULT_REF, HOST, PROTOCOL, REGISTRY_PATH},
+    error::RegistryError,
+};
+use flate2::read::GzDecoder;
+use http::uri::Builder;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// `RegistrySource` represents a copy of the [chain registry](https://github.com/cosmos/chain-registry)
+/// from which the chain data, asset lists and IBC paths are read.
+#[derive(Clone, Debug)]
+pub enum RegistrySource {
+    /// The GitHub repository of the chain registry, at the given commit.
+    /// If no commit is given, the latest commit of the default branch is used.
+    GitHub { commit: Option<String> },
+    /// An HTTP server mirroring the content of the repository, such as a caching proxy.
+    /// The resources are fetched at `<url>/<path of the resource in the repository>`.
+    Mirror { url: String },
+    /// A local clone of the repository.
+    Directory { path: PathBuf },
+    /// An archive of the repository, such as the tarballs GitHub provides for every commit.
+    /// Its JSON files are loaded in memory when the source is opened.
+    Archive {
+        path: PathBuf,
+        files: Arc<HashMap<PathBuf, String>>,
+    },
+}
+
+impl Default for RegistrySource {
+    fn default() -> Self {
+        Self::GitHub { commit: None }
+    }
+}
+
+impl RegistrySource {
+    /// Opens the source found at the given location, which is either the URL of a mirror,
+    /// a `.tar` or `.tar.gz` archive, or a local directory.
+    pub fn open(location: &str) -> Result<Self, RegistryError> {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            return Ok(Self::Mirror {
+                url: location.trim_end_matches('/').to_string(),
+            });
+        }
+
+        let path = PathBuf::from(location);
+
+        if is_archive(&path) {
+            let files = read_archive(&path)?;
+
+            Ok(Self::Archive {
+                path,
+                files: Arc::new(files),
+            })
+        } else if path.is_dir() {
+            Ok(Self::Directory { path })
+        } else {
+            Err(RegistryError::invalid_source(location.to_string()))
+        }
+    }
+
+    /// Reads the content of the resource at the given path of the repository.
+    pub async fn read(&self, path: &Path) -> Result<String, RegistryError> {
+        let resource = path
+            .to_str()
+            .ok_or_else(|| RegistryError::path_error(path.to_path_buf()))?;
+
+        match self {
+            Self::GitHub { commit } => {
+                let url = Builder::new()
+                    .scheme(PROTOCOL)
+                    .authority(HOST)
+                    .path_and_query(
+                        format!(
+                            "{}/{}/{}",
+                            REGISTRY_PATH,
+                            commit.as_deref().unwrap_or(DEFAULT_REF),
+                            resource,
+                        )
+                        .as_str(),
+                    )
+                    .build()
+                    .map_err(|e| RegistryError::url_parse_error(resource.to_string(), e))?;
+
+                fetch_url(url.to_string()).await
+            }
+            Self::Mirror { url } => fetch_url(format!("{url}/{resource}")).await,
+            Self::Directory { path: root } => {
+                let file = root.join(path);
+                std::fs::read_to_string(&file).map_err(|e| RegistryError::io_error(file, e))
+            }
+            Self::Archive {
+                path: archive,
+                files,
+            } => files.get(path).cloned().ok_or_else(|| {
+                RegistryError::missing_resource(path.to_path_buf(), archive.to_path_buf())
+            }),
+        }
+    }
+}
+
+async fn fetch_url(url: String) -> Result<String, RegistryError> {
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| RegistryError::request_error(url.clone(), e))?;
+
+    if response.status().is_success() {
+        response
+            .text()
+            .await
+            .map_err(|e| RegistryError::request_error(url, e))
+    } else {
+        Err(RegistryError::status_error(url, response.status().as_u16()))
+    }
+}
+
+fn is_archive(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Loads the JSON files of the archive at the given path, indexed by their path in the
+/// repository. Archives of a repository usually nest its content in a top-level directory,
+/// eg. `chain-registry-master/`, which is therefore stripped from the paths.
+fn read_archive(path: &Path) -> Result<HashMap<PathBuf, String>, RegistryError> {
+    let file = File::open(path).map_err(|e| RegistryError::io_error(path.to_path_buf(), e))?;
+
+    let reader: Box<dyn Read> = if path.to_string_lossy().ends_with(".tar") {
+        Box::new(file)
+    } else {
+        Box::new(GzDecoder::new(file))
+    };
+
+    let io_error = |e| RegistryError::io_error(path.to_path_buf(), e);
+
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries().map_err(io_error)? {
+        let mut entry = entry.map_err(io_error)?;
+        let entry_path = entry.path().map_err(io_error)?.into_owned();
+
+        if !entry.header().entry_type().is_file()
+            || entry_path.extension().map_or(true, |ext| ext != "json")
+        {
+            continue;
+        }
+
+        let mut content = String::new();
+        entry.read_to_string(&mut content).map_err(io_error)?;
+
+        entries.push((entry_path, content));
+    }
+
+    let top_level_dir = entries
+        .first()
+        .and_then(|(entry_path, _)| entry_path.components().next())
+        .map(|component| PathBuf::from(component.as_os_str()));
+
+    let nested = top_level_dir.map_or(false, |dir| {
+        entries.iter().all(|(entry_path, _)| {
+            entry_path.components().count() > 1 && entry_path.starts_with(&dir)
+        })
+    });
+
+    Ok(entries
+        .into_iter()
+        .map(|(entry_path, content)| {
+            let entry_path = if nested {
+                entry_path.components().skip(1).collect()
+            } else {
+                entry_path
+            };
+
+            (entry_path, content)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asset_list::AssetList, fetchable::Fetchable};
+    use flate2::{write::GzEncoder, Compression};
+
+    const ASSET_LIST: &str = r#"{
+        "chain_name": "test",
+        "assets": [
+            {
+                "description": "test",
+                "denom_units": [],
+                "base": "utest",
+                "name": "test",
+                "display": "test",
+                "symbol": "TEST",
+                "logo_URIs": {},
+                "coingecko_id": "test"
+            }
+        ]
+    }"#;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("chain-registry-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn open_mirror() {
+        match RegistrySource::open("https://mirror.example.com/chain-registry/").unwrap() {
+            RegistrySource::Mirror { url } => {
+                assert_eq!(url, "https://mirror.example.com/chain-registry")
+            }
+            source => panic!("unexpected source: {source:?}"),
+        }
+    }
+
+    #[test]
+    fn open_missing_directory() {
+        assert!(RegistrySource::open("/does/not/exist").is_err());
+    }
+
+    #[tokio::test]
+    async fn fetch_from_directory() -> Result<(), RegistryError> {
+        let dir = test_dir("directory");
+        std::fs::create_dir_all(dir.join("test")).unwrap();
+        std::fs::write(dir.join("test/assetlist.json"), ASSET_LIST).unwrap();
+
+        let source = RegistrySource::open(dir.to_str().unwrap())?;
+        let assets = AssetList::fetch_from("test".to_string(), &source).await?;
+        assert_eq!(assets.assets[0].base, "utest");
+
+        assert!(AssetList::fetch_from("other".to_string(), &source)
+            .await
+            .is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fetch_from_archive() -> Result<(), RegistryError> {
+        let dir = test_dir("archive");
+        let archive = dir.join("chain-registry.tar.gz");
+
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive).unwrap(),
+            Compression::default(),
+        ));
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(ASSET_LIST.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        builder
+            .append_data(
+                &mut header,
+                "chain-registry-master/test/assetlist.json",
+                ASSET_LIST.as_bytes(),
+            )
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let source = RegistrySource::open(archive.to_str().unwrap())?;
+        let assets = AssetList::fetch_from("test".to_string(), &source).await?;
+        assert_eq!(assets.assets[0].base, "utest");
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
@@ -5,9 +5,10 @@ use ibc_chain_registry::{
     chain::ChainData,
     error::RegistryError,
     fetchable::Fetchable,
-    formatter::{SimpleGrpcFormatter, UriFormatter},
+    formatter::{SimpleGrpcFormatter, SimpleWebSocketFormatter, UriFormatter},
     paths::IBCPath,
     querier::*,
+    source::RegistrySource,
 };
 
 use futures::future::join_all;
@@ -15,15 +16,16 @@ use http::Uri;
 
 use ibc_relayer::{
     config::{
+        dynamic_gas::DynamicGasPrice,
         filter::{ChannelFilters, FilterPattern, PacketFilter},
         gas_multiplier::GasMultiplier,
         types::{MaxMsgNum, MaxTxSize, Memo},
-        {default, AddressType, ChainConfig, GasPrice},
+        {default, AddressType, ChainConfig, EventSource, GasPrice},
     },
     keyring::Store,
 };
 
-use std::{collections::HashMap, marker::Send};
+use std::{collections::HashMap, marker::Send, str::FromStr, time::Duration};
 
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tendermint_rpc::Url;
@@ -61,15 +63,153 @@ fn construct_packet_filters(ibc_paths: Vec<IBCPath>) -> HashMap<String, PacketFi
         .collect()
 }
 
+/// Gas price used when the chain registry does not list any for the fee token of a chain.
+const DEFAULT_GAS_PRICE: f64 = 0.1;
+
+/// Picks the gas price of the first fee token listed in the chain registry, preferring its average
+/// gas price over its low and minimum ones. Falls back to the first asset of the chain if no fee token is listed.
+fn gas_price(chain_data: &ChainData, assets: &AssetList) -> Result<GasPrice, RegistryError> {
+    if let Some(fee_token) = chain_data.fees.fee_tokens.first() {
+        let price = [
+            fee_token.average_gas_price,
+            fee_token.low_gas_price,
+            fee_token.fixed_min_gas_price,
+        ]
+        .into_iter()
+        .find(|price| *price > 0.0)
+        .unwrap_or(DEFAULT_GAS_PRICE);
+
+        return Ok(GasPrice::new(price, fee_token.denom.clone()));
+    }
+
+    let asset = assets
+        .assets
+        .first()
+        .ok_or_else(|| RegistryError::no_asset_found(chain_data.chain_name.to_string()))?;
+
+    Ok(GasPrice::new(DEFAULT_GAS_PRICE, asset.base.clone()))
+}
+
+/// Chains using the Ethereum coin type or key algorithm derive their addresses from Ethermint keys.
+fn address_type(chain_data: &ChainData) -> AddressType {
+    const ETHEREUM_COIN_TYPE: u32 = 60;
+
+    let ethermint = chain_data.slip44 == ETHEREUM_COIN_TYPE
+        || chain_data
+            .key_algos
+            .iter()
+            .any(|algo| algo == "ethsecp256k1");
+
+    if !ethermint {
+        return AddressType::Cosmos;
+    }
+
+    let pk_type = if chain_data.bech32_prefix == "inj" {
+        "/injective.crypto.v1beta1.ethsecp256k1.PubKey"
+    } else {
+        "/ethermint.crypto.v1.ethsecp256k1.PubKey"
+    };
+
+    AddressType::Ethermint {
+        pk_type: pk_type.to_string(),
+    }
+}
+
+/// Leaves room for blocks to take up to three times longer than the chain's average block time.
+fn max_block_time(block_time: Option<Duration>) -> Duration {
+    block_time
+        .map(|block_time| block_time * 3)
+        .unwrap_or_default()
+        .max(default::max_block_time())
+}
+
+/// The RPC and gRPC endpoints of a chain, best first, along with the
+/// data learnt from probing them, if they were probed.
+struct Endpoints {
+    /// The RPC addresses and the corresponding WebSocket addresses
+    rpc: Vec<(Url, Url)>,
+    grpc: Vec<Url>,
+    block_time: Option<Duration>,
+    unbonding_period: Option<Duration>,
+}
+
+/// Queries the given endpoints, keeping the healthy ones ranked from best to worst.
+async fn probe_endpoints<GrpcQuerier, RpcQuerier>(
+    chain_name: &str,
+    rpc_endpoints: Vec<String>,
+    grpc_endpoints: Vec<Uri>,
+) -> Result<Endpoints, RegistryError>
+where
+    GrpcQuerier: QueryContext<QueryInput = Uri, QueryOutput = GrpcEndpointData, QueryError = RegistryError>
+        + Send,
+    RpcQuerier: QueryContext<
+            QueryInput = String,
+            QueryOutput = HermesConfigData,
+            QueryError = RegistryError,
+        > + Send,
+{
+    let rpc_data = RpcQuerier::query_ranked(chain_name.to_string(), rpc_endpoints).await?;
+    let grpc_data = GrpcQuerier::query_ranked(chain_name.to_string(), grpc_endpoints).await?;
+
+    Ok(Endpoints {
+        block_time: rpc_data.first().and_then(|data| data.block_time),
+        unbonding_period: grpc_data.iter().find_map(|data| data.unbonding_period),
+        rpc: rpc_data
+            .into_iter()
+            .map(|data| (data.rpc_address, data.websocket))
+            .collect(),
+        grpc: grpc_data
+            .into_iter()
+            .map(|data| data.grpc_address)
+            .collect(),
+    })
+}
+
+/// Takes the given endpoints in the order they are listed in, without querying them.
+fn listed_endpoints(
+    rpc_endpoints: Vec<String>,
+    grpc_endpoints: Vec<Uri>,
+) -> Result<Endpoints, RegistryError> {
+    let rpc = rpc_endpoints
+        .into_iter()
+        .map(|rpc| {
+            let websocket = SimpleWebSocketFormatter::parse_or_build_address(&rpc)?;
+            let rpc_address = Url::from_str(&rpc)
+                .map_err(|e| RegistryError::tendermint_url_parse_error(rpc, e))?;
+
+            Ok((rpc_address, websocket))
+        })
+        .collect::<Result<_, RegistryError>>()?;
+
+    let grpc = grpc_endpoints
+        .into_iter()
+        .map(|grpc| {
+            Url::from_str(&grpc.to_string())
+                .map_err(|e| RegistryError::tendermint_url_parse_error(grpc.to_string(), e))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Endpoints {
+        rpc,
+        grpc,
+        block_time: None,
+        unbonding_period: None,
+    })
+}
+
 /// Generates a ChainConfig for a given chain from ChainData, AssetList, and an optional PacketFilter.
+///
+/// If `probe` is true, the endpoints listed in the chain registry are queried to pick the
+/// healthy ones, best first. Otherwise they are used in the order they are listed in.
 async fn hermes_config<GrpcQuerier, RpcQuerier, GrpcFormatter>(
     chain_data: ChainData,
     assets: AssetList,
     packet_filter: Option<PacketFilter>,
+    probe: bool,
 ) -> Result<ChainConfig, RegistryError>
 where
-    GrpcQuerier:
-        QueryContext<QueryInput = Uri, QueryOutput = Url, QueryError = RegistryError> + Send,
+    GrpcQuerier: QueryContext<QueryInput = Uri, QueryOutput = GrpcEndpointData, QueryError = RegistryError>
+        + Send,
     RpcQuerier: QueryContext<
             QueryInput = String,
             QueryOutput = HermesConfigData,
@@ -77,12 +217,10 @@ where
         > + Send,
     GrpcFormatter: UriFormatter<OutputFormat = Uri>,
 {
-    let chain_name = chain_data.chain_name;
+    let gas_price = gas_price(&chain_data, &assets)?;
+    let address_type = address_type(&chain_data);
 
-    let asset = assets
-        .assets
-        .first()
-        .ok_or_else(|| RegistryError::no_asset_found(chain_name.to_string()))?;
+    let chain_name = chain_data.chain_name;
 
     let grpc_endpoints = chain_data
         .apis
@@ -98,18 +236,48 @@ where
         .map(|rpc| rpc.address.to_owned())
         .collect();
 
-    let rpc_data = RpcQuerier::query_healthy(chain_name.to_string(), rpc_endpoints).await?;
-    let grpc_address = GrpcQuerier::query_healthy(chain_name.to_string(), grpc_endpoints).await?;
+    let endpoints = if probe {
+        probe_endpoints::<GrpcQuerier, RpcQuerier>(&chain_name, rpc_endpoints, grpc_endpoints)
+            .await?
+    } else {
+        listed_endpoints(rpc_endpoints, grpc_endpoints)?
+    };
+
+    // The first endpoints are used, the other ones are kept as fallbacks
+    let mut rpc_endpoints = endpoints.rpc.into_iter();
+    let mut grpc_endpoints = endpoints.grpc.into_iter();
+
+    let (rpc_address, websocket_addr) = match rpc_endpoints.next() {
+        Some(best) => best,
+        None => return Err(RegistryError::no_healthy_rpc(chain_name)),
+    };
+
+    let (fallback_rpc_addrs, fallback_websocket_addrs) = rpc_endpoints.unzip();
+
+    let grpc_address = match grpc_endpoints.next() {
+        Some(best) => best,
+        None => return Err(RegistryError::no_healthy_grpc(chain_name)),
+    };
+
+    // Trust headers for two thirds of the unbonding period, as Hermes does by default
+    let trusting_period = endpoints
+        .unbonding_period
+        .map(|unbonding_period| 2 * unbonding_period / 3);
 
     Ok(ChainConfig {
         id: chain_data.chain_id,
         r#type: default::chain_type(),
-        rpc_addr: rpc_data.rpc_address,
-        websocket_addr: rpc_data.websocket,
+        rpc_addr: rpc_address,
+        websocket_addr,
         grpc_addr: grpc_address,
+        witness_addrs: Vec::new(),
+        fallback_rpc_addrs,
+        fallback_grpc_addrs: grpc_endpoints.collect(),
+        fallback_websocket_addrs,
         rpc_timeout: default::rpc_timeout(),
         account_prefix: chain_data.bech32_prefix,
         key_name: String::new(),
+        extra_key_names: Vec::new(),
         key_store_type: Store::default(),
         store_prefix: "ibc".to_string(),
         default_gas: Some(100000),
@@ -120,17 +288,19 @@ where
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
         clock_drift: default::clock_drift(),
-        max_block_time: default::max_block_time(),
-        trusting_period: None,
+        max_block_time: max_block_time(endpoints.block_time),
+        trusting_period,
         memo_prefix: Memo::default(),
         proof_specs: Default::default(),
         trust_threshold: TrustThreshold::default(),
-        gas_price: GasPrice {
-            price: 0.1,
-            denom: asset.base.to_owned(),
-        },
+        gas_price,
+        dynamic_gas_price: DynamicGasPrice::default(),
+        gas_estimation: Default::default(),
         packet_filter: packet_filter.unwrap_or_default(),
-        address_type: AddressType::default(),
+        fee_filter: None,
+        excluded_sequences: Default::default(),
+        address_type,
+        event_source: EventSource::default(),
         sequential_batch_tx: false,
         extension_options: Vec::new(),
     })
@@ -138,14 +308,14 @@ where
 
 async fn get_handles<T: Fetchable + Send + 'static>(
     resources: &[String],
-    commit: &Option<String>,
+    source: &RegistrySource,
 ) -> Vec<JoinHandle<Result<T, RegistryError>>> {
     let handles = resources
         .iter()
         .map(|resource| {
             let resource = resource.to_string();
-            let commit = commit.clone();
-            tokio::spawn(async move { T::fetch(resource, commit).await })
+            let source = source.clone();
+            tokio::spawn(async move { T::fetch_from(resource, &source).await })
         })
         .collect();
     handles
@@ -164,23 +334,28 @@ async fn get_data_from_handles<T>(
 }
 
 /// Generates a `Vec<ChainConfig>` for a slice of chains names by fetching data from
-/// <https://github.com/cosmos/chain-registry>. Gas settings are set to default values.
+/// <https://github.com/cosmos/chain-registry>, or a copy of it. The gas price is taken from the
+/// fee tokens of the chain, while the gas limits are set to default values.
 ///
 /// # Arguments
 ///
 /// * `chains` - A slice of strings that holds the name of the chains for which a `ChainConfig` will be generated. It must be sorted.
-/// * `commit` - An optional String representing the commit hash from which the chain configs will be generated. If it's None, the latest commit will be used.
+/// * `source` - The copy of the chain registry from which the chain configs will be generated, eg. the GitHub repository at a given commit.
+/// * `probe` - Whether to query the RPC and gRPC endpoints listed in the chain registry to pick the healthy ones,
+///   instead of using them in the order they are listed in.
 ///
 /// # Example
 ///
 /// ```
+/// use ibc_chain_registry::source::RegistrySource;
 /// use ibc_relayer_cli::chain_registry::get_configs;
 /// let chains = &vec!["cosmoshub".to_string(), "osmosis".to_string()];
-/// let configs = get_configs(chains, None);
+/// let configs = get_configs(chains, RegistrySource::default(), true);
 /// ```
 pub async fn get_configs(
     chains: &[String],
-    commit: Option<String>,
+    source: RegistrySource,
+    probe: bool,
 ) -> Result<Vec<ChainConfig>, RegistryError> {
     let n = chains.len();
     if n == 0 {
@@ -188,17 +363,17 @@ pub async fn get_configs(
     }
 
     // Spawn tasks to fetch data from the chain-registry
-    let chain_data_handle = get_handles::<ChainData>(chains, &commit).await;
-    let asset_lists_handle = get_handles::<AssetList>(chains, &commit).await;
+    let chain_data_handle = get_handles::<ChainData>(chains, &source).await;
+    let asset_lists_handle = get_handles::<AssetList>(chains, &source).await;
 
     let mut path_handles = Vec::with_capacity(n * (n - 1) / 2);
     for i in 0..n {
         for chain_j in &chains[i + 1..] {
             let chain_i = &chains[i];
             let resource = format!("{}-{}.json", chain_i, chain_j).to_string();
-            let commit_clone = commit.clone();
+            let source = source.clone();
             path_handles.push(tokio::spawn(async move {
-                IBCPath::fetch(resource, commit_clone).await
+                IBCPath::fetch_from(resource, &source).await
             }));
         }
     }
@@ -230,7 +405,7 @@ pub async fn get_configs(
                         GrpcHealthCheckQuerier,
                         SimpleHermesRpcQuerier,
                         SimpleGrpcFormatter,
-                    >(chain_data, assets, packet_filter)
+                    >(chain_data, assets, packet_filter, probe)
                     .await
             })
         })
@@ -244,9 +419,24 @@ mod tests {
     use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
     use std::str::FromStr;
 
+    /// A copy of the chain registry with a few chains, whose endpoints are not reachable,
+    /// so the configs are generated from it without probing them.
+    fn fixture_source() -> RegistrySource {
+        RegistrySource::Directory {
+            path: [
+                env!("CARGO_MANIFEST_DIR"),
+                "tests",
+                "fixtures",
+                "chain-registry",
+            ]
+            .iter()
+            .collect(),
+        }
+    }
+
     // Helper function for configs without filter
     async fn should_have_no_filter(test_chains: &[String]) -> Result<(), RegistryError> {
-        let configs = get_configs(test_chains, None).await?;
+        let configs = get_configs(test_chains, fixture_source(), false).await?;
         for config in configs {
             match config.packet_filter {
                 PacketFilter::AllowAll => {}
@@ -258,7 +448,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn fetch_chain_config_with_packet_filters() -> Result<(), RegistryError> {
         let test_chains: &[String] = &[
             "cosmoshub".to_string(),
@@ -266,7 +455,8 @@ mod tests {
             "osmosis".to_string(),
         ]; // Must be sorted
 
-        let configs = get_configs(test_chains, None).await?;
+        let configs = get_configs(test_chains, fixture_source(), false).await?;
+        assert_eq!(configs.len(), 3);
 
         for config in configs {
             match config.packet_filter {
@@ -343,14 +533,12 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore]
     async fn fetch_chain_config_without_packet_filters() -> Result<(), RegistryError> {
         let test_chains: &[String] = &["cosmoshub".to_string(), "evmos".to_string()]; // Must be sorted
         should_have_no_filter(test_chains).await
     }
 
     #[tokio::test]
-    #[ignore]
     async fn fetch_one_chain() -> Result<(), RegistryError> {
         let test_chains: &[String] = &["cosmoshub".to_string()]; // Must be sorted
         should_have_no_filter(test_chains).await
@@ -359,10 +547,126 @@ mod tests {
     #[tokio::test]
     async fn fetch_no_chain() -> Result<(), RegistryError> {
         let test_chains: &[String] = &[];
-        let configs = get_configs(test_chains, None).await?;
+        let configs = get_configs(test_chains, RegistrySource::default(), true).await?;
 
         assert_eq!(configs.len(), 0);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn fetch_chain_config_with_listed_endpoints() -> Result<(), RegistryError> {
+        let test_chains: &[String] = &["cosmoshub".to_string()];
+        let configs = get_configs(test_chains, fixture_source(), false).await?;
+
+        let config = &configs[0];
+        assert_eq!(config.id.as_str(), "cosmoshub-4");
+        assert_eq!(config.gas_price, GasPrice::new(0.025, "uatom".to_string()));
+
+        // The endpoints are taken in the order they are listed in
+        assert_eq!(
+            config.rpc_addr.to_string(),
+            "https://cosmos-rpc.example.com/"
+        );
+        assert_eq!(
+            config.websocket_addr.to_string(),
+            "wss://cosmos-rpc.example.com/websocket"
+        );
+        assert_eq!(
+            config.grpc_addr.to_string(),
+            "https://cosmos-grpc.example.com:9090/"
+        );
+        assert_eq!(config.fallback_rpc_addrs.len(), 1);
+        assert_eq!(config.fallback_websocket_addrs.len(), 1);
+        assert_eq!(config.fallback_grpc_addrs.len(), 1);
+        assert_eq!(
+            config.fallback_grpc_addrs[0].to_string(),
+            "https://cosmos-grpc-2.example.com/"
+        );
+
+        // Without probing, neither the block time nor the unbonding period are known
+        assert_eq!(config.max_block_time, default::max_block_time());
+        assert_eq!(config.trusting_period, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_price_from_fee_token() {
+        use ibc_chain_registry::chain::{FeeToken, FeeTokens};
+
+        let mut chain_data = ChainData {
+            fees: FeeTokens {
+                fee_tokens: vec![FeeToken {
+                    denom: "uosmo".to_string(),
+                    low_gas_price: 0.0025,
+                    ..Default::default()
+                }],
+            },
+            ..Default::default()
+        };
+
+        let price = gas_price(&chain_data, &AssetList::default()).unwrap();
+        assert_eq!(price, GasPrice::new(0.0025, "uosmo".to_string()));
+
+        chain_data.fees.fee_tokens[0].average_gas_price = 0.025;
+
+        let price = gas_price(&chain_data, &AssetList::default()).unwrap();
+        assert_eq!(price, GasPrice::new(0.025, "uosmo".to_string()));
+    }
+
+    #[test]
+    fn gas_price_without_fee_token() {
+        use ibc_chain_registry::asset_list::Asset;
+
+        let assets = AssetList {
+            assets: vec![Asset {
+                base: "uatom".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let price = gas_price(&ChainData::default(), &assets).unwrap();
+        assert_eq!(price, GasPrice::new(DEFAULT_GAS_PRICE, "uatom".to_string()));
+
+        assert!(gas_price(&ChainData::default(), &AssetList::default()).is_err());
+    }
+
+    #[test]
+    fn ethermint_address_type() {
+        let chain_data = ChainData {
+            bech32_prefix: "evmos".to_string(),
+            slip44: 60,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            address_type(&chain_data),
+            AddressType::Ethermint {
+                pk_type: "/ethermint.crypto.v1.ethsecp256k1.PubKey".to_string()
+            }
+        );
+
+        let chain_data = ChainData {
+            bech32_prefix: "cosmos".to_string(),
+            slip44: 118,
+            ..Default::default()
+        };
+
+        assert_eq!(address_type(&chain_data), AddressType::Cosmos);
+    }
+
+    #[test]
+    fn max_block_time_from_block_time() {
+        assert_eq!(max_block_time(None), default::max_block_time());
+        assert_eq!(
+            max_block_time(Some(Duration::from_secs(6))),
+            default::max_block_time()
+        );
+        assert_eq!(
+            max_block_time(Some(Duration::from_secs(20))),
+            Duration::from_secs(60)
+        );
+    }
 }
@@ -4,6 +4,7 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 mod auto;
+mod reload;
 mod validate;
 
 /// `config` subcommand
@@ -14,4 +15,7 @@ pub enum ConfigCmd {
 
     ///Automatically generate a configuration file by fetching data from the chain-registry. If a pair of chains exists in the _IBC folder of the chain-registry then a corresponding packet filter is added to the configuration
     Auto(auto::AutoCmd),
+
+    /// Reload the configuration file and apply the changes to the chains of a running instance of Hermes
+    Reload(reload::ReloadCmd),
 }
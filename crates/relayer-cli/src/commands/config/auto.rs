@@ -6,8 +6,10 @@ use abscissa_core::{
 
 use crate::conclude::Output;
 
+use ibc_chain_registry::source::RegistrySource;
+
 use ibc_relayer::{
-    config::{store, ChainConfig, Config},
+    config::{default, load, store, ChainConfig, Config},
     keyring::list_keys,
 };
 
@@ -19,14 +21,50 @@ fn find_key(chain_config: &ChainConfig) -> Option<String> {
     keys.into_iter().next().map(|(name, _)| name)
 }
 
+/// Overwrites the settings of an existing chain config which are derived from the chain registry,
+/// keeping the ones specific to this relayer, such as its keys, filters and fee settings.
+///
+/// The gas price is always kept, as is the `max_block_time` unless it has its default value,
+/// since they may have been tuned, and the trusting period is only overwritten when it could
+/// be derived from the unbonding period of the chain.
+fn merge_chain_config(existing: &mut ChainConfig, generated: ChainConfig) {
+    existing.rpc_addr = generated.rpc_addr;
+    existing.websocket_addr = generated.websocket_addr;
+    existing.grpc_addr = generated.grpc_addr;
+    existing.fallback_rpc_addrs = generated.fallback_rpc_addrs;
+    existing.fallback_grpc_addrs = generated.fallback_grpc_addrs;
+    existing.fallback_websocket_addrs = generated.fallback_websocket_addrs;
+    existing.account_prefix = generated.account_prefix;
+    existing.address_type = generated.address_type;
+
+    if existing.max_block_time == default::max_block_time() {
+        existing.max_block_time = generated.max_block_time;
+    }
+
+    if generated.trusting_period.is_some() {
+        existing.trusting_period = generated.trusting_period;
+    }
+}
+
 /// The data structure that represents the arguments when invoking the `config auto` CLI command.
 ///
-/// The command has two required arguments and an optional one which is used to manually specify commit hash of the chain-registry from which the chain configs will be generated:
+/// The command has two required arguments and two optional ones which are used to manually specify either the commit hash of the chain-registry
+/// or a copy of the chain-registry, from which the chain configs will be generated:
+///
+/// `config auto [OPTIONS] --output <PATH> --chains <CHAIN_NAME_1[:<KEY_1>] CHAIN_NAME_2[:<KEY_2>]...> [--commit <COMMIT_HASH> | --registry-path <PATH_OR_URL>] [--no-probe]`
+///
+/// The copy of the chain-registry can be a local clone of the repository, a `.tar` or `.tar.gz` archive of it,
+/// or the URL of a mirror serving its files, which allows generating configs without access to GitHub.
 ///
-/// `config auto [OPTIONS] --output <PATH> --chains <CHAIN_NAME_1[:<KEY_1>] CHAIN_NAME_2[:<KEY_2>]...> [--commit <COMMIT_HASH>]`
+/// The RPC and gRPC endpoints listed in the chain-registry are queried to pick the healthy ones, unless `--no-probe`
+/// is given, in which case they are used in the order they are listed in, so that configs can be generated offline.
 ///
 /// If no key is specified, the first key stored in the KEYSTORE_DEFAULT_FOLDER, if it exists, will be used otherwise the field `key_name` will be left empty.
 /// If a is specified then it will be used without verifying that it exists.
+///
+/// If the configuration file already exists, the generated chain configs are merged into it: the settings of the chains
+/// it already contains are updated from the chain-registry, while its other settings and chains are left untouched.
+/// The file is written anew from the merged configuration, so its comments and formatting are not preserved.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
     override_usage = "hermes config auto [OPTIONS] --output <PATH> --chains <CHAIN_NAME:OPTIONAL_KEY_NAME>"
@@ -57,6 +95,20 @@ pub struct AutoCmd {
         help = "Commit hash from which the chain configs will be generated. If it's not set, the latest commit will be used."
     )]
     commit: Option<String>,
+
+    #[clap(
+        long = "registry-path",
+        value_name = "PATH_OR_URL",
+        conflicts_with = "commit",
+        help = "Path to a local clone or a .tar(.gz) archive of the chain registry, or URL of a mirror serving its files, from which the chain configs will be generated instead of GitHub."
+    )]
+    registry_path: Option<String>,
+
+    #[clap(
+        long = "no-probe",
+        help = "Use the RPC and gRPC endpoints in the order they are listed in the chain registry, instead of querying them to pick the healthy ones."
+    )]
+    no_probe: bool,
 }
 
 fn extract_chains_and_keys(chain_names: &[String]) -> Vec<(String, Option<String>)> {
@@ -86,24 +138,58 @@ impl Runnable for AutoCmd {
             .cloned()
             .collect::<Vec<_>>();
 
-        let commit = self.commit.clone();
+        let source = match &self.registry_path {
+            Some(location) => match RegistrySource::open(location) {
+                Ok(source) => source,
+                Err(e) => Output::error(e.to_string()).exit(),
+            },
+            None => RegistrySource::GitHub {
+                commit: self.commit.clone(),
+            },
+        };
 
         // Extract keys and sort chains by name
         // Fetch chain configs from the chain registry
         info!("Fetching configuration for chains: {sorted_names:?}");
 
-        match runtime.block_on(get_configs(&sorted_names, commit)) {
-            Ok(mut chain_configs) => {
+        match runtime.block_on(get_configs(&sorted_names, source, !self.no_probe)) {
+            Ok(chain_configs) => {
+                let mut config = if self.path.exists() {
+                    match load(&self.path) {
+                        Ok(config) => config,
+                        Err(e) => Output::error(e.to_string()).exit(),
+                    }
+                } else {
+                    Config::default()
+                };
+
                 let configs_and_keys = chain_configs
-                    .iter_mut()
+                    .into_iter()
                     .zip(names_and_keys.iter().map(|n| &n.1).cloned());
 
-                for (mut chain_config, key_option) in configs_and_keys {
+                for (generated, key_option) in configs_and_keys {
+                    let existing = config
+                        .chains
+                        .iter()
+                        .position(|chain_config| chain_config.id == generated.id);
+
+                    let chain_config = match existing {
+                        Some(index) => {
+                            info!("{}: updates existing configuration", &generated.id);
+                            merge_chain_config(&mut config.chains[index], generated);
+                            &mut config.chains[index]
+                        }
+                        None => {
+                            config.chains.push(generated);
+                            config.chains.last_mut().unwrap()
+                        }
+                    };
+
                     // If a key is provided, use it
                     if let Some(key_name) = key_option {
                         info!("{}: uses key \"{}\"", &chain_config.id, &key_name);
                         chain_config.key_name = key_name;
-                    } else {
+                    } else if chain_config.key_name.is_empty() {
                         // Otherwise, find the key in the keystore
                         let chain_id = &chain_config.id;
                         let key = find_key(chain_config);
@@ -117,14 +203,9 @@ impl Runnable for AutoCmd {
                     }
                 }
 
-                let config = Config {
-                    chains: chain_configs,
-                    ..Config::default()
-                };
-
                 match store(&config, &self.path) {
                     Ok(_) => {
-                        warn!("Gas parameters are set to default values.");
+                        warn!("Gas limits are set to default values.");
                         Output::success(format!(
                             "Config file written successfully : {}.",
                             self.path.to_str().unwrap()
@@ -143,10 +224,61 @@ impl Runnable for AutoCmd {
 
 #[cfg(test)]
 mod tests {
-    use super::AutoCmd;
+    use super::{merge_chain_config, AutoCmd};
     use abscissa_core::clap::Parser;
+    use core::time::Duration;
+    use ibc_relayer::config::{ChainConfig, GasPrice};
     use std::path::PathBuf;
 
+    fn chain_config() -> ChainConfig {
+        serde_json::from_value(serde_json::json!({
+            "id": "cosmoshub-4",
+            "rpc_addr": "http://127.0.0.1:26657",
+            "grpc_addr": "http://127.0.0.1:9090",
+            "websocket_addr": "ws://127.0.0.1:26657/websocket",
+            "account_prefix": "cosmos",
+            "key_name": "relayer",
+            "store_prefix": "ibc",
+            "gas_price": { "price": 0.001, "denom": "uatom" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn merge_generated_chain_config() {
+        let mut existing = chain_config();
+        existing.key_name = "hermes".to_string();
+        existing.gas_price = GasPrice::new(0.005, "uatom".to_string());
+        existing.trusting_period = Some(Duration::from_secs(3600));
+
+        let mut generated = chain_config();
+        generated.rpc_addr = "https://rpc.cosmos.network".parse().unwrap();
+        generated.gas_price = GasPrice::new(0.025, "uatom".to_string());
+        generated.max_block_time = Duration::from_secs(45);
+        generated.trusting_period = None;
+
+        merge_chain_config(&mut existing, generated.clone());
+
+        assert_eq!(existing.rpc_addr, generated.rpc_addr);
+        assert_eq!(existing.key_name, "hermes");
+        assert_eq!(
+            existing.gas_price,
+            GasPrice::new(0.005, "uatom".to_string())
+        );
+        assert_eq!(existing.max_block_time, Duration::from_secs(45));
+        assert_eq!(existing.trusting_period, Some(Duration::from_secs(3600)));
+
+        // A tuned `max_block_time` is kept, and the derived trusting period is used
+        existing.max_block_time = Duration::from_secs(60);
+        generated.max_block_time = Duration::from_secs(15);
+        generated.trusting_period = Some(Duration::from_secs(7200));
+
+        merge_chain_config(&mut existing, generated);
+
+        assert_eq!(existing.max_block_time, Duration::from_secs(60));
+        assert_eq!(existing.trusting_period, Some(Duration::from_secs(7200)));
+    }
+
     #[test]
     fn auto_config_without_commit() {
         assert_eq!(
@@ -154,6 +286,8 @@ mod tests {
                 path: PathBuf::from("./example.toml"),
                 chain_names: vec!["chain1:key1".to_string(), "chain2".to_string()],
                 commit: None,
+                registry_path: None,
+                no_probe: false,
             },
             AutoCmd::parse_from([
                 "test",
@@ -173,6 +307,8 @@ mod tests {
                 path: PathBuf::from("./example.toml"),
                 chain_names: vec!["chain1:key1".to_string(), "chain2".to_string()],
                 commit: Some("test_commit".to_string()),
+                registry_path: None,
+                no_probe: false,
             },
             AutoCmd::parse_from([
                 "test",
@@ -186,4 +322,66 @@ mod tests {
             ])
         )
     }
+
+    #[test]
+    fn auto_config_with_registry_path() {
+        assert_eq!(
+            AutoCmd {
+                path: PathBuf::from("./example.toml"),
+                chain_names: vec!["chain1:key1".to_string(), "chain2".to_string()],
+                commit: None,
+                registry_path: Some("./chain-registry".to_string()),
+                no_probe: false,
+            },
+            AutoCmd::parse_from([
+                "test",
+                "--output",
+                "./example.toml",
+                "--chains",
+                "chain1:key1",
+                "chain2",
+                "--registry-path",
+                "./chain-registry"
+            ])
+        )
+    }
+
+    #[test]
+    fn auto_config_without_probe() {
+        assert_eq!(
+            AutoCmd {
+                path: PathBuf::from("./example.toml"),
+                chain_names: vec!["chain1".to_string()],
+                commit: None,
+                registry_path: Some("./chain-registry".to_string()),
+                no_probe: true,
+            },
+            AutoCmd::parse_from([
+                "test",
+                "--output",
+                "./example.toml",
+                "--chains",
+                "chain1",
+                "--registry-path",
+                "./chain-registry",
+                "--no-probe"
+            ])
+        )
+    }
+
+    #[test]
+    fn auto_config_with_commit_and_registry_path() {
+        assert!(AutoCmd::try_parse_from([
+            "test",
+            "--output",
+            "./example.toml",
+            "--chains",
+            "chain1",
+            "--commit",
+            "test_commit",
+            "--registry-path",
+            "./chain-registry"
+        ])
+        .is_err())
+    }
 }
@@ -0,0 +1,177 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use ibc_relayer::config::{ChainConfig, RestConfig};
+use ibc_relayer::supervisor::cmd::ConfigUpdate;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Re-read the configuration file and apply the changes made to the chains
+/// it contains to a running instance of Hermes, without restarting it.
+///
+/// The running instance is reached through its REST API, which therefore
+/// must be enabled, with the `auth_token` of its write endpoints. The
+/// chains known to the running instance are compared against the ones in
+/// the configuration file: new chains are added, chains which are not in
+/// the file anymore are removed, and chains whose configuration changed
+/// are updated.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct ReloadCmd {}
+
+impl Runnable for ReloadCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        if !config.rest.enabled {
+            Output::error(
+                "the REST API must be enabled in the configuration to reload a running instance",
+            )
+            .exit();
+        }
+
+        if config.rest.auth_token.is_none() {
+            Output::error(
+                "the REST API must have an `auth_token` in the configuration to reload a running instance",
+            )
+            .exit();
+        }
+
+        let client = RestClient::new(&config.rest);
+
+        let live_chains = match client.chains() {
+            Ok(chains) => chains,
+            Err(e) => Output::error(format!(
+                "failed to fetch the configuration of the running instance: {}",
+                e
+            ))
+            .exit(),
+        };
+
+        let mut applied = Vec::new();
+
+        for update in ConfigUpdate::diff(&live_chains, &config.chains) {
+            let chain_id = update.chain_id().clone();
+
+            let (result, action) = match update {
+                ConfigUpdate::Add(chain_config) => (client.add_chain(&chain_config), "added"),
+                ConfigUpdate::Update(chain_config) => {
+                    (client.update_chain(&chain_config), "updated")
+                }
+                ConfigUpdate::Remove(chain_id) => (client.remove_chain(&chain_id), "removed"),
+            };
+
+            match result {
+                Ok(()) => {
+                    info!("{} chain {}", action, chain_id);
+                    applied.push(format!("{} chain {}", action, chain_id));
+                }
+                Err(e) => Output::error(format!(
+                    "failed to apply configuration update for chain {} (applied so far: {:?}): {}",
+                    chain_id, applied, e
+                ))
+                .exit(),
+            }
+        }
+
+        if applied.is_empty() {
+            Output::success_msg("configuration is already up to date").exit()
+        } else {
+            Output::success(applied).exit()
+        }
+    }
+}
+
+/// The JSON envelope of the replies sent by the REST API.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", content = "result")]
+#[serde(rename_all = "lowercase")]
+enum JsonResult<R> {
+    Success(R),
+    Error(RestError),
+}
+
+#[derive(Debug, Deserialize)]
+struct RestError {
+    name: String,
+    msg: String,
+}
+
+/// A minimal client for the chain configuration endpoints of the REST API.
+struct RestClient {
+    base_url: String,
+    auth_token: Option<String>,
+}
+
+impl RestClient {
+    fn new(config: &RestConfig) -> Self {
+        Self {
+            base_url: format!("http://{}:{}", config.host, config.port),
+            auth_token: config.auth_token.clone(),
+        }
+    }
+
+    fn chains(&self) -> Result<Vec<ChainConfig>, String> {
+        let chain_ids: Vec<ChainId> = self.call(ureq::get(&self.url("/chains")), None)?;
+
+        chain_ids
+            .iter()
+            .map(|id| self.call(ureq::get(&self.url(&format!("/chain/{}", id))), None))
+            .collect()
+    }
+
+    fn add_chain(&self, chain_config: &ChainConfig) -> Result<(), String> {
+        self.call(ureq::post(&self.url("/chain")), Some(chain_config))
+    }
+
+    fn update_chain(&self, chain_config: &ChainConfig) -> Result<(), String> {
+        self.call(ureq::put(&self.url("/chain")), Some(chain_config))
+    }
+
+    fn remove_chain(&self, chain_id: &ChainId) -> Result<(), String> {
+        self.call(
+            ureq::delete(&self.url(&format!("/chain/{}", chain_id))),
+            None,
+        )
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn call<R: DeserializeOwned>(
+        &self,
+        request: ureq::Request,
+        body: Option<&ChainConfig>,
+    ) -> Result<R, String> {
+        let request = match &self.auth_token {
+            Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+            None => request,
+        };
+
+        let response = match body {
+            Some(body) => {
+                let body = serde_json::to_string(body).map_err(|e| e.to_string())?;
+                request
+                    .set("Content-Type", "application/json")
+                    .send_string(&body)
+            }
+            None => request.call(),
+        };
+
+        // Rejected requests still carry the error in a JSON envelope
+        let response = match response {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let response = response.into_string().map_err(|e| e.to_string())?;
+
+        match serde_json::from_str(&response).map_err(|e| e.to_string())? {
+            JsonResult::Success(result) => Ok(result),
+            JsonResult::Error(e) => Err(format!("{}: {}", e.name, e.msg)),
+        }
+    }
+}
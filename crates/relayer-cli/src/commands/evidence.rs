@@ -0,0 +1,317 @@
+use core::time::Duration;
+use std::thread;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use eyre::eyre;
+use tendermint_rpc::{Client, HttpClient};
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc_relayer::chain::cosmos::evidence::{
+    fetch_block_evidence, BlockEvidence, LightClientAttack,
+};
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{
+    PageRequest, QueryClientStatesRequest, QueryConsensusStateHeightsRequest,
+};
+use ibc_relayer::client_state::IdentifiedAnyClientState;
+use ibc_relayer::config::Config;
+use ibc_relayer::foreign_client::{ForeignClient, MisbehaviourResults};
+use ibc_relayer::light_client::AnyHeader;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics02_client::events::{Attributes, UpdateClient};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Interval between two polls for new blocks.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct EvidenceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain whose blocks are monitored for evidence of misbehaviour"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "check-past-blocks",
+        value_name = "NUM_BLOCKS",
+        default_value = "100",
+        help = "Number of blocks before the latest one to check for evidence on startup"
+    )]
+    check_past_blocks: u64,
+}
+
+impl Runnable for EvidenceCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        match monitor_evidence(&self.chain_id, self.check_past_blocks, &config) {
+            Ok(()) => Output::success_msg("done").exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Monitors the blocks of the given chain for evidence of misbehaviour.
+///
+/// Duplicate votes are punished by the chain itself when it commits their evidence.
+/// Light client attacks, however, target the IBC clients of the chain hosted by its
+/// counterparties: for each of them, the conflicting block is submitted as misbehaviour
+/// to the clients of the chain hosted by the other chains in the configuration.
+pub fn monitor_evidence(
+    chain_id: &ChainId,
+    check_past_blocks: u64,
+    config: &Config,
+) -> eyre::Result<()> {
+    let chain_config = config
+        .find_chain(chain_id)
+        .ok_or_else(|| eyre!("chain '{}' not found in configuration", chain_id))?;
+
+    let chain = spawn_chain_runtime(config, chain_id)
+        .map_err(|e| eyre!("could not spawn the chain runtime for {}: {}", chain_id, e))?;
+
+    let rt = TokioRuntime::new()?;
+    let rpc_client = HttpClient::new(chain_config.rpc_addr.clone())?;
+
+    let latest_height = rt.block_on(rpc_client.latest_block())?.block.header.height;
+    let mut next_height = latest_height
+        .value()
+        .saturating_sub(check_past_blocks)
+        .max(1);
+
+    info!(
+        chain = %chain_id,
+        "monitoring blocks for evidence of misbehaviour, starting at height {}", next_height
+    );
+
+    loop {
+        let latest_height = match rt.block_on(rpc_client.latest_block()) {
+            Ok(response) => response.block.header.height.value(),
+            Err(e) => {
+                warn!("failed to fetch the latest block, retrying: {}", e);
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+
+        while next_height <= latest_height {
+            let height = tendermint::block::Height::try_from(next_height)?;
+
+            let evidence_list = match rt.block_on(fetch_block_evidence(
+                &rpc_client,
+                &chain_config.rpc_addr,
+                height,
+            )) {
+                Ok(evidence_list) => evidence_list,
+                Err(e) => {
+                    warn!(
+                        "failed to fetch the evidence of block at height {}, retrying: {}",
+                        height, e
+                    );
+                    break;
+                }
+            };
+
+            for evidence in evidence_list {
+                match evidence {
+                    BlockEvidence::DuplicateVote => {
+                        info!(
+                            "found duplicate vote evidence at height {}, already handled by the chain",
+                            height
+                        );
+                    }
+                    BlockEvidence::LightClientAttack(attack) => {
+                        if let Err(e) = handle_light_client_attack(config, &chain, &attack) {
+                            error!(
+                                "failed to handle light client attack evidence found at height {}: {}",
+                                height, e
+                            );
+                        }
+                    }
+                    BlockEvidence::Unknown(evidence_type) => {
+                        warn!(
+                            "skipping evidence of unknown type {} found at height {}",
+                            evidence_type, height
+                        );
+                    }
+                }
+            }
+
+            next_height += 1;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Submits the conflicting block of a light client attack on the given chain as misbehaviour
+/// to every client of that chain hosted by the other chains in the configuration.
+fn handle_light_client_attack<Chain: ChainHandle>(
+    config: &Config,
+    chain: &Chain,
+    attack: &LightClientAttack,
+) -> eyre::Result<()> {
+    let chain_id = chain.id();
+
+    info!(
+        "found light client attack evidence: block at height {} conflicts with the chain, common height is {}",
+        attack.conflicting_height(),
+        attack.common_height
+    );
+
+    for counterparty_config in config.chains.iter().filter(|c| c.id != chain_id) {
+        let counterparty = match spawn_chain_runtime(config, &counterparty_config.id) {
+            Ok(counterparty) => counterparty,
+            Err(e) => {
+                warn!(
+                    "skipping the clients hosted by {}, could not spawn its runtime: {}",
+                    counterparty_config.id, e
+                );
+                continue;
+            }
+        };
+
+        let clients = counterparty.query_clients(QueryClientStatesRequest {
+            pagination: Some(PageRequest::all()),
+        })?;
+
+        let clients = clients.into_iter().filter(|client| {
+            client.client_state.chain_id() == chain_id
+                && client.client_state.frozen_height().is_none()
+        });
+
+        for client in clients {
+            if let Err(e) = submit_conflicting_header(chain, &counterparty, &client, attack) {
+                error!(
+                    "failed to submit misbehaviour to client {} on {}: {}",
+                    client.client_id, counterparty_config.id, e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits the conflicting block of a light client attack on `chain` to the given client of that
+/// chain hosted by `counterparty`, as a header trusted at the highest consensus height of the client
+/// not above the common height of the attack.
+fn submit_conflicting_header<Chain: ChainHandle, Counterparty: ChainHandle>(
+    chain: &Chain,
+    counterparty: &Counterparty,
+    client: &IdentifiedAnyClientState,
+    attack: &LightClientAttack,
+) -> eyre::Result<()> {
+    let client_id = &client.client_id;
+    let revision_number = chain.id().version();
+    let common_height = Height::new(revision_number, attack.common_height.value())?;
+    let conflicting_height = Height::new(revision_number, attack.conflicting_height().value())?;
+
+    let trusted_height = counterparty
+        .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+            client_id: client_id.clone(),
+            pagination: Some(PageRequest::all()),
+        })?
+        .into_iter()
+        .filter(|height| *height <= common_height)
+        .max();
+
+    let trusted_height = match trusted_height {
+        Some(trusted_height) => trusted_height,
+        None => {
+            warn!(
+                "skipping client {} which has no consensus state at or below the common height {}",
+                client_id, common_height
+            );
+            return Ok(());
+        }
+    };
+
+    // The validator set trusted by a header built on top of the trusted height
+    let (AnyHeader::Tendermint(trusted_header), _) = chain.build_header(
+        trusted_height,
+        trusted_height.increment(),
+        client.client_state.clone(),
+    )?;
+
+    let header = attack.conflicting_header(trusted_height, trusted_header.trusted_validator_set);
+
+    let update_event = UpdateClient {
+        common: Attributes {
+            client_id: client_id.clone(),
+            client_type: ClientType::Tendermint,
+            consensus_height: conflicting_height,
+        },
+        header: Some(Box::new(header)),
+    };
+
+    let client = ForeignClient::restore(client_id.clone(), counterparty.clone(), chain.clone());
+
+    match client.detect_misbehaviour_and_submit_evidence(Some(&update_event)) {
+        MisbehaviourResults::EvidenceSubmitted(events) => {
+            info!(
+                "submitted misbehaviour to client {} on {}: {:?}",
+                client_id,
+                counterparty.id(),
+                events
+            );
+            Ok(())
+        }
+        MisbehaviourResults::ValidClient => {
+            warn!(
+                "the block at height {} does not conflict with the chain as seen by the relayer, no misbehaviour submitted to client {}",
+                conflicting_height, client_id
+            );
+            Ok(())
+        }
+        MisbehaviourResults::CannotExecute | MisbehaviourResults::VerificationError => Err(eyre!(
+            "could not check or submit the misbehaviour of the block at height {}",
+            conflicting_height
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EvidenceCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_evidence_required_only() {
+        assert_eq!(
+            EvidenceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                check_past_blocks: 100,
+            },
+            EvidenceCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_evidence_check_past_blocks() {
+        assert_eq!(
+            EvidenceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                check_past_blocks: 10,
+            },
+            EvidenceCmd::parse_from(["test", "--chain", "chain_id", "--check-past-blocks", "10"])
+        )
+    }
+
+    #[test]
+    fn test_evidence_no_chain() {
+        assert!(EvidenceCmd::try_parse_from(["test"]).is_err())
+    }
+}
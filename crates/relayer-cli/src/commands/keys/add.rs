@@ -13,7 +13,8 @@ use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
     keyring::{
-        AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized, Store,
+        AnySigningKeyPair, KeyRing, RemoteSigner, Secp256k1KeyPair, SigningKeyPair,
+        SigningKeyPairSized,
     },
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -24,7 +25,7 @@ use crate::conclude::Output;
 
 /// The data structure that represents the arguments when invoking the `keys add` CLI command.
 ///
-/// The command has one argument and two exclusive flags:
+/// The command has one argument and three exclusive flags:
 ///
 /// The command to add a key from a file:
 ///
@@ -34,13 +35,19 @@ use crate::conclude::Output;
 ///
 /// `keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>`
 ///
-/// The key-file and mnemonic-file flags can't be given at the same time, this will cause a terminating error.
+/// The command to add a key held by a remote signer:
+///
+/// `keys add [OPTIONS] --chain <CHAIN_ID> --remote-signer <REMOTE_SIGNER>`
+///
+/// Only one of the key-file, mnemonic-file and remote-signer flags can be given, otherwise this will cause a terminating error.
 /// If successful the key will be created or restored, depending on which flag was given.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
     override_usage = "hermes keys add [OPTIONS] --chain <CHAIN_ID> --key-file <KEY_FILE>
 
-    hermes keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>"
+    hermes keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>
+
+    hermes keys add [OPTIONS] --chain <CHAIN_ID> --remote-signer <REMOTE_SIGNER>"
 )]
 pub struct KeysAddCmd {
     #[clap(
@@ -71,6 +78,16 @@ pub struct KeysAddCmd {
     )]
     mnemonic_file: Option<PathBuf>,
 
+    #[clap(
+        long = "remote-signer",
+        required = true,
+        value_name = "REMOTE_SIGNER",
+        help_heading = "FLAGS",
+        help = "Address of the remote signer holding the key",
+        group = "add-restore"
+    )]
+    remote_signer: Option<String>,
+
     #[clap(
         long = "key-name",
         value_name = "KEY_NAME",
@@ -131,9 +148,13 @@ impl Runnable for KeysAddCmd {
             Ok(result) => result,
         };
 
-        // Check if --key-file or --mnemonic-file was given as input.
-        match (self.key_file.clone(), self.mnemonic_file.clone()) {
-            (Some(key_file), _) => {
+        // Check if --key-file, --mnemonic-file or --remote-signer was given as input.
+        match (
+            self.key_file.clone(),
+            self.mnemonic_file.clone(),
+            self.remote_signer.clone(),
+        ) {
+            (Some(key_file), _, _) => {
                 let key = add_key(
                     &opts.config,
                     &opts.name,
@@ -156,7 +177,7 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
-            (_, Some(mnemonic_file)) => {
+            (_, Some(mnemonic_file), _) => {
                 let key = restore_key(
                     &mnemonic_file,
                     &opts.name,
@@ -180,11 +201,30 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
+            (_, _, Some(remote_signer)) => {
+                let key = add_remote_key(&opts.config, &opts.name, &remote_signer, self.overwrite);
+
+                match key {
+                    Ok(key) => Output::success_msg(format!(
+                        "Added remote key '{}' ({}) on chain {}",
+                        opts.name,
+                        key.account(),
+                        opts.config.id
+                    ))
+                    .exit(),
+                    Err(e) => Output::error(format!(
+                        "An error occurred adding the key on chain {} from remote signer {}: {}",
+                        self.chain_id, remote_signer, e
+                    ))
+                    .exit(),
+                }
+            }
             // This case should never trigger.
-            // The 'required' parameter for the flags will trigger an error if both flags have not been given.
-            // And the 'group' parameter for the flags will trigger an error if both flags are given.
+            // The 'required' parameter for the flags will trigger an error if no flag has been given.
+            // And the 'group' parameter for the flags will trigger an error if several flags are given.
             _ => Output::error(
-                "--mnemonic-file and --key-file can't both be set or both None".to_string(),
+                "exactly one of --key-file, --mnemonic-file and --remote-signer must be set"
+                    .to_string(),
             )
             .exit(),
         }
@@ -200,8 +240,11 @@ pub fn add_key(
 ) -> eyre::Result<AnySigningKeyPair> {
     let key_pair = match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.account_prefix, &config.id)?;
+            let mut keyring = KeyRing::new_secp256k1(
+                config.key_store_type.persistent(),
+                &config.account_prefix,
+                &config.id,
+            )?;
 
             check_key_exists(&keyring, key_name, overwrite);
 
@@ -229,8 +272,11 @@ pub fn restore_key(
 
     let key_pair = match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.account_prefix, &config.id)?;
+            let mut keyring = KeyRing::new_secp256k1(
+                config.key_store_type.persistent(),
+                &config.account_prefix,
+                &config.id,
+            )?;
 
             check_key_exists(&keyring, key_name, overwrite);
 
@@ -249,6 +295,36 @@ pub fn restore_key(
     Ok(key_pair)
 }
 
+pub fn add_remote_key(
+    config: &ChainConfig,
+    key_name: &str,
+    remote_signer: &str,
+    overwrite: bool,
+) -> eyre::Result<AnySigningKeyPair> {
+    let key_pair = match config.r#type {
+        ChainType::CosmosSdk => {
+            let mut keyring = KeyRing::new_secp256k1(
+                config.key_store_type.persistent(),
+                &config.account_prefix,
+                &config.id,
+            )?;
+
+            check_key_exists(&keyring, key_name, overwrite);
+
+            let key_pair = Secp256k1KeyPair::from_remote_signer(
+                RemoteSigner::new(remote_signer.to_string(), key_name.to_string()),
+                &config.address_type,
+                keyring.account_prefix(),
+            )?;
+
+            keyring.add_key(key_name, key_pair.clone())?;
+            key_pair.into()
+        }
+    };
+
+    Ok(key_pair)
+}
+
 /// Check if the key with the given key name already exists.
 /// If it already exists and overwrite is false, abort the command with an error.
 /// If overwrite is true, output a warning message informing the key will be overwritten.
@@ -278,6 +354,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
+                remote_signer: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -293,6 +370,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
+                remote_signer: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false
@@ -307,6 +385,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_keys_add_remote_signer() {
+        assert_eq!(
+            KeysAddCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_file: None,
+                mnemonic_file: None,
+                remote_signer: Some("http://127.0.0.1:3500".to_string()),
+                key_name: None,
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+                overwrite: false
+            },
+            KeysAddCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--remote-signer",
+                "http://127.0.0.1:3500"
+            ])
+        )
+    }
+
     #[test]
     fn test_keys_add_key_file_overwrite() {
         assert_eq!(
@@ -314,6 +414,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
+                remote_signer: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -336,6 +437,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
+                remote_signer: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
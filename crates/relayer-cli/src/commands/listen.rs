@@ -145,9 +145,10 @@ fn subscribe(
     chain_config: &ChainConfig,
     rt: Arc<TokioRuntime>,
 ) -> eyre::Result<(EventMonitor, Subscription)> {
-    let (mut event_monitor, tx_cmd) = EventMonitor::new(
+    let (mut event_monitor, tx_cmd) = EventMonitor::with_fallbacks(
         chain_config.id.clone(),
         chain_config.websocket_addr.clone(),
+        chain_config.fallback_websocket_addrs.clone(),
         rt,
     )
     .map_err(|e| eyre!("could not initialize event monitor: {}", e))?;
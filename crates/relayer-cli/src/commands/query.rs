@@ -16,6 +16,7 @@ mod client;
 mod clients;
 mod connection;
 mod connections;
+mod ica;
 mod packet;
 mod transfer;
 mod tx;
@@ -52,6 +53,10 @@ pub enum QueryCmd {
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
 
+    /// Query information about interchain accounts
+    #[clap(subcommand)]
+    Ica(ica::IcaCmd),
+
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
@@ -108,7 +108,8 @@ fn spawn_rest_server(config: &Config) -> Option<rest::Receiver> {
     let rest = config.rest.clone();
 
     if rest.enabled {
-        let rest_config = ibc_relayer_rest::Config::new(rest.host, rest.port);
+        let rest_config =
+            ibc_relayer_rest::Config::new(rest.host, rest.port).with_auth_token(rest.auth_token);
         let (_, rest_receiver) = ibc_relayer_rest::server::spawn(rest_config);
         Some(rest_receiver)
     } else {
@@ -137,9 +138,27 @@ fn spawn_rest_server(config: &Config) -> Option<rest::Receiver> {
 fn spawn_telemetry_server(config: &Config) -> Result<(), Box<dyn Error + Send + Sync>> {
     let _span = tracing::error_span!("telemetry").entered();
 
+    let telemetry = config.telemetry.clone();
+
+    let defaults = ibc_telemetry::LatencyBuckets::default();
+
+    let buckets = |bounds: Option<Vec<u64>>, default: Vec<f64>| match bounds {
+        Some(bounds) => bounds.into_iter().map(|b| b as f64).collect(),
+        None => default,
+    };
+
+    let initialized = ibc_telemetry::init_global(ibc_telemetry::LatencyBuckets {
+        submitted: buckets(telemetry.buckets.latency_submitted, defaults.submitted),
+        confirmed: buckets(telemetry.buckets.latency_confirmed, defaults.confirmed),
+        packet: buckets(telemetry.buckets.packet_latency, defaults.packet),
+    });
+
+    if !initialized {
+        warn!("telemetry was initialized before reading the configuration, ignoring the configured histogram buckets");
+    }
+
     let state = ibc_telemetry::global();
 
-    let telemetry = config.telemetry.clone();
     if telemetry.enabled {
         match ibc_telemetry::spawn((telemetry.host, telemetry.port), state.clone()) {
             Ok((addr, _)) => {
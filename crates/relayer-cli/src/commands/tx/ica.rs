@@ -0,0 +1,306 @@
+//! `tx ica` subcommand, to control interchain accounts (ICS-27)
+
+use core::time::Duration;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Deserialize;
+use subtle_encoding::base64;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_accounts::v1::{
+    InterchainAccountPacketData as RawInterchainAccountPacketData, Type,
+};
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::event::IbcEventWithHeight;
+use ibc_relayer_types::applications::ics27_ica::msgs::register::MsgRegisterInterchainAccount;
+use ibc_relayer_types::applications::ics27_ica::msgs::send_tx::MsgSendTx;
+use ibc_relayer_types::applications::ics27_ica::packet_data::InterchainAccountPacketData;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc_relayer_types::tx_msg::Msg;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+
+/// `tx ica` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum TxIcaCmd {
+    /// Register an interchain account for the relayer's wallet (MsgRegisterInterchainAccount)
+    Register(TxIcaRegisterCmd),
+
+    /// Execute messages with the relayer's interchain account (MsgSendTx)
+    Send(TxIcaSendCmd),
+}
+
+/// The data structure that represents the arguments when invoking the `tx ica register` CLI command.
+///
+/// The command has the following format:
+///
+/// `tx ica register --chain <CHAIN_ID> --connection <CONNECTION_ID> [--version <VERSION>]`
+///
+/// The interchain account is owned by the relayer's wallet on the controller chain `CHAIN_ID`,
+/// and is created on the host chain at the other end of the connection.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcaRegisterCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the controller chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "connection",
+        visible_alias = "conn",
+        required = true,
+        value_name = "CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the connection to the host chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[clap(
+        long = "version",
+        value_name = "VERSION",
+        help = "Version of the interchain accounts channel (default: negotiated by the chains)"
+    )]
+    version: Option<String>,
+}
+
+impl Runnable for TxIcaRegisterCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let owner = chain
+            .get_signer()
+            .map_err(Error::relayer)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let msg = MsgRegisterInterchainAccount {
+            owner,
+            connection_id: self.connection_id.clone(),
+            version: self.version.clone().unwrap_or_default(),
+        };
+
+        match send_message(&chain, msg.to_any()) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `tx ica send` CLI command.
+///
+/// The command has the following format:
+///
+/// `tx ica send --chain <CHAIN_ID> --connection <CONNECTION_ID> --packet-data <FILE> [--timeout-seconds <SECONDS>]`
+///
+/// The packet data file is the JSON output of the `interchain-accounts host generate-packet-data`
+/// command of `ibc-go` chains, eg. `{"type": "TYPE_EXECUTE_TX", "data": "<base64>", "memo": ""}`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcaSendCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the controller chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "connection",
+        visible_alias = "conn",
+        required = true,
+        value_name = "CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the connection to the host chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[clap(
+        long = "packet-data",
+        required = true,
+        value_name = "FILE",
+        help_heading = "REQUIRED",
+        help = "Path to a JSON file with the interchain account packet data to send"
+    )]
+    packet_data: PathBuf,
+
+    #[clap(
+        long = "timeout-seconds",
+        default_value = "600",
+        value_name = "TIMEOUT_SECONDS",
+        help = "Timeout of the packet in seconds since current"
+    )]
+    timeout_seconds: u64,
+}
+
+impl Runnable for TxIcaSendCmd {
+    fn run(&self) {
+        let packet_data =
+            read_packet_data(&self.packet_data).unwrap_or_else(exit_with_unrecoverable_error);
+
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let owner = chain
+            .get_signer()
+            .map_err(Error::relayer)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let msg = MsgSendTx {
+            owner,
+            connection_id: self.connection_id.clone(),
+            packet_data,
+            relative_timeout: Duration::from_secs(self.timeout_seconds),
+        };
+
+        match send_message(&chain, msg.to_any()) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+fn send_message(chain: &impl ChainHandle, msg: Any) -> Result<Vec<IbcEventWithHeight>, Error> {
+    chain
+        .send_messages_and_wait_commit(TrackedMsgs::new_single(msg, "ica"))
+        .map_err(Error::relayer)
+}
+
+/// JSON encoding of the interchain account packet data, as output by `ibc-go`.
+#[derive(Deserialize)]
+struct PacketDataFile {
+    #[serde(rename = "type")]
+    ty: String,
+    data: String,
+    #[serde(default)]
+    memo: String,
+}
+
+fn read_packet_data(path: &Path) -> Result<InterchainAccountPacketData, Error> {
+    let content = std::fs::read_to_string(path).map_err(Error::io)?;
+
+    let file: PacketDataFile = serde_json::from_str(&content).map_err(|e| {
+        Error::cli_arg(format!(
+            "invalid packet data file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let ty = [Type::Unspecified, Type::ExecuteTx]
+        .into_iter()
+        .find(|ty| ty.as_str_name() == file.ty)
+        .ok_or_else(|| Error::cli_arg(format!("unknown packet data type '{}'", file.ty)))?;
+
+    let data = base64::decode(&file.data)
+        .map_err(|e| Error::cli_arg(format!("packet data is not valid base64: {}", e)))?;
+
+    let raw = RawInterchainAccountPacketData {
+        r#type: ty as i32,
+        data,
+        memo: file.memo,
+    };
+
+    InterchainAccountPacketData::try_from(raw).map_err(Error::ica)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TxIcaRegisterCmd, TxIcaSendCmd};
+
+    use abscissa_core::clap::Parser;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+
+    #[test]
+    fn test_ica_register() {
+        assert_eq!(
+            TxIcaRegisterCmd {
+                chain_id: ChainId::from_string("chain_a"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                version: None,
+            },
+            TxIcaRegisterCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_a",
+                "--connection",
+                "connection-0"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_register_version() {
+        assert_eq!(
+            TxIcaRegisterCmd {
+                chain_id: ChainId::from_string("chain_a"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                version: Some("ics27-1".to_owned()),
+            },
+            TxIcaRegisterCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_a",
+                "--conn",
+                "connection-0",
+                "--version",
+                "ics27-1"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_register_no_connection() {
+        assert!(TxIcaRegisterCmd::try_parse_from(["test", "--chain", "chain_a"]).is_err())
+    }
+
+    #[test]
+    fn test_ica_send() {
+        assert_eq!(
+            TxIcaSendCmd {
+                chain_id: ChainId::from_string("chain_a"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                packet_data: PathBuf::from("msgs.json"),
+                timeout_seconds: 600,
+            },
+            TxIcaSendCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_a",
+                "--connection",
+                "connection-0",
+                "--packet-data",
+                "msgs.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_send_no_packet_data() {
+        assert!(TxIcaSendCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_a",
+            "--connection",
+            "connection-0"
+        ])
+        .is_err())
+    }
+}
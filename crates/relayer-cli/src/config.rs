@@ -8,7 +8,7 @@ use alloc::collections::BTreeSet;
 use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
-use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use ibc_relayer::config::{ChainConfig, Config, ModeConfig, TelemetryBuckets};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
@@ -73,6 +73,14 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        InvalidTelemetryBuckets
+            { histogram: String }
+            |e| {
+                format!("config file specifies invalid buckets for the `{0}` histogram, \
+                    their upper bounds must be strictly increasing",
+                    e.histogram)
+            },
     }
 }
 
@@ -101,6 +109,29 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
     // Check for invalid mode config
     validate_mode(&config.mode)?;
 
+    validate_telemetry_buckets(&config.telemetry.buckets)?;
+
+    Ok(())
+}
+
+/// Check that the upper bounds of the buckets of each histogram are strictly increasing
+fn validate_telemetry_buckets(buckets: &TelemetryBuckets) -> Result<(), Diagnostic<Error>> {
+    let histograms = [
+        ("tx_latency_submitted", &buckets.latency_submitted),
+        ("tx_latency_confirmed", &buckets.latency_confirmed),
+        ("packet_latency", &buckets.packet_latency),
+    ];
+
+    for (histogram, bounds) in histograms {
+        let bounds = bounds.as_deref().unwrap_or_default();
+
+        if bounds.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Diagnostic::Error(Error::invalid_telemetry_buckets(
+                histogram.to_string(),
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -170,3 +201,49 @@ fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagn
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_telemetry_buckets, Diagnostic};
+
+    use ibc_relayer::config::TelemetryBuckets;
+
+    #[test]
+    fn default_telemetry_buckets_are_valid() {
+        assert!(validate_telemetry_buckets(&TelemetryBuckets::default()).is_ok());
+    }
+
+    #[test]
+    fn increasing_telemetry_buckets_are_valid() {
+        let buckets = TelemetryBuckets {
+            latency_submitted: Some(vec![100, 200, 300]),
+            latency_confirmed: Some(vec![]),
+            packet_latency: Some(vec![1000]),
+        };
+
+        assert!(validate_telemetry_buckets(&buckets).is_ok());
+    }
+
+    #[test]
+    fn non_increasing_telemetry_buckets_are_invalid() {
+        let unsorted = TelemetryBuckets {
+            latency_confirmed: Some(vec![1000, 500]),
+            ..TelemetryBuckets::default()
+        };
+
+        match validate_telemetry_buckets(&unsorted) {
+            Err(Diagnostic::Error(e)) => assert!(e.to_string().contains("tx_latency_confirmed")),
+            result => panic!("unexpected result: {result:?}"),
+        }
+
+        let duplicated = TelemetryBuckets {
+            packet_latency: Some(vec![1000, 1000]),
+            ..TelemetryBuckets::default()
+        };
+
+        match validate_telemetry_buckets(&duplicated) {
+            Err(Diagnostic::Error(e)) => assert!(e.to_string().contains("packet_latency")),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use std::io::Error as IoError;
 
 use tendermint::Error as TendermintError;
 
+use ibc_relayer_types::applications::ics27_ica::error::Error as IcaError;
 use ibc_relayer_types::applications::ics29_fee::error::Error as FeeError;
 use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -88,6 +89,10 @@ define_error! {
             [ FeeError ]
             |_| { "fee error" },
 
+        Ica
+            [ IcaError ]
+            |_| { "interchain accounts error" },
+
         Transfer
             [ TransferError ]
             |_| { "transfer error" },
@@ -5,11 +5,22 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 pub struct Config {
     pub host: String,
     pub port: u16,
+    /// The bearer token required by the write endpoints,
+    /// which are disabled if it is not set
+    pub auth_token: Option<String>,
 }
 
 impl Config {
     pub fn new(host: String, port: u16) -> Self {
-        Self { host, port }
+        Self {
+            host,
+            port,
+            auth_token: None,
+        }
+    }
+
+    pub fn with_auth_token(self, auth_token: Option<String>) -> Self {
+        Self { auth_token, ..self }
     }
 
     pub fn address(&self) -> (&str, u16) {
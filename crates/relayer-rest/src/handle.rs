@@ -7,12 +7,13 @@ use crossbeam_channel as channel;
 use ibc_relayer::supervisor::dump_state::SupervisorState;
 use ibc_relayer::{
     config::ChainConfig,
+    event::stream::{EventReceiver, StreamFilter},
     rest::{
-        request::{reply_channel, ReplySender, Request, VersionInfo},
+        request::{reply_channel, PathBacklog, ReplySender, Request, VersionInfo, WorkerStatus},
         RestApiError,
     },
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
 pub const NAME: &str = env!(
     "CARGO_PKG_NAME",
@@ -58,6 +59,167 @@ pub fn chain_config(
     })
 }
 
+pub fn add_chain(
+    sender: &channel::Sender<Request>,
+    chain_config: ChainConfig,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::AddChain {
+        chain_config,
+        reply_to,
+    })
+}
+
+pub fn update_chain(
+    sender: &channel::Sender<Request>,
+    chain_config: ChainConfig,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::UpdateChain {
+        chain_config,
+        reply_to,
+    })
+}
+
+pub fn remove_chain(sender: &channel::Sender<Request>, chain_id: &str) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::RemoveChain {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn workers(sender: &channel::Sender<Request>) -> Result<Vec<WorkerStatus>, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetWorkers { reply_to })
+}
+
+pub fn path_backlog(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    channel_id: &str,
+    port_id: &str,
+) -> Result<PathBacklog, RestApiError> {
+    let (port_id, channel_id) = parse_channel(port_id, channel_id)?;
+
+    submit_request(sender, |reply_to| Request::GetPathBacklog {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn subscribe_events(sender: &channel::Sender<Request>) -> Result<EventReceiver, RestApiError> {
+    submit_request(sender, |reply_to| Request::SubscribeEvents { reply_to })
+}
+
+/// Build the filter of the streamed events out of the query parameters of the request,
+/// where the event types are given as a comma-separated list.
+pub fn stream_filter(
+    chain_id: Option<&str>,
+    channel_id: Option<&str>,
+    event_types: Option<&str>,
+) -> Result<StreamFilter, RestApiError> {
+    let channel_id = channel_id
+        .map(|channel_id| {
+            channel_id
+                .parse::<ChannelId>()
+                .map_err(|e| RestApiError::InvalidParameter(channel_id.to_string(), e.to_string()))
+        })
+        .transpose()?;
+
+    let event_types = event_types
+        .map(|types| {
+            types
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(StreamFilter {
+        chain_id: chain_id.map(ChainId::from_string),
+        channel_id,
+        event_types,
+    })
+}
+
+pub fn clear_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    channel_id: &str,
+    port_id: &str,
+) -> Result<(), RestApiError> {
+    let (port_id, channel_id) = parse_channel(port_id, channel_id)?;
+
+    submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn refresh_client(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    client_id: &str,
+    force: bool,
+) -> Result<(), RestApiError> {
+    let client_id = client_id
+        .parse::<ClientId>()
+        .map_err(|e| RestApiError::InvalidParameter(client_id.to_string(), e.to_string()))?;
+
+    submit_request(sender, |reply_to| Request::RefreshClient {
+        chain_id: ChainId::from_string(chain_id),
+        client_id,
+        force,
+        reply_to,
+    })
+}
+
+pub fn pause_packet_worker(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    channel_id: &str,
+    port_id: &str,
+) -> Result<(), RestApiError> {
+    let (port_id, channel_id) = parse_channel(port_id, channel_id)?;
+
+    submit_request(sender, |reply_to| Request::PausePacketWorker {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn resume_packet_worker(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    channel_id: &str,
+    port_id: &str,
+) -> Result<(), RestApiError> {
+    let (port_id, channel_id) = parse_channel(port_id, channel_id)?;
+
+    submit_request(sender, |reply_to| Request::ResumePacketWorker {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+fn parse_channel(port_id: &str, channel_id: &str) -> Result<(PortId, ChannelId), RestApiError> {
+    let port_id = port_id
+        .parse::<PortId>()
+        .map_err(|e| RestApiError::InvalidParameter(port_id.to_string(), e.to_string()))?;
+
+    let channel_id = channel_id
+        .parse::<ChannelId>()
+        .map_err(|e| RestApiError::InvalidParameter(channel_id.to_string(), e.to_string()))?;
+
+    Ok((port_id, channel_id))
+}
+
 pub fn supervisor_state(
     sender: &channel::Sender<Request>,
 ) -> Result<SupervisorState, RestApiError> {
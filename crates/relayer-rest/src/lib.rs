@@ -7,3 +7,4 @@ pub use config::Config;
 pub mod server;
 
 pub(crate) mod handle;
+pub(crate) mod stream;
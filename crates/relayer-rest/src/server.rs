@@ -4,10 +4,19 @@ use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
 use tracing::{info, trace};
 
-use ibc_relayer::rest::request::Request;
+use ibc_relayer::{
+    config::ChainConfig,
+    event::stream::StreamFilter,
+    rest::{request::Request, RestApiError},
+};
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{
+        add_chain, all_chain_ids, assemble_version_info, chain_config, clear_packets, path_backlog,
+        pause_packet_worker, refresh_client, remove_chain, resume_packet_worker, stream_filter,
+        subscribe_events, supervisor_state, update_chain, workers,
+    },
+    stream::forward_events,
     Config,
 };
 
@@ -52,9 +61,74 @@ impl<R, E> From<Result<R, E>> for JsonResult<R, E> {
     }
 }
 
+/// Check that the request carries the configured authentication token,
+/// as a bearer token in its `Authorization` header.
+fn authorize(request: &rouille::Request, auth_token: Option<&str>) -> Result<(), RestApiError> {
+    let auth_token = auth_token.ok_or(RestApiError::WriteDisabled)?;
+
+    let token = request
+        .header("Authorization")
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .ok_or(RestApiError::Unauthorized)?;
+
+    // Compare the whole tokens, so that the time taken does not leak how much of them match
+    let matches = token.len() == auth_token.len()
+        && token
+            .bytes()
+            .zip(auth_token.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+
+    if matches {
+        Ok(())
+    } else {
+        Err(RestApiError::Unauthorized)
+    }
+}
+
+/// Respond to a request to a write endpoint with the given result.
+fn write_response<R: Serialize>(result: Result<R, RestApiError>) -> rouille::Response {
+    let status_code = match &result {
+        Err(RestApiError::Unauthorized) => 401,
+        Err(RestApiError::WriteDisabled) => 403,
+        _ => 200,
+    };
+
+    rouille::Response::json(&JsonResult::from(result)).with_status_code(status_code)
+}
+
+/// Upgrade the request to a websocket, through which the events
+/// of the relayer which pass the given filter are then streamed.
+fn events_response(
+    request: &rouille::Request,
+    sender: &channel::Sender<Request>,
+    filter: StreamFilter,
+) -> rouille::Response {
+    let (response, websocket) = match rouille::websocket::start::<String>(request, None) {
+        Ok(started) => started,
+        Err(e) => {
+            let error = RestApiError::Websocket(e.to_string());
+            return rouille::Response::json(&JsonResult::<(), _>::Error(error))
+                .with_status_code(400);
+        }
+    };
+
+    match subscribe_events(sender) {
+        Ok(events) => {
+            forward_events(websocket, events, filter);
+            response
+        }
+        Err(e) => rouille::Response::json(&JsonResult::<(), _>::Error(e)).with_status_code(500),
+    }
+}
+
 #[allow(clippy::manual_strip)]
 fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
+    let auth_token = config.auth_token.clone();
+
     let server = rouille::Server::new(config.address(), move |request| {
+        let auth_token = auth_token.as_deref();
+
         router!(request,
             (GET) (/version) => {
                 trace!("[rest/server] GET /version");
@@ -76,12 +150,103 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (POST) (/chain) => {
+                trace!("[rest] POST /chain");
+                let result = authorize(request, auth_token)
+                    .and_then(|()| {
+                        rouille::input::json_input::<ChainConfig>(request)
+                            .map_err(|e| RestApiError::InvalidChainConfig(e.to_string()))
+                    })
+                    .and_then(|config| add_chain(&sender, config));
+                write_response(result)
+            },
+
+            (PUT) (/chain) => {
+                trace!("[rest] PUT /chain");
+                let result = authorize(request, auth_token)
+                    .and_then(|()| {
+                        rouille::input::json_input::<ChainConfig>(request)
+                            .map_err(|e| RestApiError::InvalidChainConfig(e.to_string()))
+                    })
+                    .and_then(|config| update_chain(&sender, config));
+                write_response(result)
+            },
+
+            (DELETE) (/chain/{id: String}) => {
+                trace!("[rest] DELETE /chain/{}", id);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| remove_chain(&sender, &id));
+                write_response(result)
+            },
+
             (GET) (/state) => {
                 trace!("[rest] GET /state");
                 let result = supervisor_state(&sender);
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (GET) (/workers) => {
+                trace!("[rest] GET /workers");
+                let result = workers(&sender);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/path/{chain: String}/{channel: String}/{port: String}/backlog) => {
+                trace!("[rest] GET /path/{}/{}/{}/backlog", chain, channel, port);
+                let result = path_backlog(&sender, &chain, &channel, &port);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/events) => {
+                trace!("[rest] GET /events");
+                let filter = stream_filter(
+                    request.get_param("chain").as_deref(),
+                    request.get_param("channel").as_deref(),
+                    request.get_param("type").as_deref(),
+                );
+
+                match filter {
+                    Ok(filter) => events_response(request, &sender, filter),
+                    Err(e) => rouille::Response::json(&JsonResult::<(), _>::Error(e))
+                        .with_status_code(400),
+                }
+            },
+
+            (POST) (/path/{chain: String}/{channel: String}/{port: String}/clear) => {
+                trace!("[rest] POST /path/{}/{}/{}/clear", chain, channel, port);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| clear_packets(&sender, &chain, &channel, &port));
+                write_response(result)
+            },
+
+            (POST) (/path/{chain: String}/{channel: String}/{port: String}/pause) => {
+                trace!("[rest] POST /path/{}/{}/{}/pause", chain, channel, port);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| pause_packet_worker(&sender, &chain, &channel, &port));
+                write_response(result)
+            },
+
+            (POST) (/path/{chain: String}/{channel: String}/{port: String}/resume) => {
+                trace!("[rest] POST /path/{}/{}/{}/resume", chain, channel, port);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| resume_packet_worker(&sender, &chain, &channel, &port));
+                write_response(result)
+            },
+
+            (POST) (/client/{chain: String}/{client: String}/refresh) => {
+                trace!("[rest] POST /client/{}/{}/refresh", chain, client);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| refresh_client(&sender, &chain, &client, false));
+                write_response(result)
+            },
+
+            (POST) (/client/{chain: String}/{client: String}/update) => {
+                trace!("[rest] POST /client/{}/{}/update", chain, client);
+                let result = authorize(request, auth_token)
+                    .and_then(|()| refresh_client(&sender, &chain, &client, true));
+                write_response(result)
+            },
+
             _ => rouille::Response::empty_404(),
         )
     })
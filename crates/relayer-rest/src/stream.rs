@@ -0,0 +1,67 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::RecvTimeoutError;
+use rouille::websocket::Websocket;
+use tracing::{debug, error};
+
+use ibc_relayer::event::stream::{EventReceiver, StreamFilter};
+
+/// How long to wait for the websocket to be opened once the upgrade response is sent
+const OPEN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often to check whether the websocket has been closed while no event is received
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Forward the events which pass the given filter to the websocket, once opened,
+/// as JSON text messages, from a dedicated thread.
+///
+/// The subscription to the events ends as soon as the websocket is closed,
+/// or sending a message to it fails.
+pub fn forward_events(
+    websocket: mpsc::Receiver<Websocket>,
+    events: EventReceiver,
+    filter: StreamFilter,
+) {
+    thread::spawn(move || {
+        let mut websocket = match websocket.recv_timeout(OPEN_TIMEOUT) {
+            Ok(websocket) => websocket,
+            Err(e) => {
+                error!("[rest] websocket was not opened: {}", e);
+                return;
+            }
+        };
+
+        debug!("[rest] streaming events with filter {:?}", filter);
+
+        loop {
+            let event = match events.recv_timeout(POLL_INTERVAL) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) if websocket.is_closed() => break,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            let event = match filter.apply(&event) {
+                Some(event) => event,
+                None => continue,
+            };
+
+            let message = match serde_json::to_string(&event) {
+                Ok(message) => message,
+                Err(e) => {
+                    error!("[rest] failed to serialize event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = websocket.send_text(&message) {
+                debug!("[rest] stopped streaming events: {:?}", e);
+                break;
+            }
+        }
+
+        debug!("[rest] closed event stream");
+    });
+}
@@ -1,13 +1,20 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
-    rest::request::{Request, VersionInfo},
+    event::stream::StreamEvent,
+    object::{Object, Packet},
+    rest::request::{PathBacklog, Request, VersionInfo, WorkerStatus},
     supervisor::dump_state::SupervisorState,
+    worker::WorkerId,
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::Height;
 
 use ibc_relayer_rest::{server::spawn, Config};
 
@@ -28,9 +35,55 @@ fn run_test<R, F>(port: u16, path: &str, expected: R, handler: F)
 where
     R: Serialize,
     F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    run_request_test(port, path, |url| ureq::get(url).call(), expected, handler)
+}
+
+fn run_request_test<R, F, C>(port: u16, path: &str, call: C, expected: R, handler: F)
+where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+    C: FnOnce(&str) -> Result<ureq::Response, ureq::Error>,
 {
     let config = Config::new("127.0.0.1".to_string(), port);
+    run_request_test_with_config(config, path, call, expected, handler)
+}
+
+const AUTH_TOKEN: &str = "mock-token";
+
+fn run_write_test<R, F>(port: u16, path: &str, expected: R, handler: F)
+where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    let config =
+        Config::new("127.0.0.1".to_string(), port).with_auth_token(Some(AUTH_TOKEN.to_string()));
+
+    run_request_test_with_config(
+        config,
+        path,
+        |url| {
+            ureq::post(url)
+                .set("Authorization", &format!("Bearer {}", AUTH_TOKEN))
+                .call()
+        },
+        expected,
+        handler,
+    )
+}
 
+fn run_request_test_with_config<R, F, C>(
+    config: Config,
+    path: &str,
+    call: C,
+    expected: R,
+    handler: F,
+) where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+    C: FnOnce(&str) -> Result<ureq::Response, ureq::Error>,
+{
+    let port = config.port;
     let (handle, rx) = spawn(config);
 
     std::thread::spawn(move || match rx.recv() {
@@ -41,8 +94,7 @@ where
         Err(e) => panic!("got an error: {}", e),
     });
 
-    let response = ureq::get(&format!("http://127.0.0.1:{}{}", port, path))
-        .call()
+    let response = call(&format!("http://127.0.0.1:{}{}", port, path))
         .unwrap()
         .into_string()
         .unwrap();
@@ -137,3 +189,352 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+fn mock_backlog() -> PathBacklog {
+    PathBacklog {
+        chain_id: ChainId::from_string("mock-0"),
+        channel_id: ChannelId::new(0),
+        port_id: PortId::transfer(),
+        pending_sequences: vec![3.into(), 4.into()],
+        oldest_pending_timestamp: Some(1_670_000_000),
+        last_relayed_height: Some(Height::new(0, 42).unwrap()),
+        last_error: None,
+    }
+}
+
+#[test]
+fn workers() {
+    let status = WorkerStatus {
+        id: WorkerId::new(1),
+        object: Object::Packet(Packet {
+            dst_chain_id: ChainId::from_string("mock-1"),
+            src_chain_id: ChainId::from_string("mock-0"),
+            src_channel_id: ChannelId::new(0),
+            src_port_id: PortId::transfer(),
+        }),
+        data: None,
+        stopped: false,
+        backlog: Some(mock_backlog()),
+    };
+
+    let result: JsonResult<_, ()> = JsonResult::Success(vec![status.clone()]);
+
+    run_test(19112, "/workers", result, |req| match req {
+        Request::GetWorkers { reply_to } => {
+            reply_to.send(Ok(vec![status])).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
+
+#[test]
+fn path_backlog() {
+    let backlog = mock_backlog();
+    let result: JsonResult<_, ()> = JsonResult::Success(backlog.clone());
+
+    run_test(
+        19113,
+        "/path/mock-0/channel-0/transfer/backlog",
+        result,
+        |req| match req {
+            Request::GetPathBacklog {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } if chain_id == backlog.chain_id
+                && port_id == backlog.port_id
+                && channel_id == backlog.channel_id =>
+            {
+                reply_to.send(Ok(backlog)).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+/// Open a websocket to the given path through a raw handshake,
+/// and return the connection once the server has accepted the upgrade.
+fn open_websocket(port: u16, path: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\n\
+         Host: 127.0.0.1:{}\r\n\
+         Connection: Upgrade\r\n\
+         Upgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+        path, port
+    )
+    .unwrap();
+
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+
+    while !response.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).unwrap();
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8(response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 101"), "{}", response);
+
+    stream
+}
+
+/// Read a text message sent by the server, which sends them as single unmasked frames.
+fn read_text_message(stream: &mut TcpStream) -> String {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[0], 0x81);
+
+    let len = match header[1] {
+        126 => {
+            let mut len = [0u8; 2];
+            stream.read_exact(&mut len).unwrap();
+            u16::from_be_bytes(len) as usize
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            stream.read_exact(&mut len).unwrap();
+            u64::from_be_bytes(len) as usize
+        }
+        len => len as usize,
+    };
+
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).unwrap();
+
+    String::from_utf8(payload).unwrap()
+}
+
+fn mock_tx_failed(chain_id: &str) -> StreamEvent {
+    StreamEvent::TxFailed {
+        chain_id: ChainId::from_string(chain_id),
+        counterparty_chain_id: ChainId::from_string("mock-2"),
+        channel_id: ChannelId::new(0),
+        port_id: PortId::transfer(),
+        tracking_id: "mock-tracking-id".to_string(),
+        tx_hash: None,
+        code: Some(11),
+        sdk_error: None,
+        log: "out of gas".to_string(),
+    }
+}
+
+#[test]
+fn events() {
+    let config = Config::new("127.0.0.1".to_string(), 19114);
+    let (handle, rx) = spawn(config);
+
+    std::thread::spawn(move || match rx.recv() {
+        Ok(Request::SubscribeEvents { reply_to }) => {
+            let (events_tx, events_rx) = crossbeam_channel::unbounded();
+            reply_to.send(Ok(events_rx)).unwrap();
+
+            // Filtered out, as it is on another chain
+            events_tx.send(Arc::new(mock_tx_failed("mock-1"))).unwrap();
+            events_tx.send(Arc::new(mock_tx_failed("mock-0"))).unwrap();
+        }
+        Ok(req) => panic!("got the wrong request: {:?}", req),
+        Err(e) => panic!("got an error: {}", e),
+    });
+
+    let mut websocket = open_websocket(19114, "/events?chain=mock-0&type=tx_submitted,tx_failed");
+
+    let message = read_text_message(&mut websocket);
+    let expected = serde_json::to_string(&mock_tx_failed("mock-0")).unwrap();
+    assert_eq!(message, expected);
+
+    handle.stop();
+    handle.join().unwrap();
+}
+
+#[test]
+fn events_invalid_filter() {
+    let config = Config::new("127.0.0.1".to_string(), 19115);
+    let (handle, _rx) = spawn(config);
+
+    let response = ureq::get("http://127.0.0.1:19115/events?channel=chan").call();
+    assert!(matches!(response, Err(ureq::Error::Status(400, _))));
+
+    handle.stop();
+    handle.join().unwrap();
+}
+
+#[test]
+fn add_chain() {
+    let config: ChainConfig = toml::de::from_str(MOCK_CHAIN_CONFIG).unwrap();
+    let body = serde_json::to_string(&config).unwrap();
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    let config =
+        Config::new("127.0.0.1".to_string(), 19105).with_auth_token(Some(AUTH_TOKEN.to_string()));
+
+    run_request_test_with_config(
+        config,
+        "/chain",
+        |url| {
+            ureq::post(url)
+                .set("Authorization", &format!("Bearer {}", AUTH_TOKEN))
+                .set("Content-Type", "application/json")
+                .send_string(&body)
+        },
+        result,
+        |req| match req {
+            Request::AddChain {
+                chain_config,
+                reply_to,
+            } if chain_config.id.to_string().as_str() == "mock-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn remove_chain() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    let config =
+        Config::new("127.0.0.1".to_string(), 19106).with_auth_token(Some(AUTH_TOKEN.to_string()));
+
+    run_request_test_with_config(
+        config,
+        "/chain/mock-0",
+        |url| {
+            ureq::delete(url)
+                .set("Authorization", &format!("Bearer {}", AUTH_TOKEN))
+                .call()
+        },
+        result,
+        |req| match req {
+            Request::RemoveChain { chain_id, reply_to }
+                if chain_id.to_string().as_str() == "mock-0" =>
+            {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn clear_packets() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_write_test(
+        19107,
+        "/path/mock-0/channel-0/transfer/clear",
+        result,
+        |req| match req {
+            Request::ClearPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } if chain_id.as_str() == "mock-0"
+                && port_id == PortId::transfer()
+                && channel_id == ChannelId::new(0) =>
+            {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn refresh_client() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_write_test(
+        19108,
+        "/client/mock-0/07-tendermint-0/update",
+        result,
+        |req| match req {
+            Request::RefreshClient {
+                chain_id,
+                client_id,
+                force: true,
+                reply_to,
+            } if chain_id.as_str() == "mock-0" && client_id.as_str() == "07-tendermint-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn pause_packet_worker() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_write_test(
+        19109,
+        "/path/mock-0/channel-0/transfer/pause",
+        result,
+        |req| match req {
+            Request::PausePacketWorker {
+                chain_id, reply_to, ..
+            } if chain_id.as_str() == "mock-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn write_requires_auth_token() {
+    let config =
+        Config::new("127.0.0.1".to_string(), 19110).with_auth_token(Some(AUTH_TOKEN.to_string()));
+
+    let (handle, _rx) = spawn(config);
+
+    let url = "http://127.0.0.1:19110/path/mock-0/channel-0/transfer/resume";
+
+    let missing = ureq::post(url).call();
+    assert!(matches!(missing, Err(ureq::Error::Status(401, _))));
+
+    let wrong = ureq::post(url)
+        .set("Authorization", "Bearer wrong-token")
+        .call();
+    assert!(matches!(wrong, Err(ureq::Error::Status(401, _))));
+
+    let remove = ureq::delete("http://127.0.0.1:19110/chain/mock-0").call();
+    assert!(matches!(remove, Err(ureq::Error::Status(401, _))));
+
+    let add = ureq::post("http://127.0.0.1:19110/chain")
+        .set("Content-Type", "application/json")
+        .send_string("{}");
+    assert!(matches!(add, Err(ureq::Error::Status(401, _))));
+
+    handle.stop();
+    handle.join().unwrap();
+}
+
+#[test]
+fn write_disabled_without_auth_token() {
+    let config = Config::new("127.0.0.1".to_string(), 19111);
+
+    let (handle, _rx) = spawn(config);
+
+    let response = ureq::post("http://127.0.0.1:19111/path/mock-0/channel-0/transfer/clear")
+        .set("Authorization", &format!("Bearer {}", AUTH_TOKEN))
+        .call();
+    assert!(matches!(response, Err(ureq::Error::Status(403, _))));
+
+    handle.stop();
+    handle.join().unwrap();
+}
@@ -0,0 +1,40 @@
+use flex_error::{define_error, TraceError};
+use ibc_proto::protobuf::Error as ProtobufError;
+use prost::DecodeError;
+
+use crate::core::ics24_host::error::ValidationError;
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        InvalidConnectionId
+            { connection_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid connection identifier {}", e.connection_id) },
+
+        EmptyPacketData
+            | _ | { "expect packet_data field to be non-empty" },
+
+        UnsupportedPacketType
+            { packet_type: i32 }
+            | e | { format_args!("unsupported interchain account packet type {}, expected TYPE_EXECUTE_TX", e.packet_type) },
+
+        DecodeCosmosTx
+            [ TraceError<DecodeError> ]
+            | _ | { "error decoding the transaction of an interchain account packet" },
+
+        DecodeRawMsg
+            [ TraceError<ProtobufError> ]
+            | _ | { "error decoding raw msg" },
+
+        UnknownMsgType
+            { msg_type: String }
+            | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+    }
+}
@@ -0,0 +1,7 @@
+//! The controller side of the ICS 27 interchain accounts [spec](https://github.com/cosmos/ibc/blob/main/spec/app/ics-027-interchain-accounts/README.md).
+//! Lets an account on a controller chain register an interchain account on a host chain,
+//! and send it transactions to execute on the host chain.
+
+pub mod error;
+pub mod msgs;
+pub mod packet_data;
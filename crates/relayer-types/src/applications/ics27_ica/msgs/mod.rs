@@ -0,0 +1,4 @@
+//! Messages of the interchain accounts controller module.
+
+pub mod register;
+pub mod send_tx;
@@ -0,0 +1,109 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::applications::ics27_ica::error::Error;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str =
+    "/ibc.applications.interchain_accounts.controller.v1.MsgRegisterInterchainAccount";
+
+/// Protobuf definition of `MsgRegisterInterchainAccount`, which is missing from `ibc-proto`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawMsgRegisterInterchainAccount {
+    #[prost(string, tag = "1")]
+    pub owner: String,
+    #[prost(string, tag = "2")]
+    pub connection_id: String,
+    #[prost(string, tag = "3")]
+    pub version: String,
+}
+
+/// Register an interchain account for the `owner` account of the controller chain,
+/// on the host chain at the other end of the given connection. This initiates the
+/// opening handshake of the channel used to send transactions to the interchain account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgRegisterInterchainAccount {
+    pub owner: Signer,
+    pub connection_id: ConnectionId,
+    /// The version of the channel to open. If empty, the controller
+    /// chain picks the default version for the connection.
+    pub version: String,
+}
+
+impl Msg for MsgRegisterInterchainAccount {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterInterchainAccount;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRegisterInterchainAccount> for MsgRegisterInterchainAccount {}
+
+impl TryFrom<RawMsgRegisterInterchainAccount> for MsgRegisterInterchainAccount {
+    type Error = Error;
+
+    fn try_from(raw: RawMsgRegisterInterchainAccount) -> Result<Self, Self::Error> {
+        Ok(Self {
+            owner: raw.owner.parse().map_err(Error::signer)?,
+            connection_id: raw
+                .connection_id
+                .parse()
+                .map_err(|e| Error::invalid_connection_id(raw.connection_id.clone(), e))?,
+            version: raw.version,
+        })
+    }
+}
+
+impl From<MsgRegisterInterchainAccount> for RawMsgRegisterInterchainAccount {
+    fn from(value: MsgRegisterInterchainAccount) -> Self {
+        RawMsgRegisterInterchainAccount {
+            owner: value.owner.to_string(),
+            connection_id: value.connection_id.to_string(),
+            version: value.version,
+        }
+    }
+}
+
+impl TryFrom<Any> for MsgRegisterInterchainAccount {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => Self::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_roundtrip() {
+        let msg = MsgRegisterInterchainAccount {
+            owner: "cosmos1owner".parse().unwrap(),
+            connection_id: ConnectionId::new(0),
+            version: String::new(),
+        };
+
+        let any = msg.clone().to_any();
+        assert_eq!(any.type_url, TYPE_URL);
+        assert_eq!(MsgRegisterInterchainAccount::try_from(any).unwrap(), msg);
+
+        let raw = RawMsgRegisterInterchainAccount {
+            connection_id: "not a connection".to_string(),
+            ..msg.into()
+        };
+        assert!(MsgRegisterInterchainAccount::try_from(raw).is_err());
+    }
+}
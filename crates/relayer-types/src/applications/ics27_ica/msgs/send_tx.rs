@@ -0,0 +1,126 @@
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_accounts::v1::InterchainAccountPacketData as RawInterchainAccountPacketData;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::applications::ics27_ica::error::Error;
+use crate::applications::ics27_ica::packet_data::InterchainAccountPacketData;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.interchain_accounts.controller.v1.MsgSendTx";
+
+/// Protobuf definition of `MsgSendTx`, which is missing from `ibc-proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgSendTx {
+    #[prost(string, tag = "1")]
+    pub owner: String,
+    #[prost(string, tag = "2")]
+    pub connection_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub packet_data: Option<RawInterchainAccountPacketData>,
+    /// Relative timeout, in nanoseconds
+    #[prost(uint64, tag = "4")]
+    pub relative_timeout: u64,
+}
+
+/// Send a transaction to be executed by the interchain account of the `owner`
+/// account of the controller chain, on the host chain at the other end of the given connection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgSendTx {
+    pub owner: Signer,
+    pub connection_id: ConnectionId,
+    pub packet_data: InterchainAccountPacketData,
+    /// The timeout of the packet, relative to the timestamp of the controller chain
+    pub relative_timeout: Duration,
+}
+
+impl Msg for MsgSendTx {
+    type ValidationError = Error;
+    type Raw = RawMsgSendTx;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgSendTx> for MsgSendTx {}
+
+impl TryFrom<RawMsgSendTx> for MsgSendTx {
+    type Error = Error;
+
+    fn try_from(raw: RawMsgSendTx) -> Result<Self, Self::Error> {
+        Ok(Self {
+            owner: raw.owner.parse().map_err(Error::signer)?,
+            connection_id: raw
+                .connection_id
+                .parse()
+                .map_err(|e| Error::invalid_connection_id(raw.connection_id.clone(), e))?,
+            packet_data: raw
+                .packet_data
+                .ok_or_else(Error::empty_packet_data)?
+                .try_into()?,
+            relative_timeout: Duration::from_nanos(raw.relative_timeout),
+        })
+    }
+}
+
+impl From<MsgSendTx> for RawMsgSendTx {
+    fn from(value: MsgSendTx) -> Self {
+        RawMsgSendTx {
+            owner: value.owner.to_string(),
+            connection_id: value.connection_id.to_string(),
+            packet_data: Some(value.packet_data.into()),
+            relative_timeout: value.relative_timeout.as_nanos() as u64,
+        }
+    }
+}
+
+impl TryFrom<Any> for MsgSendTx {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => Self::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_tx_roundtrip() {
+        let msg = MsgSendTx {
+            owner: "cosmos1owner".parse().unwrap(),
+            connection_id: ConnectionId::new(1),
+            packet_data: InterchainAccountPacketData::new(
+                vec![Any {
+                    type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                    value: vec![1, 2, 3],
+                }],
+                String::new(),
+            ),
+            relative_timeout: Duration::from_secs(600),
+        };
+
+        let any = msg.clone().to_any();
+        assert_eq!(any.type_url, TYPE_URL);
+        assert_eq!(MsgSendTx::try_from(any).unwrap(), msg);
+
+        let raw = RawMsgSendTx {
+            packet_data: None,
+            ..msg.into()
+        };
+        assert!(MsgSendTx::try_from(raw).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_accounts::v1::{
+    CosmosTx, InterchainAccountPacketData as RawInterchainAccountPacketData, Type,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::applications::ics27_ica::error::Error;
+use crate::prelude::*;
+use crate::tx_msg::encode_message;
+
+/// The data of a packet sent by the controller of an interchain account, holding
+/// the messages to execute on the host chain in a single transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterchainAccountPacketData {
+    /// The messages to execute on the host chain, signed by the interchain account
+    pub messages: Vec<Any>,
+    /// An optional memo
+    pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+    pub fn new(messages: Vec<Any>, memo: String) -> Self {
+        Self { messages, memo }
+    }
+}
+
+impl Protobuf<RawInterchainAccountPacketData> for InterchainAccountPacketData {}
+
+impl TryFrom<RawInterchainAccountPacketData> for InterchainAccountPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawInterchainAccountPacketData) -> Result<Self, Self::Error> {
+        if raw.r#type != Type::ExecuteTx as i32 {
+            return Err(Error::unsupported_packet_type(raw.r#type));
+        }
+
+        let tx = CosmosTx::decode(raw.data.as_slice()).map_err(Error::decode_cosmos_tx)?;
+
+        Ok(Self {
+            messages: tx.messages,
+            memo: raw.memo,
+        })
+    }
+}
+
+impl From<InterchainAccountPacketData> for RawInterchainAccountPacketData {
+    fn from(value: InterchainAccountPacketData) -> Self {
+        let tx = CosmosTx {
+            messages: value.messages,
+        };
+
+        RawInterchainAccountPacketData {
+            r#type: Type::ExecuteTx as i32,
+            // Encoding into a `Vec` cannot fail, as it grows as needed
+            data: encode_message(&tx).expect("encoding `CosmosTx` into a `Vec`"),
+            memo: value.memo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_data_roundtrip() {
+        let data = InterchainAccountPacketData::new(
+            vec![Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: vec![1, 2, 3],
+            }],
+            "memo".to_string(),
+        );
+
+        let raw = RawInterchainAccountPacketData::from(data.clone());
+        assert_eq!(raw.r#type, Type::ExecuteTx as i32);
+        assert_eq!(InterchainAccountPacketData::try_from(raw).unwrap(), data);
+
+        let unspecified = RawInterchainAccountPacketData {
+            r#type: Type::Unspecified as i32,
+            data: vec![],
+            memo: String::new(),
+        };
+        assert!(InterchainAccountPacketData::try_from(unspecified).is_err());
+    }
+}
@@ -12,7 +12,7 @@ use ibc_proto::ibc::core::channel::v1::{
     IdentifiedChannel as RawIdentifiedChannel,
 };
 
-use crate::core::ics04_channel::{error::Error, version::Version};
+use crate::core::ics04_channel::{error::Error, packet::Sequence, version::Version};
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -73,6 +73,24 @@ impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
     }
 }
 
+/// Protobuf definition of the `Channel` of ibc-go v8, which extends the one
+/// of `ibc-proto` with the sequence of the latest upgrade of the channel.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawChannelEnd {
+    #[prost(int32, tag = "1")]
+    pub state: i32,
+    #[prost(int32, tag = "2")]
+    pub ordering: i32,
+    #[prost(message, optional, tag = "3")]
+    pub counterparty: Option<RawCounterparty>,
+    #[prost(string, repeated, tag = "4")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "5")]
+    pub version: String,
+    #[prost(uint64, tag = "6")]
+    pub upgrade_sequence: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChannelEnd {
     pub state: State,
@@ -80,14 +98,17 @@ pub struct ChannelEnd {
     pub remote: Counterparty,
     pub connection_hops: Vec<ConnectionId>,
     pub version: Version,
+    /// The sequence of the latest upgrade attempted on this channel end,
+    /// zero if it was never upgraded.
+    pub upgrade_sequence: Sequence,
 }
 
 impl Display for ChannelEnd {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(
             f,
-            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {} }}",
-            self.state, self.ordering, self.remote, PrettySlice(&self.connection_hops), self.version
+            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {}, upgrade_sequence: {} }}",
+            self.state, self.ordering, self.remote, PrettySlice(&self.connection_hops), self.version, self.upgrade_sequence
         )
     }
 }
@@ -100,11 +121,47 @@ impl Default for ChannelEnd {
             remote: Counterparty::default(),
             connection_hops: Vec::new(),
             version: Version::default(),
+            upgrade_sequence: Sequence::default(),
         }
     }
 }
 
-impl Protobuf<RawChannel> for ChannelEnd {}
+impl Protobuf<RawChannelEnd> for ChannelEnd {}
+
+impl TryFrom<RawChannelEnd> for ChannelEnd {
+    type Error = Error;
+
+    fn try_from(value: RawChannelEnd) -> Result<Self, Self::Error> {
+        let raw_channel = RawChannel {
+            state: value.state,
+            ordering: value.ordering,
+            counterparty: value.counterparty,
+            connection_hops: value.connection_hops,
+            version: value.version,
+        };
+
+        let mut channel_end = ChannelEnd::try_from(raw_channel)?;
+        channel_end.upgrade_sequence = value.upgrade_sequence.into();
+
+        Ok(channel_end)
+    }
+}
+
+impl From<ChannelEnd> for RawChannelEnd {
+    fn from(value: ChannelEnd) -> Self {
+        let upgrade_sequence = value.upgrade_sequence.into();
+        let raw_channel = RawChannel::from(value);
+
+        RawChannelEnd {
+            state: raw_channel.state,
+            ordering: raw_channel.ordering,
+            counterparty: raw_channel.counterparty,
+            connection_hops: raw_channel.connection_hops,
+            version: raw_channel.version,
+            upgrade_sequence,
+        }
+    }
+}
 
 impl TryFrom<RawChannel> for ChannelEnd {
     type Error = Error;
@@ -175,6 +232,7 @@ impl ChannelEnd {
             remote,
             connection_hops,
             version,
+            upgrade_sequence: Sequence::default(),
         }
     }
 
@@ -383,6 +441,8 @@ pub enum State {
     TryOpen = 2,
     Open = 3,
     Closed = 4,
+    Flushing = 5,
+    FlushComplete = 6,
 }
 
 impl State {
@@ -394,6 +454,8 @@ impl State {
             Self::TryOpen => "TRYOPEN",
             Self::Open => "OPEN",
             Self::Closed => "CLOSED",
+            Self::Flushing => "FLUSHING",
+            Self::FlushComplete => "FLUSHCOMPLETE",
         }
     }
 
@@ -405,6 +467,8 @@ impl State {
             2 => Ok(Self::TryOpen),
             3 => Ok(Self::Open),
             4 => Ok(Self::Closed),
+            5 => Ok(Self::Flushing),
+            6 => Ok(Self::FlushComplete),
             _ => Err(Error::unknown_state(s)),
         }
     }
@@ -414,6 +478,14 @@ impl State {
         self == State::Open
     }
 
+    /// Returns whether or not the channel with this state is being upgraded,
+    /// and waits for the packets in flight to be flushed, or has flushed them.
+    ///
+    /// Packets are still relayed on the channel in these states.
+    pub fn is_upgrading(self) -> bool {
+        matches!(self, State::Flushing | State::FlushComplete)
+    }
+
     /// Returns whether or not the channel with this state
     /// has progressed less or the same than the argument.
     ///
@@ -608,4 +680,29 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn channel_end_with_upgrade_sequence() {
+        use super::{RawChannelEnd, State};
+        use ibc_proto::protobuf::Protobuf;
+        use prost::Message;
+
+        let raw_channel_end = get_dummy_raw_channel_end();
+
+        let raw = RawChannelEnd {
+            state: 5,
+            ordering: raw_channel_end.ordering,
+            counterparty: raw_channel_end.counterparty,
+            connection_hops: raw_channel_end.connection_hops,
+            version: raw_channel_end.version,
+            upgrade_sequence: 2,
+        };
+
+        let channel_end =
+            <ChannelEnd as Protobuf<RawChannelEnd>>::decode_vec(&raw.encode_to_vec()).unwrap();
+
+        assert_eq!(channel_end.state, State::Flushing);
+        assert_eq!(channel_end.upgrade_sequence, 2u64.into());
+        assert_eq!(RawChannelEnd::from(channel_end), raw);
+    }
 }
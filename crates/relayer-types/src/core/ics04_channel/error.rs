@@ -99,6 +99,18 @@ define_error! {
         MissingChannel
             | _ | { "missing channel end" },
 
+        MissingUpgrade
+            | _ | { "missing channel upgrade" },
+
+        MissingUpgradeFields
+            | _ | { "missing channel upgrade fields" },
+
+        MissingUpgradeTimeout
+            | _ | { "missing channel upgrade timeout" },
+
+        MissingErrorReceipt
+            | _ | { "missing channel upgrade error receipt" },
+
         InvalidVersionLengthConnection
             | _ | { "single version must be negociated on connection before opening channel" },
 
@@ -0,0 +1,360 @@
+//! Data types for the channel upgrade handshake introduced in ibc-go v8,
+//! which allows to change the version, ordering or connection of an open
+//! channel, e.g. to add ICS-29 fees to an existing transfer channel.
+//!
+//! The protobuf definitions of these types are missing from `ibc-proto`,
+//! and are therefore defined here.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+use serde::{Deserialize, Serialize};
+
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::version::Version;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+use crate::utils::pretty::PrettySlice;
+use crate::Height;
+
+/// Protobuf definition of `ibc.core.channel.v1.UpgradeFields`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawUpgradeFields {
+    #[prost(int32, tag = "1")]
+    pub ordering: i32,
+    #[prost(string, repeated, tag = "2")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "3")]
+    pub version: String,
+}
+
+/// Protobuf definition of `ibc.core.channel.v1.Timeout`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawTimeout {
+    #[prost(message, optional, tag = "1")]
+    pub height: Option<RawHeight>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+/// Protobuf definition of `ibc.core.channel.v1.Upgrade`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawUpgrade {
+    #[prost(message, optional, tag = "1")]
+    pub fields: Option<RawUpgradeFields>,
+    #[prost(message, optional, tag = "2")]
+    pub timeout: Option<RawTimeout>,
+    #[prost(uint64, tag = "3")]
+    pub next_sequence_send: u64,
+}
+
+/// Protobuf definition of `ibc.core.channel.v1.ErrorReceipt`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawErrorReceipt {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+/// The parameters of the channel that an upgrade changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeFields {
+    pub ordering: Order,
+    pub connection_hops: Vec<ConnectionId>,
+    pub version: Version,
+}
+
+impl UpgradeFields {
+    pub fn new(ordering: Order, connection_hops: Vec<ConnectionId>, version: Version) -> Self {
+        Self {
+            ordering,
+            connection_hops,
+            version,
+        }
+    }
+}
+
+impl Display for UpgradeFields {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "UpgradeFields {{ ordering: {}, connection_hops: {}, version: {} }}",
+            self.ordering,
+            PrettySlice(&self.connection_hops),
+            self.version
+        )
+    }
+}
+
+impl Protobuf<RawUpgradeFields> for UpgradeFields {}
+
+impl TryFrom<RawUpgradeFields> for UpgradeFields {
+    type Error = Error;
+
+    fn try_from(value: RawUpgradeFields) -> Result<Self, Self::Error> {
+        let ordering = Order::from_i32(value.ordering)?;
+
+        let connection_hops = value
+            .connection_hops
+            .iter()
+            .map(|conn_id| ConnectionId::from_str(conn_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::identifier)?;
+
+        Ok(Self::new(ordering, connection_hops, value.version.into()))
+    }
+}
+
+impl From<UpgradeFields> for RawUpgradeFields {
+    fn from(value: UpgradeFields) -> Self {
+        Self {
+            ordering: value.ordering as i32,
+            connection_hops: value
+                .connection_hops
+                .iter()
+                .map(|conn_id| conn_id.to_string())
+                .collect(),
+            version: value.version.to_string(),
+        }
+    }
+}
+
+/// The height and the time on the counterparty chain after which
+/// the upgrade is aborted, if it has not completed on that chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeTimeout {
+    pub height: TimeoutHeight,
+    pub timestamp: Timestamp,
+}
+
+impl UpgradeTimeout {
+    /// Returns whether the upgrade has timed out on a chain whose
+    /// latest block has the given height and timestamp.
+    ///
+    /// Unlike packet timeouts, the upgrade expires once the chain
+    /// reaches its timeout height or timestamp.
+    pub fn has_expired(&self, height: Height, timestamp: Timestamp) -> bool {
+        let height_expired = match self.height {
+            TimeoutHeight::At(timeout_height) => height >= timeout_height,
+            TimeoutHeight::Never => false,
+        };
+
+        let timestamp_expired = self.timestamp != Timestamp::none()
+            && timestamp != Timestamp::none()
+            && timestamp.nanoseconds() >= self.timestamp.nanoseconds();
+
+        height_expired || timestamp_expired
+    }
+}
+
+impl Display for UpgradeTimeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "UpgradeTimeout {{ height: {}, timestamp: {} }}",
+            self.height, self.timestamp
+        )
+    }
+}
+
+impl Protobuf<RawTimeout> for UpgradeTimeout {}
+
+impl TryFrom<RawTimeout> for UpgradeTimeout {
+    type Error = Error;
+
+    fn try_from(value: RawTimeout) -> Result<Self, Self::Error> {
+        let height = value
+            .height
+            .try_into()
+            .map_err(|_| Error::invalid_timeout_height())?;
+
+        let timestamp = Timestamp::from_nanoseconds(value.timestamp)
+            .map_err(Error::invalid_packet_timestamp)?;
+
+        Ok(Self { height, timestamp })
+    }
+}
+
+impl From<UpgradeTimeout> for RawTimeout {
+    fn from(value: UpgradeTimeout) -> Self {
+        Self {
+            height: value.height.into(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// An upgrade of a channel, as proposed or agreed upon by one of its ends.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Upgrade {
+    pub fields: UpgradeFields,
+    pub timeout: UpgradeTimeout,
+    /// The next sequence number of the packets sent on the channel when
+    /// it started flushing, i.e. once no more packets could be sent on it.
+    pub next_sequence_send: Sequence,
+}
+
+impl Display for Upgrade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "Upgrade {{ fields: {}, timeout: {}, next_sequence_send: {} }}",
+            self.fields, self.timeout, self.next_sequence_send
+        )
+    }
+}
+
+impl Protobuf<RawUpgrade> for Upgrade {}
+
+impl TryFrom<RawUpgrade> for Upgrade {
+    type Error = Error;
+
+    fn try_from(value: RawUpgrade) -> Result<Self, Self::Error> {
+        Ok(Self {
+            fields: value
+                .fields
+                .ok_or_else(Error::missing_upgrade_fields)?
+                .try_into()?,
+            timeout: value
+                .timeout
+                .ok_or_else(Error::missing_upgrade_timeout)?
+                .try_into()?,
+            next_sequence_send: value.next_sequence_send.into(),
+        })
+    }
+}
+
+impl From<Upgrade> for RawUpgrade {
+    fn from(value: Upgrade) -> Self {
+        Self {
+            fields: Some(value.fields.into()),
+            timeout: Some(value.timeout.into()),
+            next_sequence_send: value.next_sequence_send.into(),
+        }
+    }
+}
+
+/// Records the failure of the upgrade with the given sequence
+/// on one end of the channel, so that the other end can cancel it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReceipt {
+    pub sequence: Sequence,
+    pub message: String,
+}
+
+impl Display for ErrorReceipt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "ErrorReceipt {{ sequence: {}, message: {} }}",
+            self.sequence, self.message
+        )
+    }
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+    type Error = Error;
+
+    fn try_from(value: RawErrorReceipt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: value.sequence.into(),
+            message: value.message,
+        })
+    }
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+    fn from(value: ErrorReceipt) -> Self {
+        Self {
+            sequence: value.sequence.into(),
+            message: value.message,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::core::ics24_host::identifier::ConnectionId;
+    use crate::prelude::*;
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use super::{RawErrorReceipt, RawTimeout, RawUpgrade, RawUpgradeFields};
+
+    /// Returns dummy `RawUpgradeFields`, for testing only!
+    pub fn get_dummy_raw_upgrade_fields() -> RawUpgradeFields {
+        RawUpgradeFields {
+            ordering: 1,
+            connection_hops: vec![ConnectionId::default().to_string()],
+            version: "ics20-1".to_string(),
+        }
+    }
+
+    /// Returns a dummy `RawUpgrade`, for testing only!
+    pub fn get_dummy_raw_upgrade() -> RawUpgrade {
+        RawUpgrade {
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            timeout: Some(RawTimeout {
+                height: Some(RawHeight {
+                    revision_number: 0,
+                    revision_height: 100,
+                }),
+                timestamp: 0,
+            }),
+            next_sequence_send: 1,
+        }
+    }
+
+    /// Returns a dummy `RawErrorReceipt`, for testing only!
+    pub fn get_dummy_raw_error_receipt() -> RawErrorReceipt {
+        RawErrorReceipt {
+            sequence: 1,
+            message: "upgrade handshake failed".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use test_log::test;
+
+    use super::test_util::get_dummy_raw_upgrade;
+    use super::*;
+
+    #[test]
+    fn upgrade_to_and_from_raw() {
+        let raw = get_dummy_raw_upgrade();
+        let upgrade = Upgrade::try_from(raw.clone()).unwrap();
+        assert_eq!(RawUpgrade::from(upgrade), raw);
+    }
+
+    #[test]
+    fn upgrade_without_fields() {
+        let raw = RawUpgrade {
+            fields: None,
+            ..get_dummy_raw_upgrade()
+        };
+
+        assert!(Upgrade::try_from(raw).is_err());
+    }
+
+    #[test]
+    fn upgrade_timeout_expiry() {
+        let timeout = UpgradeTimeout {
+            height: TimeoutHeight::At(Height::new(0, 100).unwrap()),
+            timestamp: Timestamp::none(),
+        };
+
+        assert!(!timeout.has_expired(Height::new(0, 99).unwrap(), Timestamp::now()));
+        assert!(timeout.has_expired(Height::new(0, 100).unwrap(), Timestamp::now()));
+    }
+}
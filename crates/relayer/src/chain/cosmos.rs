@@ -11,12 +11,14 @@ use num_bigint::BigInt;
 use std::{cmp::Ordering, thread};
 
 use tokio::runtime::Runtime as TokioRuntime;
+use tokio::task::JoinHandle;
 use tonic::{codegen::http::Uri, metadata::AsciiMetadataValue};
 use tracing::{error, instrument, trace, warn};
 
 use ibc_proto::cosmos::base::node::v1beta1::ConfigResponse;
 use ibc_proto::cosmos::staking::v1beta1::Params as StakingParams;
 use ibc_proto::protobuf::Protobuf;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TmClientState,
@@ -31,14 +33,16 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 };
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradeErrorsPath, ChannelUpgradesPath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
+    SeqRecvsPath,
 };
 use ibc_relayer_types::core::ics24_host::{
     ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH,
@@ -48,10 +52,11 @@ use ibc_relayer_types::Height as ICSHeight;
 
 use tendermint::block::Height as TmHeight;
 use tendermint::node::info::TxIndexStatus;
+use tendermint::Hash as TxHash;
 use tendermint_light_client_verifier::types::LightBlock as TmLightBlock;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::endpoint::status;
-use tendermint_rpc::{Client, HttpClient, Order};
+use tendermint_rpc::{Client, HttpClient, Order, Url};
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
@@ -60,33 +65,36 @@ use crate::chain::cosmos::batch::{
     sequential_send_batched_messages_and_wait_commit,
 };
 use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::cosmos::endpoints::{unreachable_endpoint, Endpoints, RpcEndpoint};
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
 use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
+use crate::chain::cosmos::query::fee::query_incentivized_packets;
+use crate::chain::cosmos::query::ica::query_interchain_account;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
-    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
+    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_tx_outcomes,
+    query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
-use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{
     default_gas_from_config, gas_multiplier_from_config, max_gas_from_config,
 };
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::cosmos::wallet::WalletPool;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck, TxOutcome};
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::{parse_gas_prices, ChainConfig, EventSource, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::monitor::{EventMonitor, TxMonitorCmd};
+use crate::event::monitor::{pull::PullEventMonitor, EventMonitor, TxMonitorCmd};
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
@@ -99,10 +107,14 @@ use crate::util::pretty::{
 pub mod batch;
 pub mod client;
 pub mod compatibility;
+pub mod cost;
 pub mod encode;
+pub mod endpoints;
 pub mod estimate;
+pub mod evidence;
 pub mod fee;
 pub mod gas;
+pub mod gas_table;
 pub mod query;
 pub mod retry;
 pub mod simulate;
@@ -110,6 +122,7 @@ pub mod tx;
 pub mod types;
 pub mod version;
 pub mod wait;
+pub mod wallet;
 
 /// Defines an upper limit on how large any transaction can be.
 /// This upper limit is defined as a fraction relative to the block's
@@ -129,14 +142,15 @@ pub const BLOCK_MAX_BYTES_MAX_FRACTION: f64 = 0.9;
 pub struct CosmosSdkChain {
     config: ChainConfig,
     tx_config: TxConfig,
-    rpc_client: HttpClient,
-    grpc_addr: Uri,
+    endpoints: Arc<Endpoints>,
+    /// Background task checking the health of the endpoints, if there are several
+    health_checks: Option<JoinHandle<()>>,
     light_client: TmLightClient,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing<Secp256k1KeyPair>,
 
-    /// A cached copy of the account information
-    account: Option<Account>,
+    /// The wallets submitting transactions, along with a cached copy of their account information
+    wallets: WalletPool,
 
     tx_monitor_cmd: Option<TxMonitorCmd>,
 }
@@ -147,6 +161,46 @@ impl CosmosSdkChain {
         &self.config
     }
 
+    /// The client for the RPC endpoint in use.
+    fn rpc_client(&self) -> HttpClient {
+        self.endpoints.rpc().client
+    }
+
+    /// The address of the RPC endpoint in use.
+    fn rpc_addr(&self) -> Url {
+        self.endpoints.rpc().url
+    }
+
+    /// The address of the gRPC endpoint in use.
+    fn grpc_addr(&self) -> Uri {
+        self.endpoints.grpc_addr()
+    }
+
+    /// The configuration for submitting transactions to the endpoints in use.
+    fn tx_config(&self) -> TxConfig {
+        let rpc = self.endpoints.rpc();
+
+        TxConfig {
+            rpc_client: rpc.client,
+            rpc_address: rpc.url,
+            grpc_address: self.endpoints.grpc_addr(),
+            ..self.tx_config.clone()
+        }
+    }
+
+    /// Point the light client to the RPC endpoint in use, if it changed
+    /// since the last time and the identifier of its node is known.
+    fn sync_light_client(&mut self) -> Result<(), Error> {
+        let rpc = self.endpoints.rpc();
+
+        match rpc.peer_id {
+            Some(peer_id) if self.light_client.primary() != &rpc.url => self
+                .light_client
+                .set_primary(&self.config, &rpc.url, peer_id),
+            _ => Ok(()),
+        }
+    }
+
     /// The maximum size of any transaction sent by the relayer to this chain
     fn max_tx_size(&self) -> usize {
         self.config.max_tx_size.into()
@@ -158,6 +212,19 @@ impl CosmosSdkChain {
             .map_err(Error::key_base)
     }
 
+    /// The key of the given wallet.
+    fn wallet_key(&self, wallet: usize) -> Result<Secp256k1KeyPair, Error> {
+        if wallet == 0 {
+            return self.key();
+        }
+
+        let key_name = &self.wallets.get(wallet).key_name;
+
+        self.keybase()
+            .get_key(key_name)
+            .map_err(|e| Error::key_not_found(key_name.clone(), e))
+    }
+
     /// Fetches the trusting period as a `Duration` from the chain config.
     /// If no trusting period exists in the config, the trusting period is calculated
     /// as two-thirds of the `unbonding_period`.
@@ -221,11 +288,11 @@ impl CosmosSdkChain {
 
         // Check on the configured max_tx_size against the consensus parameters at latest height
         let result = self
-            .block_on(self.rpc_client.consensus_params(latest_height))
+            .block_on(self.rpc_client().consensus_params(latest_height))
             .map_err(|e| {
                 Error::config_validation_json_rpc(
                     self.id().clone(),
-                    self.config.rpc_addr.to_string(),
+                    self.rpc_addr().to_string(),
                     "/consensus_params".to_string(),
                     e,
                 )
@@ -278,20 +345,40 @@ impl CosmosSdkChain {
     fn init_event_monitor(&mut self) -> Result<TxMonitorCmd, Error> {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            self.rt.clone(),
-        )
-        .map_err(Error::event_monitor)?;
+        match self.config.event_source {
+            EventSource::Push => {
+                let (mut event_monitor, monitor_tx) = EventMonitor::with_fallbacks(
+                    self.config.id.clone(),
+                    self.config.websocket_addr.clone(),
+                    self.config.fallback_websocket_addrs.clone(),
+                    self.rt.clone(),
+                )
+                .map_err(Error::event_monitor)?;
+
+                event_monitor
+                    .init_subscriptions()
+                    .map_err(Error::event_monitor)?;
+
+                thread::spawn(move || event_monitor.run());
 
-        event_monitor
-            .init_subscriptions()
-            .map_err(Error::event_monitor)?;
+                Ok(monitor_tx)
+            }
+            EventSource::Pull { interval } => {
+                let (event_monitor, monitor_tx) = PullEventMonitor::new(
+                    self.config.id.clone(),
+                    self.rpc_addr(),
+                    interval,
+                    self.rt.clone(),
+                )
+                .map_err(Error::event_monitor)?;
 
-        thread::spawn(move || event_monitor.run());
+                let event_monitor = event_monitor.with_endpoints(self.endpoints.clone());
 
-        Ok(monitor_tx)
+                thread::spawn(move || event_monitor.run());
+
+                Ok(monitor_tx)
+            }
+        }
     }
 
     /// Query the chain staking parameters
@@ -302,7 +389,7 @@ impl CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -349,7 +436,7 @@ impl CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::cosmos::base::node::v1beta1::service_client::ServiceClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -429,8 +516,8 @@ impl CosmosSdkChain {
         }
 
         let response = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_client(),
+            &self.rpc_addr(),
             path,
             data.to_string(),
             height,
@@ -468,8 +555,8 @@ impl CosmosSdkChain {
         let path = SDK_UPGRADE_QUERY_PATH.into();
 
         let response: QueryResponse = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_client(),
+            &self.rpc_addr(),
             path,
             Path::Upgrade(query_data).to_string(),
             TmHeight::try_from(query_height.revision_height()).map_err(Error::invalid_height)?,
@@ -490,12 +577,12 @@ impl CosmosSdkChain {
         crate::telemetry!(query, self.id(), "status");
 
         let status = self
-            .block_on(self.rpc_client.status())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().status())
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         if status.sync_info.catching_up {
             return Err(Error::chain_not_caught_up(
-                self.config.rpc_addr.to_string(),
+                self.rpc_addr().to_string(),
                 self.config().id.clone(),
             ));
         }
@@ -510,8 +597,8 @@ impl CosmosSdkChain {
 
         let status = self.rt.block_on(query_status(
             self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_client(),
+            &self.rpc_addr(),
         ))?;
 
         Ok(status.height)
@@ -534,30 +621,35 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        let wallets = self.wallets.select(&proto_msgs);
+        let key_pairs = wallets
+            .iter()
+            .map(|&wallet| self.wallet_key(wallet))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let tx_config = self.tx_config();
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let mut signers = self
+            .wallets
+            .signers(&wallets, key_pairs, &tx_config.grpc_address)
+            .await?;
 
         if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
-                &self.tx_config,
+                &tx_config,
                 self.config.max_msg_num,
                 self.config.max_tx_size,
-                &key_pair,
-                account,
+                &mut signers,
                 &self.config.memo_prefix,
                 proto_msgs,
             )
             .await
         } else {
             send_batched_messages_and_wait_commit(
-                &self.tx_config,
+                &tx_config,
                 self.config.max_msg_num,
                 self.config.max_tx_size,
-                &key_pair,
-                account,
+                &mut signers,
                 &self.config.memo_prefix,
                 proto_msgs,
             )
@@ -582,18 +674,24 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        let wallets = self.wallets.select(&proto_msgs);
+        let key_pairs = wallets
+            .iter()
+            .map(|&wallet| self.wallet_key(wallet))
+            .collect::<Result<Vec<_>, Error>>()?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let tx_config = self.tx_config();
+
+        let mut signers = self
+            .wallets
+            .signers(&wallets, key_pairs, &tx_config.grpc_address)
+            .await?;
 
         send_batched_messages_and_wait_check_tx(
-            &self.tx_config,
+            &tx_config,
             self.config.max_msg_num,
             self.config.max_tx_size,
-            &key_pair,
-            account,
+            &mut signers,
             &self.config.memo_prefix,
             proto_msgs,
         )
@@ -616,8 +714,8 @@ impl CosmosSdkChain {
             tendermint::block::Height::try_from(block_height.revision_height()).unwrap();
 
         let response = self
-            .block_on(self.rpc_client.block_results(tm_height))
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().block_results(tm_height))
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         let response_height = ICSHeight::new(self.id().version(), u64::from(response.height))
             .map_err(|_| Error::invalid_height_no_source())?;
@@ -657,7 +755,7 @@ impl CosmosSdkChain {
 
         for seq in request.sequences.iter().copied() {
             let response = self
-                .block_on(self.rpc_client.block_search(
+                .block_on(self.rpc_client().block_search(
                     packet_query(request, seq),
                     // We only need the first page
                     1,
@@ -675,7 +773,7 @@ impl CosmosSdkChain {
                     // blocks first.
                     Order::Descending,
                 ))
-                .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
             for block in response.blocks.into_iter().map(|response| response.block) {
                 let response_height =
@@ -710,32 +808,46 @@ impl ChainEndpoint for CosmosSdkChain {
     type SigningKeyPair = Secp256k1KeyPair;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let endpoints = Arc::new(Endpoints::from_config(&config)?);
+
+        // Start with the best endpoints if there are several
+        if endpoints.has_fallbacks() {
+            rt.block_on(endpoints.check_health());
+        }
 
-        let light_client = rt.block_on(init_light_client(&rpc_client, &config))?;
+        let light_client = rt.block_on(init_light_client(&endpoints.rpc(), &config))?;
 
         // Initialize key store and load key
         let keybase =
             KeyRing::new_secp256k1(config.key_store_type, &config.account_prefix, &config.id)
                 .map_err(Error::key_base)?;
 
-        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
-            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
-
         let tx_config = TxConfig::try_from(&config)?;
 
+        // Fail early if any of the additional wallets is missing, rather than on its first transaction
+        for key_name in &config.extra_key_names {
+            keybase
+                .get_key(key_name)
+                .map_err(|e| Error::key_not_found(key_name.clone(), e))?;
+        }
+
+        let wallets = WalletPool::new(config.key_name.clone(), config.extra_key_names.clone());
+
+        let health_checks = endpoints
+            .has_fallbacks()
+            .then(|| endpoints.spawn_health_checks(&rt));
+
         // Retrieve the version specification of this chain
 
         let chain = Self {
             config,
-            rpc_client,
-            grpc_addr,
+            endpoints,
+            health_checks,
             light_client,
             rt,
             keybase,
             tx_config,
-            account: None,
+            wallets,
             tx_monitor_cmd: None,
         };
 
@@ -743,6 +855,10 @@ impl ChainEndpoint for CosmosSdkChain {
     }
 
     fn shutdown(self) -> Result<(), Error> {
+        if let Some(health_checks) = self.health_checks {
+            health_checks.abort();
+        }
+
         if let Some(monitor_tx) = self.tx_monitor_cmd {
             monitor_tx.shutdown().map_err(Error::event_monitor)?;
         }
@@ -803,6 +919,13 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(HealthCheck::Healthy)
     }
 
+    /// Fail over to another endpoint if the error shows the one in use is unreachable.
+    fn on_error(&self, e: &Error) {
+        if let Some(kind) = unreachable_endpoint(e) {
+            self.endpoints.report_failure(kind);
+        }
+    }
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -810,6 +933,8 @@ impl ChainEndpoint for CosmosSdkChain {
         target: ICSHeight,
         client_state: &AnyClientState,
     ) -> Result<Self::LightBlock, Error> {
+        self.sync_light_client()?;
+
         self.light_client
             .verify(trusted, target, client_state)
             .map(|v| v.target)
@@ -822,6 +947,8 @@ impl ChainEndpoint for CosmosSdkChain {
         update: &UpdateClient,
         client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        self.sync_light_client()?;
+
         self.light_client.check_misbehaviour(update, client_state)
     }
 
@@ -871,7 +998,7 @@ impl ChainEndpoint for CosmosSdkChain {
     }
 
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
-        let version_specs = self.block_on(fetch_version_specs(self.id(), &self.grpc_addr))?;
+        let version_specs = self.block_on(fetch_version_specs(self.id(), &self.grpc_addr()))?;
         Ok(version_specs.ibc_go)
     }
 
@@ -885,7 +1012,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let account = key.account();
 
         let denom = denom.unwrap_or(&self.config.gas_price.denom);
-        let balance = self.block_on(query_balance(&self.grpc_addr, &account, denom))?;
+        let balance = self.block_on(query_balance(&self.grpc_addr(), &account, denom))?;
 
         Ok(balance)
     }
@@ -899,13 +1026,13 @@ impl ChainEndpoint for CosmosSdkChain {
         };
         let account = key.account();
 
-        let balance = self.block_on(query_all_balances(&self.grpc_addr, &account))?;
+        let balance = self.block_on(query_all_balances(&self.grpc_addr(), &account))?;
 
         Ok(balance)
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
-        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr, &hash))?;
+        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr(), &hash))?;
 
         Ok(denom_trace)
     }
@@ -928,8 +1055,8 @@ impl ChainEndpoint for CosmosSdkChain {
         // Instead, we need to pull block height via `/abci_info` and then fetch block
         // metadata at the given height via `/blockchain` endpoint.
         let abci_info = self
-            .block_on(self.rpc_client.abci_info())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().abci_info())
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         // Query `/blockchain` endpoint to pull the block metadata corresponding to
         // the latest block that the application committed.
@@ -937,10 +1064,10 @@ impl ChainEndpoint for CosmosSdkChain {
         //  https://github.com/informalsystems/tendermint-rs/pull/1101
         let blocks = self
             .block_on(
-                self.rpc_client
+                self.rpc_client()
                     .blockchain(abci_info.last_block_height, abci_info.last_block_height),
             )
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?
             .block_metas;
 
         return if let Some(latest_app_block) = blocks.first() {
@@ -970,7 +1097,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1082,7 +1209,7 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<Vec<ICSHeight>, Error> {
         self.block_on(query_consensus_state_heights(
             self.id(),
-            &self.grpc_addr,
+            &self.grpc_addr(),
             request,
         ))
     }
@@ -1133,7 +1260,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1169,7 +1296,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1216,10 +1343,9 @@ impl ChainEndpoint for CosmosSdkChain {
             use ibc_proto::ibc::core::connection::v1 as connection;
             use tonic::IntoRequest;
 
-            let mut client =
-                connection::query_client::QueryClient::connect(chain.grpc_addr.clone())
-                    .await
-                    .map_err(Error::grpc_transport)?;
+            let mut client = connection::query_client::QueryClient::connect(chain.grpc_addr())
+                .await
+                .map_err(Error::grpc_transport)?;
 
             let mut request = connection::QueryConnectionRequest {
                 connection_id: connection_id.to_string(),
@@ -1289,7 +1415,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1329,7 +1455,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1384,6 +1510,66 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        crate::time!("query_upgrade");
+        crate::telemetry!(query, self.id(), "query_upgrade");
+
+        let res = self.query(
+            ChannelUpgradesPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        // The key is absent from the store if there is no such value for the channel end
+        if res.value.is_empty() {
+            return Err(Error::empty_response_value());
+        }
+
+        let upgrade = Upgrade::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((upgrade, Some(proof)))
+            }
+            IncludeProof::No => Ok((upgrade, None)),
+        }
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        crate::time!("query_upgrade_error");
+        crate::telemetry!(query, self.id(), "query_upgrade_error");
+
+        let res = self.query(
+            ChannelUpgradeErrorsPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        // The key is absent from the store if there is no such value for the channel end
+        if res.value.is_empty() {
+            return Err(Error::empty_response_value());
+        }
+
+        let error_receipt = ErrorReceipt::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((error_receipt, Some(proof)))
+            }
+            IncludeProof::No => Ok((error_receipt, None)),
+        }
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -1394,7 +1580,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1449,7 +1635,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1512,7 +1698,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1568,7 +1754,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1605,7 +1791,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1656,7 +1842,7 @@ impl ChainEndpoint for CosmosSdkChain {
                 let mut client = self
                     .block_on(
                         ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                            self.grpc_addr.clone(),
+                            self.grpc_addr(),
                         ),
                     )
                     .map_err(Error::grpc_transport)?;
@@ -1682,12 +1868,24 @@ impl ChainEndpoint for CosmosSdkChain {
 
         self.block_on(query_txs(
             self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_client(),
+            &self.rpc_addr(),
             request,
         ))
     }
 
+    fn query_tx_outcomes(&self, tx_hashes: Vec<TxHash>) -> Result<Option<Vec<TxOutcome>>, Error> {
+        crate::time!("query_tx_outcomes");
+
+        self.block_on(query_tx_outcomes(
+            self.id(),
+            &self.rpc_client(),
+            &self.rpc_addr(),
+            &self.tx_config.gas_config,
+            &tx_hashes,
+        ))
+    }
+
     /// This function queries transactions for packet events matching certain criteria.
     /// It returns at most one packet event for each sequence specified in the request.
     ///    Note - there is no way to format the packet query such that it asks for Tx-es with either
@@ -1710,15 +1908,15 @@ impl ChainEndpoint for CosmosSdkChain {
             // user passes the flag `packet-data-query-height`.
             Qualified::Equal(_) => self.block_on(query_packets_from_block(
                 self.id(),
-                &self.rpc_client,
-                &self.config.rpc_addr,
+                &self.rpc_client(),
+                &self.rpc_addr(),
                 &request,
             )),
             Qualified::SmallerEqual(_) => {
                 let tx_events = self.block_on(query_packets_from_txs(
                     self.id(),
-                    &self.rpc_client,
-                    &self.config.rpc_addr,
+                    &self.rpc_client(),
+                    &self.rpc_addr(),
                     &request,
                 ))?;
 
@@ -1776,13 +1974,14 @@ impl ChainEndpoint for CosmosSdkChain {
         };
 
         // TODO(hu55a1n1): use the `/header` RPC endpoint instead when we move to tendermint v0.35.x
+        let rpc_client = self.rpc_client();
         let rpc_call = match height.value() {
-            0 => self.rpc_client.latest_block(),
-            _ => self.rpc_client.block(height),
+            0 => rpc_client.latest_block(),
+            _ => rpc_client.block(height),
         };
         let response = self
             .block_on(rpc_call)
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
         Ok(response.block.header.into())
     }
 
@@ -1834,6 +2033,8 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
         crate::time!("build_header");
 
+        self.sync_light_client()?;
+
         // Get the light block at target_height from chain.
         let Verified { target, supporting } = self.light_client.header_and_minimal_set(
             trusted_height,
@@ -1850,28 +2051,34 @@ impl ChainEndpoint for CosmosSdkChain {
         port_id: &PortId,
         counterparty_payee: &Signer,
     ) -> Result<(), Error> {
-        let address = self.get_signer()?;
-        let key_pair = self.key()?;
+        // Every wallet may relay the packets of the channel, and thus earn their fees
+        for wallet in 0..self.wallets.count() {
+            let key_pair = self.wallet_key(wallet)?;
+            let address = key_pair_to_signer(&key_pair)?;
 
-        self.rt.block_on(maybe_register_counterparty_payee(
-            &self.tx_config,
-            &key_pair,
-            &mut self.account,
-            &self.config.memo_prefix,
-            channel_id,
-            port_id,
-            &address,
-            counterparty_payee,
-        ))
+            self.rt.block_on(maybe_register_counterparty_payee(
+                &self.tx_config(),
+                &key_pair,
+                &mut self.wallets.get_mut(wallet).account,
+                &self.config.memo_prefix,
+                channel_id,
+                port_id,
+                &address,
+                counterparty_payee,
+            ))?;
+        }
+
+        Ok(())
     }
 
     fn cross_chain_query(
         &self,
         requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        let rpc_client = self.rpc_client();
         let tasks = requests
             .into_iter()
-            .map(|req| cross_chain_query_via_rpc(&self.rpc_client, req))
+            .map(|req| cross_chain_query_via_rpc(&rpc_client, req))
             .collect::<Vec<_>>();
 
         let joined_tasks = join_all(tasks);
@@ -1883,6 +2090,36 @@ impl ChainEndpoint for CosmosSdkChain {
 
         Ok(responses)
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        crate::time!("query_incentivized_packets");
+        crate::telemetry!(query, self.id(), "query_incentivized_packets");
+
+        self.block_on(query_incentivized_packets(
+            &self.grpc_addr(),
+            channel_id,
+            port_id,
+        ))
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: &Signer,
+        connection_id: &ConnectionId,
+    ) -> Result<Signer, Error> {
+        crate::time!("query_interchain_account");
+        crate::telemetry!(query, self.id(), "query_interchain_account");
+
+        self.block_on(query_interchain_account(
+            &self.grpc_addr(),
+            owner,
+            connection_id,
+        ))
+    }
 }
 
 fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
@@ -1895,23 +2132,47 @@ fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
     });
 }
 
-/// Initialize the light client for the given chain using the given HTTP client
-/// to fetch the node identifier to be used as peer id in the light client.
+/// Initialize the light client for the given chain against the given RPC endpoint,
+/// fetching the node identifier to be used as peer id in the light client if not known,
+/// along with the witness nodes listed in the chain configuration.
 async fn init_light_client(
-    rpc_client: &HttpClient,
+    rpc: &RpcEndpoint,
     config: &ChainConfig,
 ) -> Result<TmLightClient, Error> {
     use tendermint_light_client_verifier::types::PeerId;
 
     crate::time!("init_light_client");
 
-    let peer_id: PeerId = rpc_client
-        .status()
-        .await
-        .map(|s| s.node_info.id)
-        .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+    let peer_id: PeerId = match rpc.peer_id {
+        Some(peer_id) => peer_id,
+        None => rpc
+            .client
+            .status()
+            .await
+            .map(|s| s.node_info.id)
+            .map_err(|e| Error::rpc(rpc.url.clone(), e))?,
+    };
 
-    let light_client = TmLightClient::from_config(config, peer_id)?;
+    let mut light_client = TmLightClient::from_config(config, &rpc.url, peer_id)?;
+
+    for address in &config.witness_addrs {
+        let witness_client =
+            HttpClient::new(address.clone()).map_err(|e| Error::rpc(address.clone(), e))?;
+
+        // An unreachable witness must not prevent the chain from starting
+        let witness_id: PeerId = match witness_client.status().await {
+            Ok(status) => status.node_info.id,
+            Err(e) => {
+                warn!(
+                    chain = %config.id, witness = %address,
+                    "skipping witness whose status could not be fetched: {}", e
+                );
+                continue;
+            }
+        };
+
+        light_client.add_witness(config, address, witness_id)?;
+    }
 
     Ok(light_client)
 }
@@ -1941,10 +2202,10 @@ fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
 ///    advertised by the node Hermes is connected to.
 fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     let chain_id = chain.id();
-    let grpc_address = chain.grpc_addr.to_string();
-    let rpc_address = chain.config.rpc_addr.to_string();
+    let grpc_address = chain.grpc_addr().to_string();
+    let rpc_address = chain.rpc_addr().to_string();
 
-    chain.block_on(chain.rpc_client.health()).map_err(|e| {
+    chain.block_on(chain.rpc_client().health()).map_err(|e| {
         Error::health_check_json_rpc(
             chain_id.clone(),
             rpc_address.clone(),
@@ -1995,7 +2256,8 @@ fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
         );
     }
 
-    let version_specs = chain.block_on(fetch_version_specs(&chain.config.id, &chain.grpc_addr))?;
+    let version_specs =
+        chain.block_on(fetch_version_specs(&chain.config.id, &chain.grpc_addr()))?;
 
     if let Err(diagnostic) = compatibility::run_diagnostic(&version_specs) {
         return Err(Error::sdk_module_version(
@@ -1,20 +1,23 @@
 use core::mem;
 
+use futures::future::join_all;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height;
 use prost::Message;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::chain::cosmos::encode::encoded_tx_metrics;
-use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::gas_table::message_gas_estimate;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::chain::cosmos::wait::wait_for_block_commits;
+use crate::chain::cosmos::wallet::{with_signer, TxSigner};
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
@@ -26,13 +29,16 @@ use crate::keyring::Secp256k1KeyPair;
    This may improve performance in case when multiple transactions are
    committed into the same block. However this approach may not work if
    priority mempool is enabled.
+
+   The batches are spread across the given signers, which submit them concurrently,
+   each with its own account sequence. The events are returned in the order of the
+   messages.
 */
 pub async fn send_batched_messages_and_wait_commit(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut [TxSigner<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -40,22 +46,16 @@ pub async fn send_batched_messages_and_wait_commit(
         return Ok(Vec::new());
     }
 
-    let mut tx_sync_results = send_messages_as_batches(
-        config,
-        max_msg_num,
-        max_tx_size,
-        key_pair,
-        account,
-        tx_memo,
-        messages,
-    )
-    .await?;
+    let mut tx_sync_results =
+        send_messages_as_batches(config, max_msg_num, max_tx_size, signers, tx_memo, messages)
+            .await?;
 
     wait_for_block_commits(
         &config.chain_id,
         &config.rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
+        &config.gas_config,
         &mut tx_sync_results,
     )
     .await?;
@@ -72,13 +72,14 @@ pub async fn send_batched_messages_and_wait_commit(
    Send batched messages one after another, only after the previous one
    has been committed. This is only used in case if parallel transactions
    are committed in the wrong order due to interference from priority mempool.
+
+   The batches still take turns between the given signers.
 */
 pub async fn sequential_send_batched_messages_and_wait_commit(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut [TxSigner<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -90,8 +91,7 @@ pub async fn sequential_send_batched_messages_and_wait_commit(
         config,
         max_msg_num,
         max_tx_size,
-        key_pair,
-        account,
+        signers,
         tx_memo,
         messages,
     )
@@ -109,8 +109,7 @@ pub async fn send_batched_messages_and_wait_check_tx(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut [TxSigner<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<Response>, Error> {
@@ -122,20 +121,17 @@ pub async fn send_batched_messages_and_wait_check_tx(
         config,
         max_msg_num,
         max_tx_size,
-        key_pair,
-        account,
+        &signers[0].key_pair,
+        signers[0].account,
         tx_memo,
         messages,
     )?;
 
-    let mut responses = Vec::new();
-
-    for batch in batches {
-        let response =
-            send_tx_with_account_sequence_retry(config, key_pair, account, tx_memo, &batch).await?;
-
-        responses.push(response);
-    }
+    let responses = send_batches_concurrently(config, signers, tx_memo, batches)
+        .await?
+        .into_iter()
+        .map(|(_, response)| response)
+        .collect();
 
     Ok(responses)
 }
@@ -144,8 +140,7 @@ async fn send_messages_as_batches(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut [TxSigner<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<TxSyncResult>, Error> {
@@ -159,31 +154,27 @@ async fn send_messages_as_batches(
         config,
         max_msg_num,
         max_tx_size,
-        key_pair,
-        account,
+        &signers[0].key_pair,
+        signers[0].account,
         tx_memo,
         messages,
     )?;
 
     debug!(
-        "sending {} messages as {} batches to chain {} in parallel",
+        "sending {} messages as {} batches to chain {} in parallel with {} keys",
         message_count,
         batches.len(),
-        config.chain_id
+        config.chain_id,
+        signers.len().min(batches.len()),
     );
 
-    let mut tx_sync_results = Vec::new();
-
-    for batch in batches {
-        let message_count = batch.len();
-
-        let response =
-            send_tx_with_account_sequence_retry(config, key_pair, account, tx_memo, &batch).await?;
-
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
-
-        tx_sync_results.push(tx_sync_result);
-    }
+    let tx_sync_results = send_batches_concurrently(config, signers, tx_memo, batches)
+        .await?
+        .into_iter()
+        .map(|(message_count, response)| {
+            response_to_tx_sync_result(&config.chain_id, message_count, response)
+        })
+        .collect();
 
     Ok(tx_sync_results)
 }
@@ -192,8 +183,7 @@ async fn sequential_send_messages_as_batches(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut [TxSigner<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<TxSyncResult>, Error> {
@@ -207,8 +197,8 @@ async fn sequential_send_messages_as_batches(
         config,
         max_msg_num,
         max_tx_size,
-        key_pair,
-        account,
+        &signers[0].key_pair,
+        signers[0].account,
         tx_memo,
         messages,
     )?;
@@ -220,29 +210,220 @@ async fn sequential_send_messages_as_batches(
         config.chain_id
     );
 
+    let signer_batches = assign_batches(batches, &resign_as(signers))?;
     let mut tx_sync_results = Vec::new();
 
-    for batch in batches {
-        let message_count = batch.len();
+    for (signer_index, _, batch) in signer_batches {
+        let signer = &mut signers[signer_index];
+        let mut pending = vec![batch];
 
-        let response =
-            send_tx_with_account_sequence_retry(config, key_pair, account, tx_memo, &batch).await?;
+        while let Some((message_count, response)) = send_next_batch(
+            config,
+            &signer.key_pair,
+            signer.account,
+            tx_memo,
+            &mut pending,
+        )
+        .await?
+        {
+            let tx_sync_result =
+                response_to_tx_sync_result(&config.chain_id, message_count, response);
+
+            tx_sync_results.push(tx_sync_result);
+
+            wait_for_block_commits(
+                &config.chain_id,
+                &config.rpc_client,
+                &config.rpc_address,
+                &config.rpc_timeout,
+                &config.gas_config,
+                &mut tx_sync_results,
+            )
+            .await?;
+        }
+    }
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+    Ok(tx_sync_results)
+}
 
-        tx_sync_results.push(tx_sync_result);
+/// The signers the messages are re-signed with for each of the given signers.
+fn resign_as(signers: &[TxSigner<'_>]) -> Vec<Option<Signer>> {
+    signers
+        .iter()
+        .map(|signer| signer.resign_as.clone())
+        .collect()
+}
 
-        wait_for_block_commits(
-            &config.chain_id,
-            &config.rpc_client,
-            &config.rpc_address,
-            &config.rpc_timeout,
-            &mut tx_sync_results,
+/**
+   Assign the given batches to the signers in turn, re-signing the messages of
+   each batch for its signer if needed, see [`TxSigner::resign_as`].
+
+   Returns the index of the signer and of the batch along with the batch,
+   in the order of the batches.
+*/
+fn assign_batches(
+    batches: Vec<Vec<Any>>,
+    resign_as: &[Option<Signer>],
+) -> Result<Vec<(usize, usize, Vec<Any>)>, Error> {
+    batches
+        .into_iter()
+        .enumerate()
+        .map(|(batch_index, batch)| {
+            let signer_index = batch_index % resign_as.len();
+
+            let batch = match &resign_as[signer_index] {
+                Some(signer) => with_signer(batch, signer)?,
+                None => batch,
+            };
+
+            Ok((signer_index, batch_index, batch))
+        })
+        .collect()
+}
+
+/**
+   Send the given batches concurrently, each signer sending the batches assigned to it
+   one after another with its own account sequence.
+
+   Returns the responses along with the number of messages in their transaction,
+   in the order of the messages. A batch which exceeds the limits of the chain
+   is split into several transactions, see [`send_next_batch`].
+*/
+async fn send_batches_concurrently(
+    config: &TxConfig,
+    signers: &mut [TxSigner<'_>],
+    tx_memo: &Memo,
+    batches: Vec<Vec<Any>>,
+) -> Result<Vec<(usize, Response)>, Error> {
+    let mut signer_batches = vec![Vec::new(); signers.len()];
+
+    for (signer_index, batch_index, batch) in assign_batches(batches, &resign_as(signers))? {
+        signer_batches[signer_index].push((batch_index, batch));
+    }
+
+    let tasks = signers
+        .iter_mut()
+        .zip(signer_batches)
+        .filter(|(_, batches)| !batches.is_empty())
+        .map(|(signer, batches)| send_assigned_batches(config, signer, tx_memo, batches));
+
+    // Wait for every signer to be done, so that the account sequences
+    // are up to date even if some of the transactions failed
+    let responses = join_all(tasks)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(merge_in_order(responses))
+}
+
+/// Send the given batches one after another with the given signer, and return the responses
+/// along with the index of their batch and the number of messages in their transaction.
+async fn send_assigned_batches(
+    config: &TxConfig,
+    signer: &mut TxSigner<'_>,
+    tx_memo: &Memo,
+    batches: Vec<(usize, Vec<Any>)>,
+) -> Result<Vec<(usize, usize, Response)>, Error> {
+    let mut responses = Vec::new();
+
+    for (batch_index, batch) in batches {
+        let mut pending = vec![batch];
+
+        while let Some((message_count, response)) = send_next_batch(
+            config,
+            &signer.key_pair,
+            signer.account,
+            tx_memo,
+            &mut pending,
         )
-        .await?;
+        .await?
+        {
+            responses.push((batch_index, message_count, response));
+        }
     }
 
-    Ok(tx_sync_results)
+    Ok(responses)
+}
+
+/// Merge the responses of the signers in the order of their batches, keeping the order
+/// of the transactions a batch was split into, and thus in the order of the messages.
+fn merge_in_order<T>(responses: Vec<Vec<(usize, usize, T)>>) -> Vec<(usize, T)> {
+    let mut responses: Vec<_> = responses.into_iter().flatten().collect();
+
+    // The sort is stable, so the transactions of a batch stay in order
+    responses.sort_by_key(|(batch_index, _, _)| *batch_index);
+
+    responses
+        .into_iter()
+        .map(|(_, message_count, response)| (message_count, response))
+        .collect()
+}
+
+/**
+   Send the next of the pending batches of messages as a single transaction,
+   and return the response along with the number of messages in the transaction,
+   or `None` if there are no more batches to send.
+
+   If the transaction exceeds the limits of the chain, ie. its estimated gas
+   is higher than `max_gas` or the node rejects it as too large, the batch is
+   bisected and its first half is sent instead, with the second half left pending.
+   A transaction with a single message which exceeds the limits is sent
+   as usual, so that the error is reported to the caller.
+*/
+async fn send_next_batch(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    tx_memo: &Memo,
+    pending: &mut Vec<Vec<Any>>,
+) -> Result<Option<(usize, Response)>, Error> {
+    while let Some(mut batch) = pending.pop() {
+        let result =
+            send_tx_with_account_sequence_retry(config, key_pair, account, tx_memo, &batch).await;
+
+        if batch.len() > 1 && exceeds_tx_limits(&result) {
+            let second_half = batch.split_off(batch.len() / 2);
+
+            warn!(
+                chain = %config.chain_id,
+                "transaction with {} messages exceeds the limits of the chain, \
+                splitting it into transactions with {} and {} messages",
+                batch.len() + second_half.len(),
+                batch.len(),
+                second_half.len(),
+            );
+
+            pending.push(second_half);
+            pending.push(batch);
+
+            continue;
+        }
+
+        return result.map(|response| Some((batch.len(), response)));
+    }
+
+    Ok(None)
+}
+
+/// Whether the result of sending a transaction shows that it exceeds the limits of the chain,
+/// either because its estimated gas is higher than `max_gas`, or because the node rejected it
+/// as too large, ie. larger than the `max_tx_bytes` of its mempool or consensus parameters.
+fn exceeds_tx_limits(result: &Result<Response, Error>) -> bool {
+    use crate::error::ErrorDetail::*;
+
+    fn is_tx_too_large(message: &str) -> bool {
+        message.to_lowercase().contains("tx too large")
+    }
+
+    match result {
+        Ok(response) => response.code.is_err() && is_tx_too_large(&response.log),
+        Err(e) => match e.detail() {
+            TxSimulateGasEstimateExceeded(_) => true,
+            Rpc(detail) => is_tx_too_large(&detail.source.to_string()),
+            _ => false,
+        },
+    }
 }
 
 fn response_to_tx_sync_result(
@@ -285,14 +466,15 @@ fn batch_messages(
 ) -> Result<Vec<Vec<Any>>, Error> {
     let max_message_count = max_msg_num.to_usize();
     let max_tx_size = max_tx_size.into();
+    let max_gas = config.gas_config.max_gas;
 
     let mut batches = vec![];
 
     // Estimate the overhead of the transaction envelope's encoding,
     // by taking the encoded length of an empty tx with the same auth info and signatures.
     // Use the maximum possible fee to get an upper bound for varint encoding.
-    let max_fee = gas_amount_to_fee(&config.gas_config, config.gas_config.max_gas);
-    let tx_metrics = encoded_tx_metrics(config, key_pair, account, tx_memo, &[], &max_fee)?;
+    let max_fee = &config.gas_config.max_fee;
+    let tx_metrics = encoded_tx_metrics(config, key_pair, account, tx_memo, &[], max_fee)?;
     let tx_envelope_len = tx_metrics.envelope_len;
     let empty_body_len = tx_metrics.body_bytes_len;
 
@@ -307,6 +489,7 @@ fn batch_messages(
 
     let mut current_count = 0;
     let mut current_len = empty_body_len;
+    let mut current_gas = 0;
     let mut current_batch = vec![];
 
     for message in messages {
@@ -316,8 +499,13 @@ fn batch_messages(
         // field tag (small varint) and the length delimiter.
         let tagged_len = 1 + prost::length_delimiter_len(message_len) + message_len;
 
+        // The gas needed by the message, if it can be known without simulating it.
+        // Otherwise, batches exceeding `max_gas` are bisected once simulated.
+        let message_gas = message_gas_estimate(&config.gas_config, &message).unwrap_or(0);
+
         if current_count >= max_message_count
             || tx_len(tx_envelope_len, current_len + tagged_len) > max_tx_size
+            || (current_count > 0 && current_gas + message_gas > max_gas)
         {
             let insert_batch = mem::take(&mut current_batch);
 
@@ -329,10 +517,12 @@ fn batch_messages(
             batches.push(insert_batch);
             current_count = 0;
             current_len = empty_body_len;
+            current_gas = 0;
         }
 
         current_count += 1;
         current_len += tagged_len;
+        current_gas += message_gas;
         current_batch.push(message);
     }
 
@@ -345,7 +535,7 @@ fn batch_messages(
 
 #[cfg(test)]
 mod tests {
-    use super::batch_messages;
+    use super::{assign_batches, batch_messages, exceeds_tx_limits, merge_in_order};
     use crate::chain::cosmos::encode::sign_and_encode_tx;
     use crate::chain::cosmos::gas::gas_amount_to_fee;
     use crate::chain::cosmos::types::account::{
@@ -354,10 +544,19 @@ mod tests {
     use crate::chain::cosmos::types::config::TxConfig;
     use crate::config;
     use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+    use crate::error::Error;
     use crate::keyring::{self, KeyRing, Secp256k1KeyPair, SigningKeyPair};
+    use core::str::FromStr;
     use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::MsgRecvPacket;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::signer::Signer;
+    use prost::Message;
     use std::fs;
+    use tendermint::abci::Code;
+    use tendermint::Hash;
+    use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
     const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
 
@@ -620,6 +819,134 @@ mod tests {
         assert_eq!(batches[0].len(), 5);
     }
 
+    #[test]
+    fn test_batches_are_structured_appropriately_per_max_gas() {
+        let (mut config, key_pair, account) = test_fixture();
+        assert_eq!(config.gas_config.max_gas, 200000);
+
+        config.gas_config.gas_estimation.static_gas =
+            [("Foo".to_string(), 120000), ("Bar".to_string(), 50000)]
+                .into_iter()
+                .collect();
+
+        // Ensure that messages are batched so that the sum of their static gas does not
+        // exceed `max_gas`, and that messages without static gas do not count towards it.
+        let messages = ["Foo", "Bar", "Foo", "Bar", "Baz"]
+            .into_iter()
+            .map(|msg_type| Any {
+                type_url: format!("/example.{msg_type}"),
+                value: vec![0; 4],
+            })
+            .collect();
+
+        let batches = batch_messages(
+            &config,
+            MaxMsgNum::default(),
+            MaxTxSize::default(),
+            &key_pair,
+            &account,
+            &Memo::new("").unwrap(),
+            messages,
+        )
+        .unwrap();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[1].len(), 3);
+    }
+
+    #[test]
+    fn test_tx_exceeding_chain_limits() {
+        let response = |code: u32, log: &str| Response {
+            code: Code::from(code),
+            data: Default::default(),
+            log: log.to_string(),
+            hash: Hash::None,
+        };
+
+        assert!(exceeds_tx_limits(&Err(
+            Error::tx_simulate_gas_estimate_exceeded(
+                ChainId::from_string("chain_A"),
+                300000,
+                200000
+            )
+        )));
+        assert!(exceeds_tx_limits(&Ok(response(
+            21,
+            "tx too large; got: 2000000, max: 1048576: tx too large"
+        ))));
+
+        assert!(!exceeds_tx_limits(&Ok(response(0, ""))));
+        assert!(!exceeds_tx_limits(&Ok(response(
+            32,
+            "account sequence mismatch"
+        ))));
+        assert!(!exceeds_tx_limits(&Err(Error::message_too_big_for_tx(100))));
+    }
+
+    #[test]
+    fn batches_are_spread_across_signers() {
+        let recv_packet = |sequence: u64| Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: MsgRecvPacket {
+                signer: "cosmos1main".to_string(),
+                proof_commitment: sequence.to_be_bytes().to_vec(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+
+        let signer_of = |message: &Any| {
+            MsgRecvPacket::decode(message.value.as_slice())
+                .unwrap()
+                .signer
+        };
+
+        let batches = vec![
+            vec![recv_packet(1), recv_packet(2)],
+            vec![recv_packet(3)],
+            vec![recv_packet(4)],
+        ];
+        let resign_as = [None, Some(Signer::from_str("cosmos1extra").unwrap())];
+
+        let assigned = assign_batches(batches, &resign_as).unwrap();
+
+        let signers: Vec<_> = assigned
+            .iter()
+            .map(|(signer_index, batch_index, _)| (*signer_index, *batch_index))
+            .collect();
+        assert_eq!(signers, vec![(0, 0), (1, 1), (0, 2)]);
+
+        // The first two batches go out under two different signers
+        assert!(assigned[0]
+            .2
+            .iter()
+            .all(|msg| signer_of(msg) == "cosmos1main"));
+        assert_eq!(signer_of(&assigned[1].2[0]), "cosmos1extra");
+        assert_eq!(signer_of(&assigned[2].2[0]), "cosmos1main");
+
+        // Re-signing keeps the rest of the messages intact
+        let proof = MsgRecvPacket::decode(assigned[1].2[0].value.as_slice())
+            .unwrap()
+            .proof_commitment;
+        assert_eq!(proof, 3u64.to_be_bytes().to_vec());
+    }
+
+    #[test]
+    fn responses_are_merged_in_message_order() {
+        // The first signer sent batches 0 and 2, the latter split into two transactions,
+        // and the second signer sent batch 1
+        let responses = vec![
+            vec![(0, 2, "tx-0"), (2, 1, "tx-2a"), (2, 1, "tx-2b")],
+            vec![(1, 3, "tx-1")],
+        ];
+
+        assert_eq!(
+            merge_in_order(responses),
+            vec![(2, "tx-0"), (3, "tx-1"), (1, "tx-2a"), (1, "tx-2b")]
+        );
+    }
+
     #[test]
     #[should_panic(expected = "`max_msg_num` must be greater than or equal to 1, found 0")]
     fn test_max_msg_num_of_zero_panics() {
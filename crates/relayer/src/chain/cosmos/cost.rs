@@ -0,0 +1,240 @@
+//! Attribution of the cost of the transactions submitted by the relayer,
+//! i.e. the fees they pay and the gas they use, to the type of their
+//! messages and to the channel on which they relay packets.
+
+use ibc_proto::cosmos::tx::v1beta1::{Fee, Tx};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose,
+};
+use ibc_relayer_types::core::ics02_client::msgs::update_client;
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::core::ics24_host::identifier::PortChannelId;
+use itertools::Itertools;
+use prost::Message;
+
+/// The labels under which the cost of a transaction is recorded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxCostLabels {
+    /// The types of the messages in the transaction, without their
+    /// package, e.g. `MsgRecvPacket`. Client updates are left out,
+    /// unless the transaction only includes client updates.
+    pub msg_type: String,
+
+    /// The channel end, on the chain the transaction is submitted to,
+    /// of the first packet relayed by the transaction, if any.
+    pub path: Option<PortChannelId>,
+}
+
+impl TxCostLabels {
+    pub fn from_messages(messages: &[Any]) -> Self {
+        let non_update_messages = messages
+            .iter()
+            .filter(|msg| msg.type_url != update_client::TYPE_URL)
+            .collect::<Vec<_>>();
+
+        let msg_types = if non_update_messages.is_empty() {
+            messages.iter().collect()
+        } else {
+            non_update_messages
+        };
+
+        let msg_type = msg_types
+            .iter()
+            .map(|msg| msg.type_url.rsplit('.').next().unwrap_or_default())
+            .unique()
+            .sorted()
+            .join(",");
+
+        let path = messages.iter().find_map(local_channel_end);
+
+        Self { msg_type, path }
+    }
+}
+
+/// The messages of the committed transaction with the given bytes,
+/// or `None` if they cannot be decoded.
+pub fn messages_from_tx_bytes(tx_bytes: &[u8]) -> Option<Vec<Any>> {
+    let tx = Tx::decode(tx_bytes).ok()?;
+
+    Some(tx.body?.messages)
+}
+
+/// The amounts of the given fee, by denom.
+///
+/// The amounts of coins are integers, and those which are not,
+/// or which do not fit in 64 bits, are skipped.
+pub fn fee_amounts(fee: &Fee) -> impl Iterator<Item = (&str, u64)> {
+    fee.amount.iter().filter_map(|coin| {
+        let amount = coin.amount.parse().ok()?;
+        Some((coin.denom.as_str(), amount))
+    })
+}
+
+/// The channel end, on the chain the message is submitted to,
+/// of the packet relayed by the given message, if any.
+fn local_channel_end(msg: &Any) -> Option<PortChannelId> {
+    let (port_id, channel_id) = match msg.type_url.as_str() {
+        recv_packet::TYPE_URL => {
+            let packet = MsgRecvPacket::decode(msg.value.as_slice()).ok()?.packet?;
+            (packet.destination_port, packet.destination_channel)
+        }
+        acknowledgement::TYPE_URL => {
+            let packet = MsgAcknowledgement::decode(msg.value.as_slice())
+                .ok()?
+                .packet?;
+            (packet.source_port, packet.source_channel)
+        }
+        timeout::TYPE_URL => {
+            let packet = MsgTimeout::decode(msg.value.as_slice()).ok()?.packet?;
+            (packet.source_port, packet.source_channel)
+        }
+        timeout_on_close::TYPE_URL => {
+            let packet = MsgTimeoutOnClose::decode(msg.value.as_slice())
+                .ok()?
+                .packet?;
+            (packet.source_port, packet.source_channel)
+        }
+        _ => return None,
+    };
+
+    Some(PortChannelId::new(
+        channel_id.parse().ok()?,
+        port_id.parse().ok()?,
+    ))
+}
+
+#[cfg(feature = "telemetry")]
+mod record {
+    use ibc_proto::cosmos::tx::v1beta1::Fee;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use super::{fee_amounts, TxCostLabels};
+
+    /// Record the fees paid by a transaction with the given messages,
+    /// once it has been accepted in the mempool of the given chain.
+    pub fn record_fees_paid(chain_id: &ChainId, messages: &[Any], fee: &Fee) {
+        let labels = TxCostLabels::from_messages(messages);
+
+        for (denom, amount) in fee_amounts(fee) {
+            ibc_telemetry::global().tx_fees_paid(
+                chain_id,
+                &labels.msg_type,
+                labels.path.as_ref(),
+                denom,
+                amount,
+            );
+        }
+    }
+
+    /// Record the gas wanted and the gas used by a transaction
+    /// with the given messages, committed by the given chain.
+    pub fn record_gas(chain_id: &ChainId, messages: &[Any], gas_wanted: u64, gas_used: u64) {
+        let labels = TxCostLabels::from_messages(messages);
+
+        ibc_telemetry::global().tx_gas(
+            chain_id,
+            &labels.msg_type,
+            labels.path.as_ref(),
+            gas_wanted,
+            gas_used,
+        );
+    }
+}
+
+#[cfg(feature = "telemetry")]
+pub use record::{record_fees_paid, record_gas};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::ibc::core::channel::v1::Packet;
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+
+    fn update_client() -> Any {
+        Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: MsgUpdateClient::default().encode_to_vec(),
+        }
+    }
+
+    fn recv_packet() -> Any {
+        let msg = MsgRecvPacket {
+            packet: Some(Packet {
+                source_port: "transfer".to_string(),
+                source_channel: "channel-0".to_string(),
+                destination_port: "transfer".to_string(),
+                destination_channel: "channel-7".to_string(),
+                ..Packet::default()
+            }),
+            ..MsgRecvPacket::default()
+        };
+
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn labels_of_packet_messages() {
+        let labels = TxCostLabels::from_messages(&[update_client(), recv_packet(), recv_packet()]);
+
+        assert_eq!(
+            labels,
+            TxCostLabels {
+                msg_type: "MsgRecvPacket".to_string(),
+                path: Some(PortChannelId::new(ChannelId::new(7), PortId::transfer())),
+            }
+        );
+    }
+
+    #[test]
+    fn labels_of_client_updates() {
+        let labels = TxCostLabels::from_messages(&[update_client()]);
+
+        assert_eq!(
+            labels,
+            TxCostLabels {
+                msg_type: "MsgUpdateClient".to_string(),
+                path: None,
+            }
+        );
+    }
+
+    #[test]
+    fn amounts_of_fee() {
+        let fee = Fee {
+            amount: vec![
+                Coin {
+                    denom: "stake".to_string(),
+                    amount: "18446744073709551615".to_string(),
+                },
+                Coin {
+                    denom: "uatom".to_string(),
+                    amount: "2.5".to_string(),
+                },
+                Coin {
+                    denom: "uosmo".to_string(),
+                    amount: "18446744073709551616".to_string(),
+                },
+                Coin {
+                    denom: "ujuno".to_string(),
+                    amount: "1500".to_string(),
+                },
+            ],
+            ..Fee::default()
+        };
+
+        assert_eq!(
+            fee_amounts(&fee).collect::<Vec<_>>(),
+            vec![("stake", u64::MAX), ("ujuno", 1500)]
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! Health tracking of, and failover between, the RPC and gRPC endpoints of a chain.
+//!
+//! The health of every endpoint listed in the chain configuration is checked
+//! periodically in the background. The endpoint in use is switched to the healthy
+//! endpoint with the highest block height when it is unhealthy, or when it lags
+//! more than [`MAX_HEIGHT_LAG`] blocks behind that endpoint. A request failing to
+//! reach the endpoint in use also triggers a failover, without waiting for the
+//! next health check.
+
+use alloc::sync::Arc;
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+use core::time::Duration;
+
+use futures::future::join_all;
+use http::Uri;
+use ibc_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient, GetLatestBlockRequest,
+};
+use tendermint_light_client_verifier::types::PeerId;
+use tendermint_rpc::error::ErrorDetail as RpcErrorDetail;
+use tendermint_rpc::{Client, HttpClient, Url};
+use tokio::runtime::Runtime as TokioRuntime;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::ChainConfig;
+use crate::error::{Error, ErrorDetail};
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+
+/// Interval between two health checks of the endpoints of a chain.
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of blocks the endpoint in use may lag behind the healthy endpoint with
+/// the highest block height before failing over to the latter. This prevents
+/// switching back and forth between endpoints which are equally up to date,
+/// but were not checked at exactly the same time.
+pub const MAX_HEIGHT_LAG: u64 = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EndpointKind {
+    Rpc,
+    Grpc,
+    WebSocket,
+}
+
+impl EndpointKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rpc => "rpc",
+            Self::Grpc => "grpc",
+            Self::WebSocket => "websocket",
+        }
+    }
+}
+
+impl Display for EndpointKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The outcome of the last health check of an endpoint.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EndpointStatus {
+    /// The endpoint has not been checked yet
+    #[default]
+    Unknown,
+    /// The endpoint is reachable and its node is caught up at the given height
+    Healthy { height: u64 },
+    /// The endpoint is unreachable, or its node is catching up
+    Unhealthy,
+}
+
+impl EndpointStatus {
+    pub fn height(&self) -> Option<u64> {
+        match self {
+            Self::Healthy { height } => Some(*height),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Endpoint<T> {
+    pub address: String,
+    pub client: T,
+    pub status: EndpointStatus,
+}
+
+/// The endpoints of a given kind for a chain, along with the one in use.
+#[derive(Clone, Debug)]
+pub struct EndpointPool<T> {
+    kind: EndpointKind,
+    endpoints: Vec<Endpoint<T>>,
+    active: usize,
+}
+
+impl<T> EndpointPool<T> {
+    /// Create a pool with the given endpoints, using the first one until
+    /// the health checks find a better one.
+    pub fn new(kind: EndpointKind, endpoints: Vec<(String, T)>) -> Self {
+        assert!(!endpoints.is_empty(), "endpoint pool cannot be empty");
+
+        Self {
+            kind,
+            endpoints: endpoints
+                .into_iter()
+                .map(|(address, client)| Endpoint {
+                    address,
+                    client,
+                    status: EndpointStatus::Unknown,
+                })
+                .collect(),
+            active: 0,
+        }
+    }
+
+    pub fn kind(&self) -> EndpointKind {
+        self.kind
+    }
+
+    pub fn endpoints(&self) -> &[Endpoint<T>] {
+        &self.endpoints
+    }
+
+    pub fn active(&self) -> &Endpoint<T> {
+        &self.endpoints[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Use the endpoint at the given index, regardless of its status.
+    pub fn set_active(&mut self, index: usize) {
+        assert!(index < self.endpoints.len(), "endpoint index out of bounds");
+        self.active = index;
+    }
+
+    pub fn set_status(&mut self, index: usize, status: EndpointStatus) {
+        self.endpoints[index].status = status;
+    }
+
+    /// Switch to the healthy endpoint with the highest block height, preferring the
+    /// endpoints listed first, if the endpoint in use is not healthy or lags more than
+    /// [`MAX_HEIGHT_LAG`] blocks behind it.
+    ///
+    /// Returns the index of the previously active endpoint if a switch occurred.
+    pub fn select(&mut self) -> Option<usize> {
+        let (best, best_height) = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, endpoint)| endpoint.status.height().map(|h| (index, h)))
+            .rev()
+            .max_by_key(|(_, height)| *height)?;
+
+        let keep_active = match self.active().status.height() {
+            Some(height) => height + MAX_HEIGHT_LAG >= best_height,
+            None => false,
+        };
+
+        if keep_active || best == self.active {
+            return None;
+        }
+
+        let previous = self.active;
+        self.active = best;
+
+        Some(previous)
+    }
+}
+
+/// An RPC endpoint, along with the identifier of its node once known.
+#[derive(Clone, Debug)]
+pub struct RpcEndpoint {
+    pub url: Url,
+    pub client: HttpClient,
+    pub peer_id: Option<PeerId>,
+}
+
+/// The RPC and gRPC endpoints of a chain.
+pub struct Endpoints {
+    chain_id: ChainId,
+    rpc_timeout: Duration,
+    rpc: RwArc<EndpointPool<RpcEndpoint>>,
+    grpc: RwArc<EndpointPool<Uri>>,
+}
+
+impl Endpoints {
+    pub fn from_config(config: &ChainConfig) -> Result<Self, Error> {
+        let rpc = core::iter::once(&config.rpc_addr)
+            .chain(&config.fallback_rpc_addrs)
+            .map(|url| {
+                let client =
+                    HttpClient::new(url.clone()).map_err(|e| Error::rpc(url.clone(), e))?;
+
+                let endpoint = RpcEndpoint {
+                    url: url.clone(),
+                    client,
+                    peer_id: None,
+                };
+
+                Ok((url.to_string(), endpoint))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let grpc = core::iter::once(&config.grpc_addr)
+            .chain(&config.fallback_grpc_addrs)
+            .map(|url| {
+                let uri = Uri::from_str(&url.to_string())
+                    .map_err(|e| Error::invalid_uri(url.to_string(), e))?;
+
+                Ok((url.to_string(), uri))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            chain_id: config.id.clone(),
+            rpc_timeout: config.rpc_timeout,
+            rpc: RwArc::new_lock(EndpointPool::new(EndpointKind::Rpc, rpc)),
+            grpc: RwArc::new_lock(EndpointPool::new(EndpointKind::Grpc, grpc)),
+        })
+    }
+
+    /// Whether there is any endpoint to fail over to.
+    pub fn has_fallbacks(&self) -> bool {
+        self.rpc.acquire_read().endpoints().len() > 1
+            || self.grpc.acquire_read().endpoints().len() > 1
+    }
+
+    /// The RPC endpoint in use.
+    pub fn rpc(&self) -> RpcEndpoint {
+        self.rpc.acquire_read().active().client.clone()
+    }
+
+    /// The gRPC endpoint in use.
+    pub fn grpc_addr(&self) -> Uri {
+        self.grpc.acquire_read().active().client.clone()
+    }
+
+    /// Check the health of all the endpoints, and fail over to another
+    /// endpoint of the same kind if the one in use is not the best anymore.
+    pub async fn check_health(&self) {
+        let rpc_endpoints = self.rpc.acquire_read().endpoints().to_vec();
+        let grpc_endpoints = self.grpc.acquire_read().endpoints().to_vec();
+
+        let (rpc_results, grpc_results) = futures::join!(
+            join_all(
+                rpc_endpoints
+                    .iter()
+                    .map(|endpoint| check_rpc(&endpoint.client.client, self.rpc_timeout))
+            ),
+            join_all(
+                grpc_endpoints
+                    .iter()
+                    .map(|endpoint| check_grpc(&endpoint.client, self.rpc_timeout))
+            ),
+        );
+
+        {
+            let mut pool = self.rpc.acquire_write();
+
+            for (index, (status, peer_id)) in rpc_results.into_iter().enumerate() {
+                if peer_id.is_some() {
+                    pool.endpoints[index].client.peer_id = peer_id;
+                }
+
+                pool.set_status(index, status);
+            }
+
+            self.record_and_select(&mut pool);
+        }
+
+        {
+            let mut pool = self.grpc.acquire_write();
+
+            for (index, status) in grpc_results.into_iter().enumerate() {
+                pool.set_status(index, status);
+            }
+
+            self.record_and_select(&mut pool);
+        }
+    }
+
+    /// Fail over to the healthy endpoint of the given kind with the highest block height,
+    /// if any, after a request to the endpoint in use failed to reach it.
+    pub fn report_failure(&self, kind: EndpointKind) {
+        match kind {
+            EndpointKind::Rpc => self.fail_over(&mut self.rpc.acquire_write()),
+            EndpointKind::Grpc => self.fail_over(&mut self.grpc.acquire_write()),
+            EndpointKind::WebSocket => {}
+        }
+    }
+
+    fn fail_over<T>(&self, pool: &mut EndpointPool<T>) {
+        let active = pool.active_index();
+        pool.set_status(active, EndpointStatus::Unhealthy);
+
+        self.record_and_select(pool);
+    }
+
+    /// Periodically check the health of the endpoints on the given runtime,
+    /// until the returned task is aborted.
+    pub fn spawn_health_checks(self: &Arc<Self>, rt: &TokioRuntime) -> JoinHandle<()> {
+        let endpoints = self.clone();
+
+        rt.spawn(async move {
+            loop {
+                endpoints.check_health().await;
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+        })
+    }
+
+    fn record_and_select<T>(&self, pool: &mut EndpointPool<T>) {
+        let kind = pool.kind();
+
+        for endpoint in pool.endpoints() {
+            debug!(
+                chain = %self.chain_id, %kind, address = %endpoint.address,
+                "endpoint status: {:?}", endpoint.status
+            );
+
+            telemetry!(
+                endpoint_height,
+                &self.chain_id,
+                kind.as_str(),
+                &endpoint.address,
+                endpoint.status.height().unwrap_or(0)
+            );
+        }
+
+        if let Some(previous) = pool.select() {
+            let from = &pool.endpoints()[previous];
+            let to = pool.active();
+
+            warn!(
+                chain = %self.chain_id, %kind,
+                "failing over from {} ({:?}) to {} ({:?})",
+                from.address, from.status, to.address, to.status
+            );
+
+            telemetry!(
+                endpoint_failover,
+                &self.chain_id,
+                kind.as_str(),
+                &from.address,
+                &to.address
+            );
+        }
+    }
+}
+
+/// The kind of the endpoint which could not be reached if the given error is due to it,
+/// rather than to the request itself.
+pub fn unreachable_endpoint(e: &Error) -> Option<EndpointKind> {
+    match e.detail() {
+        ErrorDetail::Rpc(e) => matches!(
+            e.source,
+            RpcErrorDetail::Io(_)
+                | RpcErrorDetail::Http(_)
+                | RpcErrorDetail::Hyper(_)
+                | RpcErrorDetail::Timeout(_)
+        )
+        .then_some(EndpointKind::Rpc),
+        ErrorDetail::GrpcTransport(_) => Some(EndpointKind::Grpc),
+        ErrorDetail::GrpcStatus(e) => matches!(
+            e.status.code(),
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+        )
+        .then_some(EndpointKind::Grpc),
+        _ => None,
+    }
+}
+
+pub(crate) async fn check_rpc(
+    client: &HttpClient,
+    timeout: Duration,
+) -> (EndpointStatus, Option<PeerId>) {
+    match tokio::time::timeout(timeout, client.status()).await {
+        Ok(Ok(status)) if !status.sync_info.catching_up => (
+            EndpointStatus::Healthy {
+                height: status.sync_info.latest_block_height.value(),
+            },
+            Some(status.node_info.id),
+        ),
+        Ok(Ok(status)) => (EndpointStatus::Unhealthy, Some(status.node_info.id)),
+        _ => (EndpointStatus::Unhealthy, None),
+    }
+}
+
+async fn check_grpc(uri: &Uri, timeout: Duration) -> EndpointStatus {
+    let query = async {
+        let mut client = ServiceClient::connect(uri.clone()).await.ok()?;
+
+        let response = client
+            .get_latest_block(GetLatestBlockRequest {})
+            .await
+            .ok()?
+            .into_inner();
+
+        let height = response.block?.header?.height;
+
+        u64::try_from(height).ok()
+    };
+
+    match tokio::time::timeout(timeout, query).await {
+        Ok(Some(height)) => EndpointStatus::Healthy { height },
+        _ => EndpointStatus::Unhealthy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndpointKind, EndpointPool, EndpointStatus};
+
+    fn pool(statuses: &[EndpointStatus]) -> EndpointPool<()> {
+        let mut pool = EndpointPool::new(
+            EndpointKind::Rpc,
+            (0..statuses.len())
+                .map(|i| (format!("http://node-{i}"), ()))
+                .collect(),
+        );
+
+        for (index, status) in statuses.iter().enumerate() {
+            pool.set_status(index, *status);
+        }
+
+        pool
+    }
+
+    #[test]
+    fn keeps_healthy_active_endpoint_within_lag() {
+        let mut pool = pool(&[
+            EndpointStatus::Healthy { height: 98 },
+            EndpointStatus::Healthy { height: 100 },
+        ]);
+
+        assert_eq!(pool.select(), None);
+        assert_eq!(pool.active().address, "http://node-0");
+    }
+
+    #[test]
+    fn fails_over_to_highest_endpoint() {
+        let mut pool = pool(&[
+            EndpointStatus::Healthy { height: 90 },
+            EndpointStatus::Healthy { height: 100 },
+            EndpointStatus::Healthy { height: 120 },
+        ]);
+
+        assert_eq!(pool.select(), Some(0));
+        assert_eq!(pool.active().address, "http://node-2");
+    }
+
+    #[test]
+    fn fails_over_from_unhealthy_endpoint() {
+        let mut pool = pool(&[
+            EndpointStatus::Unhealthy,
+            EndpointStatus::Healthy { height: 100 },
+            EndpointStatus::Healthy { height: 100 },
+        ]);
+
+        assert_eq!(pool.select(), Some(0));
+        assert_eq!(pool.active().address, "http://node-1");
+    }
+
+    #[test]
+    fn keeps_active_endpoint_when_none_is_healthy() {
+        let mut pool = pool(&[EndpointStatus::Unhealthy, EndpointStatus::Unknown]);
+
+        assert_eq!(pool.select(), None);
+        assert_eq!(pool.active().address, "http://node-0");
+    }
+}
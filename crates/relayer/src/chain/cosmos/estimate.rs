@@ -5,11 +5,13 @@ use tonic::codegen::http::Uri;
 use tracing::{debug, error, span, warn, Level};
 
 use crate::chain::cosmos::encode::sign_tx;
-use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::gas::{dynamic_gas_price, gas_amount_to_fee};
+use crate::chain::cosmos::gas_table::static_gas_estimate;
 use crate::chain::cosmos::simulate::send_tx_simulate;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::GasConfig;
+use crate::config::gas_estimation::GasEstimationMode;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::keyring::Secp256k1KeyPair;
@@ -44,8 +46,21 @@ pub async fn estimate_tx_fees(
         signatures: signed_tx.signatures,
     };
 
-    let estimated_fee =
-        estimate_fee_with_tx(gas_config, &config.grpc_address, &config.chain_id, tx).await?;
+    let gas_price = dynamic_gas_price(gas_config, &config.chain_id, &config.grpc_address).await;
+
+    let gas_config = GasConfig {
+        gas_price,
+        ..gas_config.clone()
+    };
+
+    let estimated_fee = estimate_fee_with_tx(
+        &gas_config,
+        &config.grpc_address,
+        &config.chain_id,
+        tx,
+        messages,
+    )
+    .await?;
 
     Ok(estimated_fee)
 }
@@ -55,8 +70,9 @@ async fn estimate_fee_with_tx(
     grpc_address: &Uri,
     chain_id: &ChainId,
     tx: Tx,
+    messages: &[Any],
 ) -> Result<Fee, Error> {
-    let estimated_gas = estimate_gas_with_tx(gas_config, grpc_address, tx).await?;
+    let estimated_gas = estimate_gas_with_tx(gas_config, grpc_address, tx, messages).await?;
 
     if estimated_gas > gas_config.max_gas {
         debug!(
@@ -83,19 +99,42 @@ async fn estimate_fee_with_tx(
     Ok(adjusted_fee)
 }
 
-/// Try to simulate the given tx in order to estimate how much gas will be needed to submit it.
+/// Estimate how much gas will be needed to submit the given tx, according to the gas estimation mode of the chain.
+///
+/// Unless the static gas table is used, or the gas of the messages of the tx was learned,
+/// try to simulate the given tx.
 ///
 /// It is possible that a batch of messages are fragmented by the caller (`send_msgs`) such that
 /// they do not individually verify. For example for the following batch:
 /// [`MsgUpdateClient`, `MsgRecvPacket`, ..., `MsgRecvPacket`]
 ///
 /// If the batch is split in two TX-es, the second one will fail the simulation in `deliverTx` check.
-/// In this case we use the `default_gas` param.
+/// In this case we use the static gas table, or the `default_gas` param.
 async fn estimate_gas_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
     tx: Tx,
+    messages: &[Any],
 ) -> Result<u64, Error> {
+    let mode = gas_config.gas_estimation.mode;
+
+    match mode {
+        GasEstimationMode::Static => {
+            let gas = static_gas_estimate(gas_config, messages);
+            debug!("using static gas amount: {gas}");
+
+            return Ok(gas);
+        }
+        GasEstimationMode::Learned => {
+            if let Some(gas) = gas_config.learned_gas.estimate(messages) {
+                debug!("using learned gas amount: {gas}");
+
+                return Ok(gas);
+            }
+        }
+        GasEstimationMode::Simulate => {}
+    }
+
     let simulated_gas = send_tx_simulate(grpc_address, tx)
         .await
         .map(|sr| sr.gas_info);
@@ -113,24 +152,43 @@ async fn estimate_gas_with_tx(
         }
 
         Ok(None) => {
+            let gas = static_gas_estimate(gas_config, messages);
+
             warn!(
-                "tx simulation successful but no gas amount used was returned, falling back on default gas: {}",
-                gas_config.default_gas
+                "tx simulation successful but no gas amount used was returned, falling back on static gas: {}",
+                gas
             );
 
-            Ok(gas_config.default_gas)
+            Ok(gas)
         }
 
         // If there is a chance that the tx will be accepted once actually submitted, we fall
-        // back on the default gas and will attempt to send it anyway.
+        // back on the static gas and will attempt to send it anyway.
         // See `can_recover_from_simulation_failure` for more info.
         Err(e) if can_recover_from_simulation_failure(&e) => {
+            let gas = static_gas_estimate(gas_config, messages);
+
             warn!(
-                "failed to simulate tx, falling back on default gas because the error is potentially recoverable: {}",
+                "failed to simulate tx, falling back on static gas {} because the error is potentially recoverable: {}",
+                gas,
                 e.detail()
             );
 
-            Ok(gas_config.default_gas)
+            Ok(gas)
+        }
+
+        // Chains learning the gas of their txs do not rely on simulation,
+        // which may be unavailable on them.
+        Err(e) if mode == GasEstimationMode::Learned => {
+            let gas = static_gas_estimate(gas_config, messages);
+
+            warn!(
+                "failed to simulate tx, falling back on static gas {} until the gas of its messages is learned: {}",
+                gas,
+                e.detail()
+            );
+
+            Ok(gas)
         }
 
         Err(e) => {
@@ -158,3 +216,70 @@ fn can_recover_from_simulation_failure(e: &Error) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+
+    use crate::config::gas_estimation::GasEstimation;
+
+    fn gas_config(mode: GasEstimationMode) -> GasConfig {
+        GasConfig {
+            gas_estimation: GasEstimation {
+                mode,
+                static_gas: [("MsgUpdateClient".to_string(), 300_000)].into(),
+            },
+            ..GasConfig::for_tests()
+        }
+    }
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    #[test]
+    fn estimate_gas_according_to_mode() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // No node listens on this address, so that simulating the tx fails
+        let grpc_address: Uri = "http://127.0.0.1:1".parse().unwrap();
+
+        let messages = [msg(update_client::TYPE_URL), msg(recv_packet::TYPE_URL)];
+
+        let estimate = |gas_config: &GasConfig| {
+            runtime.block_on(estimate_gas_with_tx(
+                gas_config,
+                &grpc_address,
+                Tx::default(),
+                &messages,
+            ))
+        };
+
+        // The static gas table is used without simulating the tx
+        let config = gas_config(GasEstimationMode::Static);
+        assert_eq!(estimate(&config).unwrap(), 400_000);
+
+        // The learned gas is used without simulating the tx once learned
+        let config = gas_config(GasEstimationMode::Learned);
+        config.learned_gas.learn(&config, &messages, 250_000);
+        assert_eq!(
+            estimate(&config).unwrap(),
+            config.learned_gas.estimate(&messages).unwrap()
+        );
+
+        // Until then, the static gas table is used when the simulation fails
+        let config = gas_config(GasEstimationMode::Learned);
+        assert_eq!(estimate(&config).unwrap(), 400_000);
+
+        // The tx is always simulated otherwise
+        let config = gas_config(GasEstimationMode::Simulate);
+        config.learned_gas.learn(&config, &messages, 250_000);
+        assert!(estimate(&config).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Evidence of misbehaviour committed in the blocks of a chain.
+//!
+//! The RPC types of `tendermint-rs` do not expose the details of light client attack
+//! evidence, so the evidence of a block is fetched in its raw JSON form and parsed here.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tendermint::block::signed_header::SignedHeader;
+use tendermint::block::Height as TmHeight;
+use tendermint::validator::Set as ValidatorSet;
+use tendermint_rpc::{Client, HttpClient, Url};
+
+use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc_relayer_types::Height;
+
+use crate::error::Error;
+
+const DUPLICATE_VOTE_TYPE: &str = "tendermint/DuplicateVoteEvidence";
+const LIGHT_CLIENT_ATTACK_TYPE: &str = "tendermint/LightClientAttackEvidence";
+
+/// A piece of evidence committed in a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockEvidence {
+    /// A validator signed two conflicting votes. The chain punishes the validator when
+    /// committing the evidence, so there is nothing left to submit.
+    DuplicateVote,
+
+    /// A set of validators signed a block conflicting with the chain, in order to fool the
+    /// light clients of the chain, such as the IBC clients hosted by its counterparties.
+    LightClientAttack(Box<LightClientAttack>),
+
+    /// Evidence of an unknown type.
+    Unknown(String),
+}
+
+/// Evidence of a light client attack, ie. of a block conflicting with the chain,
+/// which can be verified from the block at the common height.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientAttack {
+    #[serde(alias = "ConflictingBlock")]
+    pub conflicting_block: ConflictingBlock,
+    #[serde(alias = "CommonHeight")]
+    pub common_height: TmHeight,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictingBlock {
+    pub signed_header: SignedHeader,
+    pub validator_set: ValidatorSet,
+}
+
+impl LightClientAttack {
+    /// The height of the conflicting block.
+    pub fn conflicting_height(&self) -> TmHeight {
+        self.conflicting_block.signed_header.header.height
+    }
+
+    /// The header of the conflicting block, as it would have been submitted to an IBC client
+    /// which trusts the given height, whose next validator set is the given one.
+    pub fn conflicting_header(
+        &self,
+        trusted_height: Height,
+        trusted_validator_set: ValidatorSet,
+    ) -> TmHeader {
+        TmHeader {
+            signed_header: self.conflicting_block.signed_header.clone(),
+            validator_set: self.conflicting_block.validator_set.clone(),
+            trusted_height,
+            trusted_validator_set,
+        }
+    }
+}
+
+/// Parse a piece of evidence, in the amino JSON form used by the RPC.
+pub fn parse_evidence(evidence: &Value) -> Result<BlockEvidence, Error> {
+    let evidence_type = evidence["type"].as_str().unwrap_or_default();
+
+    match evidence_type {
+        DUPLICATE_VOTE_TYPE => Ok(BlockEvidence::DuplicateVote),
+        LIGHT_CLIENT_ATTACK_TYPE => {
+            let attack = LightClientAttack::deserialize(&evidence["value"])
+                .map_err(|e| Error::invalid_evidence(format!("light client attack: {e}")))?;
+
+            Ok(BlockEvidence::LightClientAttack(Box::new(attack)))
+        }
+        _ => Ok(BlockEvidence::Unknown(evidence_type.to_string())),
+    }
+}
+
+/// Fetch the evidence committed in the block at the given height.
+pub async fn fetch_block_evidence(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    height: TmHeight,
+) -> Result<Vec<BlockEvidence>, Error> {
+    let response = rpc_client
+        .perform(BlockEvidenceRequest { height })
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    response
+        .block
+        .evidence
+        .evidence
+        .unwrap_or_default()
+        .iter()
+        .map(parse_evidence)
+        .collect()
+}
+
+/// A `/block` request whose response only retains the raw evidence of the block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BlockEvidenceRequest {
+    height: TmHeight,
+}
+
+impl tendermint_rpc::Request for BlockEvidenceRequest {
+    type Response = BlockEvidenceResponse;
+
+    fn method(&self) -> tendermint_rpc::Method {
+        tendermint_rpc::Method::Block
+    }
+}
+
+impl tendermint_rpc::SimpleRequest for BlockEvidenceRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BlockEvidenceResponse {
+    block: RawBlock,
+}
+
+impl tendermint_rpc::Response for BlockEvidenceResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RawBlock {
+    evidence: RawEvidenceList,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RawEvidenceList {
+    evidence: Option<Vec<Value>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_evidence, BlockEvidence};
+
+    use serde_json::json;
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    #[test]
+    fn parse_light_client_attack() {
+        let block = TestgenLightBlock::new_default(10).generate().unwrap();
+
+        let evidence = json!({
+            "type": "tendermint/LightClientAttackEvidence",
+            "value": {
+                "ConflictingBlock": {
+                    "signed_header": block.signed_header,
+                    "validator_set": block.validators,
+                },
+                "CommonHeight": "7",
+                "ByzantineValidators": [],
+                "TotalVotingPower": "100",
+                "Timestamp": "2023-03-14T10:17:03.347Z",
+            }
+        });
+
+        match parse_evidence(&evidence).unwrap() {
+            BlockEvidence::LightClientAttack(attack) => {
+                assert_eq!(attack.common_height.value(), 7);
+                assert_eq!(attack.conflicting_height().value(), 10);
+                assert_eq!(attack.conflicting_block.signed_header, block.signed_header);
+                assert_eq!(
+                    attack.conflicting_block.validator_set.hash(),
+                    block.validators.hash()
+                );
+            }
+            evidence => panic!("unexpected evidence: {evidence:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_other_evidence() {
+        let duplicate_vote = json!({ "type": "tendermint/DuplicateVoteEvidence", "value": {} });
+        assert_eq!(
+            parse_evidence(&duplicate_vote).unwrap(),
+            BlockEvidence::DuplicateVote
+        );
+
+        let unknown = json!({ "type": "tendermint/SomeEvidence", "value": {} });
+        assert_eq!(
+            parse_evidence(&unknown).unwrap(),
+            BlockEvidence::Unknown("tendermint/SomeEvidence".to_string())
+        );
+
+        let invalid = json!({ "type": "tendermint/LightClientAttackEvidence", "value": {} });
+        assert!(parse_evidence(&invalid).is_err());
+    }
+}
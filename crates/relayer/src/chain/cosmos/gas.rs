@@ -1,8 +1,12 @@
 use core::cmp::min;
+use core::str::FromStr;
+use http::Uri;
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use num_bigint::BigInt;
 use num_rational::BigRational;
+use tracing::{debug, warn};
 
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::GasPrice;
@@ -25,6 +29,72 @@ pub fn gas_amount_to_fee(config: &GasConfig, gas_amount: u64) -> Fee {
     }
 }
 
+/// The gas price to use for the next transaction.
+///
+/// If dynamic gas pricing is enabled, the current gas price is queried from
+/// the fee market module of the chain, then adjusted with the configured
+/// multiplier and cap. Otherwise, or if the query fails, the static gas price
+/// from the configuration is used.
+pub async fn dynamic_gas_price(
+    config: &GasConfig,
+    chain_id: &ChainId,
+    grpc_address: &Uri,
+) -> GasPrice {
+    let static_price = &config.gas_price;
+    let dynamic = &config.dynamic_gas_price;
+
+    if !dynamic.enabled {
+        return static_price.clone();
+    }
+
+    let grpc_address = match &dynamic.grpc_addr {
+        Some(addr) => match Uri::from_str(&addr.to_string()) {
+            Ok(uri) => uri,
+            Err(e) => {
+                warn!(
+                    id = %chain_id,
+                    "invalid fee market gRPC address {}, falling back on static gas price {}: {}",
+                    addr, static_price, e
+                );
+
+                return static_price.clone();
+            }
+        },
+        None => grpc_address.clone(),
+    };
+
+    let price = match config
+        .fee_market
+        .query_gas_price(&grpc_address, &static_price.denom)
+        .await
+    {
+        Ok(base_price) => {
+            let price = GasPrice::new(dynamic.adjust(base_price), static_price.denom.clone());
+
+            debug!(
+                id = %chain_id,
+                "using dynamic gas price {} (base gas price: {})",
+                price, base_price
+            );
+
+            price
+        }
+        Err(e) => {
+            warn!(
+                id = %chain_id,
+                "failed to query gas price from the fee market module, falling back on static gas price {}: {}",
+                static_price, e
+            );
+
+            static_price.clone()
+        }
+    };
+
+    crate::telemetry!(dynamic_gas_price, chain_id, &price.denom, price.price);
+
+    price
+}
+
 pub fn calculate_fee(adjusted_gas_amount: u64, gas_price: &GasPrice) -> Coin {
     let fee_amount = mul_ceil(adjusted_gas_amount, gas_price.price);
 
@@ -98,7 +168,31 @@ fn adjust_estimated_gas(
 
 #[cfg(test)]
 mod tests {
-    use super::{adjust_estimated_gas, AdjustGas};
+    use super::{adjust_estimated_gas, dynamic_gas_price, AdjustGas};
+
+    use core::str::FromStr;
+    use http::Uri;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use crate::chain::cosmos::types::gas::GasConfig;
+    use crate::config::dynamic_gas::DynamicGasPrice;
+
+    #[test]
+    fn dynamic_gas_price_falls_back_on_static_price() {
+        let config = GasConfig {
+            dynamic_gas_price: DynamicGasPrice::enabled(1.1, 0.6).unwrap(),
+            ..GasConfig::for_tests()
+        };
+
+        // Nothing is listening on port 1, so the query fails
+        let grpc_address = Uri::from_str("http://127.0.0.1:1").unwrap();
+        let chain_id = ChainId::from_string("chain-0");
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let price = rt.block_on(dynamic_gas_price(&config, &chain_id, &grpc_address));
+
+        assert_eq!(price, config.gas_price);
+    }
 
     #[test]
     fn adjust_zero_gas() {
@@ -0,0 +1,249 @@
+//! Estimation of the gas needed by a transaction from the gas needed by each of
+//! its messages, either configured in a static table or learned from the
+//! transactions previously committed by the chain.
+
+use alloc::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use ibc_proto::cosmos::tx::v1beta1::Tx;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use tendermint_rpc::endpoint::tx::Response as TxResponse;
+use tracing::trace;
+
+use crate::chain::cosmos::types::gas::GasConfig;
+use crate::config::gas_estimation::GasEstimationMode;
+
+/// The type of the given message, without its package, eg. `MsgRecvPacket`.
+fn msg_type(msg: &Any) -> &str {
+    msg.type_url.rsplit('.').next().unwrap_or_default()
+}
+
+/// The sum of the gas of the given messages in the static gas table, counting `default_gas`
+/// for each message whose type is not in the table, or `default_gas` if there is no message.
+pub fn static_gas_estimate(gas_config: &GasConfig, messages: &[Any]) -> u64 {
+    if messages.is_empty() {
+        return gas_config.default_gas;
+    }
+
+    let static_gas = &gas_config.gas_estimation.static_gas;
+
+    messages
+        .iter()
+        .map(|msg| {
+            static_gas
+                .get(msg_type(msg))
+                .copied()
+                .unwrap_or(gas_config.default_gas)
+        })
+        .fold(0, u64::saturating_add)
+}
+
+/// The gas needed by the given message, if known without simulating it, ie. its learned gas
+/// if the chain learns the gas of its transactions, or its gas in the static gas table otherwise.
+pub fn message_gas_estimate(gas_config: &GasConfig, msg: &Any) -> Option<u64> {
+    let msg_type = msg_type(msg);
+
+    let learned = match gas_config.gas_estimation.mode {
+        GasEstimationMode::Learned => gas_config.learned_gas.get(msg_type),
+        _ => None,
+    };
+
+    learned.or_else(|| gas_config.gas_estimation.static_gas.get(msg_type).copied())
+}
+
+fn sum_gas(table: &BTreeMap<String, u64>, messages: &[Any]) -> Option<u64> {
+    if messages.is_empty() {
+        return None;
+    }
+
+    messages
+        .iter()
+        .map(|msg| table.get(msg_type(msg)).copied())
+        .sum()
+}
+
+/// The gas needed by each type of message, learned from the gas used by the transactions
+/// committed by a chain. It is shared by all the clones of the `GasConfig` of the chain.
+#[derive(Clone, Debug, Default)]
+pub struct LearnedGas(Arc<RwLock<BTreeMap<String, u64>>>);
+
+impl LearnedGas {
+    /// Weight of the gas used by the latest transaction in the learned amounts,
+    /// which are exponential moving averages.
+    const SMOOTHING: f64 = 0.2;
+
+    /// The sum of the learned gas of the given messages, if the gas of every message type was learned.
+    pub fn estimate(&self, messages: &[Any]) -> Option<u64> {
+        sum_gas(&self.0.read().unwrap(), messages)
+    }
+
+    fn get(&self, msg_type: &str) -> Option<u64> {
+        self.0.read().unwrap().get(msg_type).copied()
+    }
+
+    /// Learn from the gas used by a transaction with the given messages.
+    ///
+    /// The gas used is split among the messages in proportion to their current estimate,
+    /// ie. their learned gas if any, or their static gas if any, or `default_gas` otherwise.
+    pub fn learn(&self, gas_config: &GasConfig, messages: &[Any], gas_used: u64) {
+        if messages.is_empty() || gas_used == 0 {
+            return;
+        }
+
+        let mut learned = self.0.write().unwrap();
+
+        let prior = |msg_type: &str| {
+            learned
+                .get(msg_type)
+                .or_else(|| gas_config.gas_estimation.static_gas.get(msg_type))
+                .copied()
+                .unwrap_or(gas_config.default_gas)
+                .max(1) as f64
+        };
+
+        let total_prior: f64 = messages.iter().map(|msg| prior(msg_type(msg))).sum();
+
+        // The gas used by the messages of each type, and the number of such messages
+        let mut observed: HashMap<&str, (f64, u64)> = HashMap::new();
+
+        for msg in messages {
+            let msg_type = msg_type(msg);
+            let share = gas_used as f64 * prior(msg_type) / total_prior;
+
+            let entry = observed.entry(msg_type).or_default();
+            entry.0 += share;
+            entry.1 += 1;
+        }
+
+        for (msg_type, (gas, count)) in observed {
+            let observed_gas = gas / count as f64;
+
+            let updated = match learned.get(msg_type) {
+                Some(&current) => {
+                    (1.0 - Self::SMOOTHING) * current as f64 + Self::SMOOTHING * observed_gas
+                }
+                None => observed_gas,
+            };
+
+            trace!(%msg_type, gas = updated, "learned gas");
+
+            learned.insert(msg_type.to_string(), updated.ceil() as u64);
+        }
+    }
+}
+
+/// Learn from the gas used by a transaction submitted by the relayer and committed by the chain,
+/// if the chain is configured to learn the gas of its transactions and the transaction succeeded.
+pub fn learn_from_tx(gas_config: &GasConfig, response: &TxResponse) {
+    if gas_config.gas_estimation.mode != GasEstimationMode::Learned
+        || response.tx_result.code.is_err()
+    {
+        return;
+    }
+
+    let messages = match Tx::decode(response.tx.as_slice())
+        .ok()
+        .and_then(|tx| tx.body)
+    {
+        Some(body) => body.messages,
+        None => return,
+    };
+
+    let gas_used = response.tx_result.gas_used.try_into().unwrap_or_default();
+
+    gas_config
+        .learned_gas
+        .learn(gas_config, &messages, gas_used);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use ibc_relayer_types::core::ics04_channel::msgs::{acknowledgement, recv_packet};
+
+    use crate::config::gas_estimation::GasEstimation;
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    fn gas_config(static_gas: &[(&str, u64)]) -> GasConfig {
+        GasConfig {
+            gas_estimation: GasEstimation {
+                mode: GasEstimationMode::Learned,
+                static_gas: static_gas
+                    .iter()
+                    .map(|(msg_type, gas)| (msg_type.to_string(), *gas))
+                    .collect(),
+            },
+            ..GasConfig::for_tests()
+        }
+    }
+
+    #[test]
+    fn static_gas_of_messages() {
+        let config = gas_config(&[("MsgUpdateClient", 300_000), ("MsgRecvPacket", 150_000)]);
+
+        let messages = [
+            msg(update_client::TYPE_URL),
+            msg(recv_packet::TYPE_URL),
+            msg(recv_packet::TYPE_URL),
+        ];
+        assert_eq!(static_gas_estimate(&config, &messages), 600_000);
+
+        // Acknowledgements are not in the table, and count for `default_gas`
+        let messages = [
+            msg(update_client::TYPE_URL),
+            msg(acknowledgement::TYPE_URL),
+            msg(acknowledgement::TYPE_URL),
+        ];
+        assert_eq!(static_gas_estimate(&config, &messages), 500_000);
+
+        assert_eq!(static_gas_estimate(&config, &[]), 100_000);
+    }
+
+    #[test]
+    fn learn_gas_of_messages() {
+        let config = gas_config(&[("MsgUpdateClient", 300_000), ("MsgRecvPacket", 100_000)]);
+        let learned = &config.learned_gas;
+
+        let messages = [
+            msg(update_client::TYPE_URL),
+            msg(recv_packet::TYPE_URL),
+            msg(recv_packet::TYPE_URL),
+        ];
+        assert_eq!(learned.estimate(&messages), None);
+
+        // The gas used is split in proportion to the static gas: 3/5 and 2 x 1/5
+        learned.learn(&config, &messages, 250_000);
+        assert_eq!(
+            learned.estimate(&[msg(update_client::TYPE_URL)]),
+            Some(150_000)
+        );
+        assert_eq!(
+            learned.estimate(&[msg(recv_packet::TYPE_URL)]),
+            Some(50_000)
+        );
+        assert_eq!(learned.estimate(&messages), Some(250_000));
+
+        // Further transactions move the learned gas towards the gas they used
+        learned.learn(&config, &[msg(recv_packet::TYPE_URL)], 100_000);
+        assert_eq!(
+            learned.estimate(&[msg(recv_packet::TYPE_URL)]),
+            Some(60_000)
+        );
+        assert_eq!(
+            learned.estimate(&[msg(update_client::TYPE_URL)]),
+            Some(150_000)
+        );
+
+        assert_eq!(learned.estimate(&[msg(acknowledgement::TYPE_URL)]), None);
+    }
+}
@@ -20,6 +20,8 @@ pub mod consensus_state;
 pub mod custom;
 pub mod denom_trace;
 pub mod fee;
+pub mod feemarket;
+pub mod ica;
 pub mod status;
 pub mod tx;
 
@@ -8,6 +8,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::signer::Signer;
 use tonic::Code;
 
+use crate::chain::requests::PageRequest;
 use crate::error::Error;
 
 pub async fn query_counterparty_payee(
@@ -54,7 +55,7 @@ pub async fn query_incentivized_packets(
     let request = QueryIncentivizedPacketsForChannelRequest {
         channel_id: channel_id.to_string(),
         port_id: port_id.to_string(),
-        pagination: None,
+        pagination: Some(PageRequest::all().into()),
         query_height: 0,
     };
 
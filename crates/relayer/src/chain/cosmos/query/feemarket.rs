@@ -0,0 +1,152 @@
+//! Query the current gas price from a fee market module, such as the
+//! EIP-1559-style `x/feemarket` module, over gRPC.
+//!
+//! The protobuf definitions of the module are not part of `ibc-proto`,
+//! so the few messages needed are declared here.
+
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+use http::uri::{PathAndQuery, Uri};
+use ibc_proto::cosmos::base::v1beta1::DecCoin;
+use tonic::codec::ProstCodec;
+use tonic::transport::{Channel, Endpoint};
+
+use crate::error::Error;
+
+const GAS_PRICE_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+
+/// Precision of the `sdk.Dec` type, ie. its number of decimals.
+const DEC_PRECISION: usize = 18;
+
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub price: Option<DecCoin>,
+}
+
+/// A client for the fee market module of a chain.
+///
+/// The gRPC channel to the module is opened lazily, reconnects on demand, and is
+/// shared by all the clones of the `GasConfig` of the chain, instead of connecting
+/// anew for every transaction.
+#[derive(Clone, Debug, Default)]
+pub struct FeeMarketClient(Arc<Mutex<Option<(Uri, Channel)>>>);
+
+impl FeeMarketClient {
+    /// Query the current gas price for the given denomination.
+    pub async fn query_gas_price(&self, grpc_address: &Uri, denom: &str) -> Result<f64, Error> {
+        let mut client = tonic::client::Grpc::new(self.channel(grpc_address));
+
+        client.ready().await.map_err(Error::grpc_transport)?;
+
+        let request = tonic::Request::new(GasPriceRequest {
+            denom: denom.to_string(),
+        });
+
+        let response: tonic::Response<GasPriceResponse> = client
+            .unary(
+                request,
+                PathAndQuery::from_static(GAS_PRICE_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(Error::grpc_status)?;
+
+        let price = response
+            .into_inner()
+            .price
+            .ok_or_else(|| Error::grpc_response_param("price".to_string()))?;
+
+        parse_dec(&price.amount).ok_or_else(|| Error::invalid_fee_market_gas_price(price.amount))
+    }
+
+    /// The channel to the given address, reusing the current one if it has the same address.
+    fn channel(&self, grpc_address: &Uri) -> Channel {
+        let mut current = self.0.lock().unwrap();
+
+        match current.as_ref() {
+            Some((address, channel)) if address == grpc_address => channel.clone(),
+            _ => {
+                let channel = Endpoint::from(grpc_address.clone()).connect_lazy();
+                *current = Some((grpc_address.clone(), channel.clone()));
+                channel
+            }
+        }
+    }
+}
+
+/// The encodings of an `sdk.Dec` value found in the responses of the fee market module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DecFormat {
+    /// The protobuf encoding, an integer scaled by `10^18`, eg. `2500000000000000`
+    Proto,
+    /// The string representation, with exactly 18 decimals, eg. `0.002500000000000000`
+    Text,
+}
+
+impl DecFormat {
+    /// The encoding of the given amount, if it is a valid `sdk.Dec` in either encoding.
+    /// Plain decimals, such as `0.0025` or `-1.0`, are not.
+    fn of(amount: &str) -> Option<Self> {
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+        match amount.split_once('.') {
+            None if is_digits(amount) => Some(Self::Proto),
+            Some((int, frac))
+                if is_digits(int) && is_digits(frac) && frac.len() == DEC_PRECISION =>
+            {
+                Some(Self::Text)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse an `sdk.Dec` value, in either of its encodings.
+fn parse_dec(amount: &str) -> Option<f64> {
+    let decimal = match DecFormat::of(amount)? {
+        DecFormat::Proto => {
+            let padded = format!("{:0>width$}", amount, width = DEC_PRECISION + 1);
+            let (int, frac) = padded.split_at(padded.len() - DEC_PRECISION);
+            format!("{int}.{frac}")
+        }
+        DecFormat::Text => amount.to_string(),
+    };
+
+    decimal
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_dec, DecFormat};
+
+    #[test]
+    fn parse_dec_amounts() {
+        assert_eq!(DecFormat::of("2500000000000000"), Some(DecFormat::Proto));
+        assert_eq!(DecFormat::of("0.002500000000000000"), Some(DecFormat::Text));
+        assert_eq!(DecFormat::of("0.0025"), None);
+
+        assert_eq!(parse_dec("2500000000000000"), Some(0.0025));
+        assert_eq!(parse_dec("1000000000000000000"), Some(1.0));
+        assert_eq!(parse_dec("1"), Some(1e-18));
+        assert_eq!(parse_dec("0.002500000000000000"), Some(0.0025));
+        assert_eq!(parse_dec("1.000000000000000000"), Some(1.0));
+
+        // Plain decimals are ambiguous, and rejected
+        assert_eq!(parse_dec("0.0025"), None);
+        assert_eq!(parse_dec("-1.0"), None);
+        assert_eq!(parse_dec("1."), None);
+        assert_eq!(parse_dec(""), None);
+        assert_eq!(parse_dec("stake"), None);
+    }
+}
@@ -0,0 +1,37 @@
+use http::uri::Uri;
+
+use ibc_proto::ibc::applications::interchain_accounts::controller::v1::{
+    query_client::QueryClient, QueryInterchainAccountRequest,
+};
+use ibc_relayer_types::core::ics24_host::identifier::ConnectionId;
+use ibc_relayer_types::signer::Signer;
+
+use crate::error::Error;
+
+/// Uses the GRPC client to retrieve the address of the interchain account
+/// registered by `owner` on the host chain at the other end of the given connection.
+pub async fn query_interchain_account(
+    grpc_address: &Uri,
+    owner: &Signer,
+    connection_id: &ConnectionId,
+) -> Result<Signer, Error> {
+    let mut client = QueryClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QueryInterchainAccountRequest {
+        owner: owner.to_string(),
+        connection_id: connection_id.to_string(),
+    });
+
+    let response = client
+        .interchain_account(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(Error::grpc_status)?;
+
+    response
+        .address
+        .parse()
+        .map_err(|_| Error::empty_interchain_account(owner.to_string(), connection_id.clone()))
+}
@@ -8,8 +8,12 @@ use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 
+use crate::chain::cosmos::cost::messages_from_tx_bytes;
+use crate::chain::cosmos::gas_table::learn_from_tx;
 use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
 use crate::chain::cosmos::types::events;
+use crate::chain::cosmos::types::gas::GasConfig;
+use crate::chain::endpoint::TxOutcome;
 use crate::chain::requests::{
     QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
 };
@@ -87,6 +91,59 @@ pub async fn query_txs(
     }
 }
 
+/// Queries the outcome of the transactions submitted by the relayer, whose confirmation
+/// is being checked. Returns `None` unless all of them were committed.
+pub async fn query_tx_outcomes(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    gas_config: &GasConfig,
+    tx_hashes: &[TxHash],
+) -> Result<Option<Vec<TxOutcome>>, Error> {
+    crate::time!("query_tx_outcomes");
+    crate::telemetry!(query, chain_id, "query_tx_outcomes");
+
+    let mut responses = Vec::with_capacity(tx_hashes.len());
+
+    for tx_hash in tx_hashes {
+        match query_tx_response(rpc_client, rpc_address, tx_hash).await? {
+            Some(response) => responses.push(response),
+            None => return Ok(None),
+        }
+    }
+
+    // Only learn from the transactions once all of them are committed,
+    // as they are queried again until then.
+    for response in &responses {
+        learn_from_tx(gas_config, response);
+    }
+
+    let outcomes = responses
+        .into_iter()
+        .map(|response| tx_outcome_from_tx_response(chain_id, response))
+        .collect();
+
+    Ok(Some(outcomes))
+}
+
+fn tx_outcome_from_tx_response(chain_id: &ChainId, response: TxResponse) -> TxOutcome {
+    let height = ICSHeight::new(chain_id.version(), u64::from(response.height)).unwrap();
+
+    let messages = messages_from_tx_bytes(&response.tx).unwrap_or_default();
+
+    TxOutcome {
+        hash: response.hash,
+        height,
+        code: response.tx_result.code.value(),
+        codespace: response.tx_result.codespace.clone(),
+        log: response.tx_result.log.clone(),
+        gas_wanted: response.tx_result.gas_wanted.try_into().unwrap_or_default(),
+        gas_used: response.tx_result.gas_used.try_into().unwrap_or_default(),
+        messages,
+        events: all_ibc_events_from_tx_search_response(chain_id, response),
+    }
+}
+
 /// This function queries transactions for packet events matching certain criteria.
 /// It returns at most one packet event for each sequence specified in the request.
 ///    Note - there is no way to format the packet query such that it asks for Tx-es with either
@@ -365,3 +422,75 @@ pub fn all_ibc_events_from_tx_search_response(
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::tx::v1beta1::{Tx, TxBody};
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use tendermint::abci::response::DeliverTx;
+    use tendermint::abci::Code;
+
+    fn tx_response(code: u32, messages: Vec<Any>) -> TxResponse {
+        let tx = Tx {
+            body: Some(TxBody {
+                messages,
+                ..TxBody::default()
+            }),
+            ..Tx::default()
+        };
+
+        TxResponse {
+            hash: TxHash::Sha256([7; 32]),
+            height: 42u32.into(),
+            index: 0,
+            tx_result: DeliverTx {
+                code: Code::from(code),
+                log: "out of gas".to_string(),
+                gas_wanted: 200_000,
+                gas_used: 150_000,
+                codespace: "sdk".to_string(),
+                ..DeliverTx::default()
+            },
+            tx: tx.encode_to_vec(),
+            proof: None,
+        }
+    }
+
+    #[test]
+    fn outcome_of_failed_tx() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let message = Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: vec![1, 2, 3],
+        };
+
+        let outcome =
+            tx_outcome_from_tx_response(&chain_id, tx_response(11, vec![message.clone()]));
+
+        assert!(!outcome.is_ok());
+        assert_eq!(outcome.hash, TxHash::Sha256([7; 32]));
+        assert_eq!(outcome.height, Height::new(0, 42).unwrap());
+        assert_eq!(outcome.code, 11);
+        assert_eq!(outcome.codespace, "sdk");
+        assert_eq!(outcome.log, "out of gas");
+        assert_eq!(outcome.gas_wanted, 200_000);
+        assert_eq!(outcome.gas_used, 150_000);
+        assert_eq!(outcome.messages, vec![message]);
+        assert_eq!(outcome.events.len(), 1);
+        assert!(matches!(outcome.events[0].event, IbcEvent::ChainError(_)));
+    }
+
+    #[test]
+    fn outcome_of_successful_tx() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        let outcome = tx_outcome_from_tx_response(&chain_id, tx_response(0, vec![]));
+
+        assert!(outcome.is_ok());
+        assert!(outcome.messages.is_empty());
+        assert!(outcome.events.is_empty());
+    }
+}
@@ -15,8 +15,10 @@ use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::telemetry;
 
 use super::batch::send_batched_messages_and_wait_commit;
+use super::wallet::TxSigner;
 
 pub async fn estimate_fee_and_send_tx(
     config: &TxConfig,
@@ -27,7 +29,17 @@ pub async fn estimate_fee_and_send_tx(
 ) -> Result<Response, Error> {
     let fee = estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
 
-    send_tx_with_fee(config, key_pair, account, tx_memo, messages, &fee).await
+    let response = send_tx_with_fee(config, key_pair, account, tx_memo, messages, &fee).await?;
+
+    if response.code.is_ok() {
+        telemetry!(crate::chain::cosmos::cost::record_fees_paid(
+            &config.chain_id,
+            messages,
+            &fee
+        ));
+    }
+
+    Ok(response)
 }
 
 async fn send_tx_with_fee(
@@ -111,12 +123,17 @@ pub async fn batched_send_tx(
         .await?
         .into();
 
+    let mut signers = [TxSigner {
+        key_pair: key_pair.clone(),
+        account: &mut account,
+        resign_as: None,
+    }];
+
     let events = send_batched_messages_and_wait_commit(
         config,
         config.max_msg_num,
         config.max_tx_size,
-        key_pair,
-        &mut account,
+        &mut signers,
         &Memo::default(),
         messages,
     )
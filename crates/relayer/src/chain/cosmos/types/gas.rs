@@ -1,6 +1,10 @@
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::chain::cosmos::calculate_fee;
+use crate::chain::cosmos::gas_table::LearnedGas;
+use crate::chain::cosmos::query::feemarket::FeeMarketClient;
+use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::gas_estimation::GasEstimation;
 use crate::config::{ChainConfig, GasPrice};
 
 /// Default gas limit when submitting a transaction.
@@ -14,6 +18,10 @@ pub struct GasConfig {
     pub max_gas: u64,
     pub gas_multiplier: f64,
     pub gas_price: GasPrice,
+    pub dynamic_gas_price: DynamicGasPrice,
+    pub fee_market: FeeMarketClient,
+    pub gas_estimation: GasEstimation,
+    pub learned_gas: LearnedGas,
     pub max_fee: Fee,
     pub fee_granter: String,
 }
@@ -25,6 +33,10 @@ impl<'a> From<&'a ChainConfig> for GasConfig {
             max_gas: max_gas_from_config(config),
             gas_multiplier: gas_multiplier_from_config(config),
             gas_price: config.gas_price.clone(),
+            dynamic_gas_price: config.dynamic_gas_price.clone(),
+            fee_market: FeeMarketClient::default(),
+            gas_estimation: config.gas_estimation.clone(),
+            learned_gas: LearnedGas::default(),
             max_fee: max_fee_from_config(config),
             fee_granter: fee_granter_from_config(config),
         }
@@ -58,11 +70,23 @@ fn fee_granter_from_config(config: &ChainConfig) -> String {
         .to_string()
 }
 
+/// The highest gas price the relayer may pay for a transaction, ie. the cap
+/// of the dynamic gas price if it is enabled and above the static gas price.
+fn max_gas_price_from_config(config: &ChainConfig) -> GasPrice {
+    let dynamic = &config.dynamic_gas_price;
+
+    if dynamic.enabled && dynamic.max > config.gas_price.price {
+        GasPrice::new(dynamic.max, config.gas_price.denom.clone())
+    } else {
+        config.gas_price.clone()
+    }
+}
+
 fn max_fee_from_config(config: &ChainConfig) -> Fee {
     let max_gas = max_gas_from_config(config);
 
     // The maximum fee the relayer pays for a transaction
-    let max_fee_in_coins = calculate_fee(max_gas, &config.gas_price);
+    let max_fee_in_coins = calculate_fee(max_gas, &max_gas_price_from_config(config));
 
     let fee_granter = fee_granter_from_config(config);
 
@@ -73,3 +97,65 @@ fn max_fee_from_config(config: &ChainConfig) -> Fee {
         granter: fee_granter,
     }
 }
+
+#[cfg(test)]
+impl GasConfig {
+    /// A gas configuration with a static gas price and the default gas estimation, for tests.
+    pub(crate) fn for_tests() -> Self {
+        Self {
+            default_gas: 100_000,
+            max_gas: 1_000_000,
+            gas_multiplier: 1.1,
+            gas_price: GasPrice::new(0.025, "stake".to_string()),
+            dynamic_gas_price: DynamicGasPrice::disabled(),
+            fee_market: FeeMarketClient::default(),
+            gas_estimation: GasEstimation::default(),
+            learned_gas: LearnedGas::default(),
+            max_fee: Fee::default(),
+            fee_granter: DEFAULT_FEE_GRANTER.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GasConfig;
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use crate::chain::cosmos::calculate_fee;
+    use crate::config;
+    use crate::config::dynamic_gas::DynamicGasPrice;
+    use crate::config::GasPrice;
+
+    #[test]
+    fn max_fee_at_max_gas_price() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+        let mut chain_config = config
+            .find_chain(&ChainId::from_string("chain_A"))
+            .unwrap()
+            .clone();
+
+        let static_max_fee = calculate_fee(200_000, &chain_config.gas_price);
+        let dynamic_max_fee = calculate_fee(200_000, &GasPrice::new(0.01, "stake".to_string()));
+
+        // The max gas at the static gas price
+        let max_fee = GasConfig::from(&chain_config).max_fee;
+        assert_eq!(max_fee.gas_limit, 200_000);
+        assert_eq!(max_fee.amount, vec![static_max_fee.clone()]);
+
+        // The max gas at the cap of the dynamic gas price
+        chain_config.dynamic_gas_price = DynamicGasPrice::enabled(1.1, 0.01).unwrap();
+        let max_fee = GasConfig::from(&chain_config).max_fee;
+        assert_eq!(max_fee.amount, vec![dynamic_max_fee]);
+
+        // The static gas price is used as a fallback, so it bounds the max fee from below
+        chain_config.dynamic_gas_price = DynamicGasPrice::enabled(1.1, 0.0001).unwrap();
+        let max_fee = GasConfig::from(&chain_config).max_fee;
+        assert_eq!(max_fee.amount, vec![static_max_fee]);
+    }
+}
@@ -11,8 +11,10 @@ use tendermint_rpc::{HttpClient, Url};
 use tokio::time::sleep;
 use tracing::{debug, trace};
 
+use crate::chain::cosmos::gas_table::learn_from_tx;
 use crate::chain::cosmos::query::tx::query_tx_response;
 use crate::chain::cosmos::types::events::from_tx_response_event;
+use crate::chain::cosmos::types::gas::GasConfig;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
@@ -27,6 +29,7 @@ pub async fn wait_for_block_commits(
     rpc_client: &HttpClient,
     rpc_address: &Url,
     rpc_timeout: &Duration,
+    gas_config: &GasConfig,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
     if all_tx_results_found(tx_sync_results) {
@@ -65,8 +68,14 @@ pub async fn wait_for_block_commits(
 
             for tx_sync_result in tx_sync_results.iter_mut() {
                 // ignore error
-                let _ =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
+                let _ = update_tx_sync_result(
+                    chain_id,
+                    rpc_client,
+                    rpc_address,
+                    gas_config,
+                    tx_sync_result,
+                )
+                .await;
             }
         }
     }
@@ -76,6 +85,7 @@ async fn update_tx_sync_result(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    gas_config: &GasConfig,
     tx_sync_result: &mut TxSyncResult,
 ) -> Result<(), Error> {
     if let TxStatus::Pending { message_count } = tx_sync_result.status {
@@ -85,6 +95,15 @@ async fn update_tx_sync_result(
         if let Some(response) = response {
             tx_sync_result.status = TxStatus::ReceivedResponse;
 
+            crate::telemetry!(crate::chain::cosmos::cost::record_gas(
+                chain_id,
+                &crate::chain::cosmos::cost::messages_from_tx_bytes(&response.tx)
+                    .unwrap_or_default(),
+                response.tx_result.gas_wanted.try_into().unwrap_or_default(),
+                response.tx_result.gas_used.try_into().unwrap_or_default(),
+            ));
+            learn_from_tx(gas_config, &response);
+
             let height = Height::new(chain_id.version(), u64::from(response.height)).unwrap();
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
@@ -0,0 +1,274 @@
+//! Pool of wallets used to submit transactions to a chain.
+//!
+//! Each wallet signs its transactions with its own key and tracks its own account
+//! sequence. The messages are built for the main wallet, ie. the one of `key_name`,
+//! and are re-signed for another wallet by replacing their `signer` field. This is only
+//! supported for the messages which update clients and relay packets; the messages of
+//! any other type are always submitted by the main wallet.
+//!
+//! The batches of messages of a single request are spread across the wallets, which
+//! submit them concurrently, see [`crate::chain::cosmos::batch`]. A transaction which
+//! is rejected or evicted from the mempool then only invalidates the pending
+//! transactions of its own wallet.
+
+use http::Uri;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose,
+};
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+use ibc_relayer_types::core::ics02_client::msgs::update_client;
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::signer::Signer;
+use prost::Message;
+
+use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::types::account::Account;
+use crate::error::Error;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+/// A key used to submit transactions, along with the cached information of its account.
+#[derive(Clone, Debug)]
+pub struct Wallet {
+    pub key_name: String,
+    pub account: Option<Account>,
+}
+
+/// A wallet submitting the transactions of a request, along with its account.
+pub struct TxSigner<'a> {
+    pub key_pair: Secp256k1KeyPair,
+    pub account: &'a mut Account,
+    /// The signer the messages are re-signed with before being submitted by this wallet,
+    /// `None` if the messages were built for its key.
+    pub resign_as: Option<Signer>,
+}
+
+/// The wallets used to submit transactions to a chain, starting with the main one.
+#[derive(Clone, Debug)]
+pub struct WalletPool {
+    wallets: Vec<Wallet>,
+    next: usize,
+}
+
+impl WalletPool {
+    pub fn new(main_key_name: String, extra_key_names: Vec<String>) -> Self {
+        let wallets = core::iter::once(main_key_name)
+            .chain(extra_key_names)
+            .map(|key_name| Wallet {
+                key_name,
+                account: None,
+            })
+            .collect();
+
+        Self { wallets, next: 0 }
+    }
+
+    /// The number of wallets in the pool, including the main one.
+    pub fn count(&self) -> usize {
+        self.wallets.len()
+    }
+
+    pub fn get(&self, index: usize) -> &Wallet {
+        &self.wallets[index]
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> &mut Wallet {
+        &mut self.wallets[index]
+    }
+
+    /// Pick the wallets which submit the given messages, in the order in which they take
+    /// the batches of the messages. If all the messages can be re-signed, every wallet is
+    /// picked, starting with a different one for each request so that the wallets share
+    /// the requests made of a single batch. Otherwise only the main wallet is picked.
+    pub fn select(&mut self, messages: &[Any]) -> Vec<usize> {
+        if self.wallets.len() < 2 || !messages.iter().all(can_resign) {
+            return vec![0];
+        }
+
+        let count = self.wallets.len();
+        let first = self.next;
+        self.next = (self.next + 1) % count;
+
+        (0..count).map(|i| (first + i) % count).collect()
+    }
+
+    /// The signers of the given wallets, whose keys are given in the same order,
+    /// fetching the accounts of the wallets which are not cached yet.
+    pub async fn signers(
+        &mut self,
+        indices: &[usize],
+        key_pairs: Vec<Secp256k1KeyPair>,
+        grpc_address: &Uri,
+    ) -> Result<Vec<TxSigner<'_>>, Error> {
+        let mut wallets: Vec<Option<&mut Wallet>> = self.wallets.iter_mut().map(Some).collect();
+        let mut signers = Vec::with_capacity(indices.len());
+
+        for (&index, key_pair) in indices.iter().zip(key_pairs) {
+            let wallet = wallets[index]
+                .take()
+                .expect("the selected wallets are distinct");
+
+            let account =
+                get_or_fetch_account(grpc_address, &key_pair.account(), &mut wallet.account)
+                    .await?;
+
+            let resign_as = if index == 0 {
+                None
+            } else {
+                Some(key_pair_to_signer(&key_pair)?)
+            };
+
+            signers.push(TxSigner {
+                key_pair,
+                account,
+                resign_as,
+            });
+        }
+
+        Ok(signers)
+    }
+}
+
+/// Whether the signer of the given message can be replaced with [`with_signer`].
+pub fn can_resign(message: &Any) -> bool {
+    [
+        update_client::TYPE_URL,
+        recv_packet::TYPE_URL,
+        acknowledgement::TYPE_URL,
+        timeout::TYPE_URL,
+        timeout_on_close::TYPE_URL,
+    ]
+    .contains(&message.type_url.as_str())
+}
+
+/// Replace the signer of the given messages with the given one.
+///
+/// Fails if any message cannot be re-signed, see [`can_resign`].
+pub fn with_signer(messages: Vec<Any>, signer: &Signer) -> Result<Vec<Any>, Error> {
+    messages
+        .into_iter()
+        .map(|message| {
+            let value = match message.type_url.as_str() {
+                update_client::TYPE_URL => set_signer::<MsgUpdateClient>(&message, signer)?,
+                recv_packet::TYPE_URL => set_signer::<MsgRecvPacket>(&message, signer)?,
+                acknowledgement::TYPE_URL => set_signer::<MsgAcknowledgement>(&message, signer)?,
+                timeout::TYPE_URL => set_signer::<MsgTimeout>(&message, signer)?,
+                timeout_on_close::TYPE_URL => set_signer::<MsgTimeoutOnClose>(&message, signer)?,
+                other => {
+                    return Err(Error::protobuf_decode(
+                        other.to_string(),
+                        prost::DecodeError::new("message type cannot be re-signed"),
+                    ))
+                }
+            };
+
+            Ok(Any {
+                type_url: message.type_url,
+                value,
+            })
+        })
+        .collect()
+}
+
+trait HasSigner {
+    fn set_signer(&mut self, signer: String);
+}
+
+macro_rules! impl_has_signer {
+    ($($msg:ty),*) => {
+        $(
+            impl HasSigner for $msg {
+                fn set_signer(&mut self, signer: String) {
+                    self.signer = signer;
+                }
+            }
+        )*
+    };
+}
+
+impl_has_signer!(
+    MsgUpdateClient,
+    MsgRecvPacket,
+    MsgAcknowledgement,
+    MsgTimeout,
+    MsgTimeoutOnClose
+);
+
+fn set_signer<M>(message: &Any, signer: &Signer) -> Result<Vec<u8>, Error>
+where
+    M: Message + Default + HasSigner,
+{
+    let mut raw = M::decode(message.value.as_slice())
+        .map_err(|e| Error::protobuf_decode(message.type_url.clone(), e))?;
+
+    raw.set_signer(signer.to_string());
+
+    Ok(raw.encode_to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{with_signer, WalletPool};
+
+    use core::str::FromStr;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::MsgRecvPacket;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+    use ibc_relayer_types::signer::Signer;
+    use prost::Message;
+
+    fn recv_packet(signer: &str) -> Any {
+        let msg = MsgRecvPacket {
+            proof_commitment: vec![1, 2, 3],
+            signer: signer.to_string(),
+            ..Default::default()
+        };
+
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn other_msg() -> Any {
+        Any {
+            type_url: "/ibc.core.channel.v1.MsgChannelOpenInit".to_string(),
+            value: vec![],
+        }
+    }
+
+    #[test]
+    fn wallets_take_turns() {
+        let mut pool = WalletPool::new("main".to_string(), vec!["extra".to_string()]);
+        let msgs = vec![recv_packet("main")];
+
+        assert_eq!(pool.select(&msgs), vec![0, 1]);
+        assert_eq!(pool.select(&msgs), vec![1, 0]);
+        assert_eq!(pool.select(&msgs), vec![0, 1]);
+    }
+
+    #[test]
+    fn main_wallet_submits_other_messages() {
+        let mut pool = WalletPool::new("main".to_string(), vec!["extra".to_string()]);
+        let msgs = vec![recv_packet("main"), other_msg()];
+
+        assert_eq!(pool.select(&msgs), vec![0]);
+        assert_eq!(pool.select(&msgs), vec![0]);
+    }
+
+    #[test]
+    fn replaces_signer() {
+        let signer = Signer::from_str("cosmos1extra").unwrap();
+        let msgs = with_signer(vec![recv_packet("cosmos1main")], &signer).unwrap();
+
+        let msg = MsgRecvPacket::decode(msgs[0].value.as_slice()).unwrap();
+        assert_eq!(msg.signer, "cosmos1extra");
+        assert_eq!(msg.proof_commitment, vec![1, 2, 3]);
+
+        assert!(with_signer(vec![other_msg()], &signer).is_err());
+    }
+}
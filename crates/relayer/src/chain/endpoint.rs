@@ -3,6 +3,7 @@ use core::convert::TryFrom;
 
 use tokio::runtime::Runtime as TokioRuntime;
 
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics02_client::consensus_state::ConsensusState;
@@ -14,6 +15,7 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, Version};
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes,
 };
@@ -26,6 +28,8 @@ use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::Height as ICSHeight;
 
+use ibc_proto::google::protobuf::Any;
+use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
 use crate::account::Balance;
@@ -58,6 +62,30 @@ pub struct ChainStatus {
     pub timestamp: Timestamp,
 }
 
+/// The outcome of a transaction committed by a chain.
+#[derive(Clone, Debug)]
+pub struct TxOutcome {
+    pub hash: TxHash,
+    pub height: ICSHeight,
+    /// The code of the result of the transaction, zero on success
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    /// The messages of the transaction
+    pub messages: Vec<Any>,
+    /// The IBC events emitted by the transaction,
+    /// or a single `ChainError` event if the transaction failed
+    pub events: Vec<IbcEventWithHeight>,
+}
+
+impl TxOutcome {
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+}
+
 /// Defines a blockchain as understood by the relayer
 pub trait ChainEndpoint: Sized {
     /// Type of light blocks for this chain
@@ -94,6 +122,10 @@ pub trait ChainEndpoint: Sized {
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
+    /// React to the error of a request handled by the chain runtime,
+    /// eg. by failing over to another endpoint if it could not reach the one in use.
+    fn on_error(&self, _e: &Error) {}
+
     // Events
     fn subscribe(&mut self) -> Result<Subscription, Error>;
 
@@ -120,6 +152,23 @@ pub trait ChainEndpoint: Sized {
         Ok(key_pair)
     }
 
+    /// Get the signing key pairs of all the wallets which submit transactions,
+    /// starting with the one of `key_name`
+    fn get_wallet_keys(&mut self) -> Result<Vec<Self::SigningKeyPair>, Error> {
+        crate::time!("get_wallet_keys");
+
+        let config = self.config();
+
+        core::iter::once(&config.key_name)
+            .chain(&config.extra_key_names)
+            .map(|key_name| {
+                self.keybase()
+                    .get_key(key_name)
+                    .map_err(|e| Error::key_not_found(key_name.clone(), e))
+            })
+            .collect()
+    }
+
     fn add_key(&mut self, key_name: &str, key_pair: Self::SigningKeyPair) -> Result<(), Error> {
         self.keybase_mut()
             .add_key(key_name, key_pair)
@@ -349,8 +398,33 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade in progress for the given
+    /// channel end, stored at path `path::ChannelUpgradesPath`.
+    /// A proof can optionally be returned along with the result.
+    /// Fails with `Error::empty_response_value` if no upgrade is in progress.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the error receipt of the last failed
+    /// upgrade of the given channel end, stored at path
+    /// `path::ChannelUpgradeErrorsPath`.
+    /// A proof can optionally be returned along with the result.
+    /// Fails with `Error::empty_response_value` if no upgrade ever failed.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the outcome of the given transactions submitted by the relayer,
+    /// in the same order, if they were all committed.
+    fn query_tx_outcomes(&self, tx_hashes: Vec<TxHash>) -> Result<Option<Vec<TxOutcome>>, Error>;
+
     fn query_packet_events(
         &self,
         request: QueryPacketEventDataRequest,
@@ -625,4 +699,19 @@ pub trait ChainEndpoint: Sized {
         &self,
         requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error>;
+
+    /// Query the ICS-29 fees escrowed for the packets sent on the given channel
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Query the address of the interchain account registered by `owner`
+    /// on the host chain at the other end of the given connection
+    fn query_interchain_account(
+        &self,
+        owner: &Signer,
+        connection_id: &ConnectionId,
+    ) -> Result<Signer, Error>;
 }
@@ -2,9 +2,11 @@ use alloc::sync::Arc;
 use core::fmt::{self, Debug, Display};
 
 use crossbeam_channel as channel;
+use tendermint::Hash as TxHash;
 use tracing::Span;
 
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -15,6 +17,7 @@ use ibc_relayer_types::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -43,7 +46,7 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainStatus, HealthCheck},
+    endpoint::{ChainStatus, HealthCheck, TxOutcome},
     requests::*,
     tracking::TrackedMsgs,
 };
@@ -132,6 +135,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<AnySigningKeyPair>,
     },
 
+    GetWalletKeys {
+        reply_to: ReplyTo<Vec<AnySigningKeyPair>>,
+    },
+
     AddKey {
         key_name: String,
         key: AnySigningKeyPair,
@@ -279,6 +286,18 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(Sequence, Option<MerkleProof>)>,
     },
 
+    QueryUpgrade {
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    },
+
+    QueryUpgradeError {
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    },
+
     BuildChannelProofs {
         port_id: PortId,
         channel_id: ChannelId,
@@ -338,6 +357,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     },
 
+    QueryTxOutcomes {
+        tx_hashes: Vec<TxHash>,
+        reply_to: ReplyTo<Option<Vec<TxOutcome>>>,
+    },
+
     QueryPacketEventData {
         request: QueryPacketEventDataRequest,
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
@@ -359,6 +383,18 @@ pub enum ChainRequest {
         request: Vec<CrossChainQueryRequest>,
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
     },
+
+    QueryIncentivizedPackets {
+        channel_id: ChannelId,
+        port_id: PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    },
+
+    QueryInterchainAccount {
+        owner: Signer,
+        connection_id: ConnectionId,
+        reply_to: ReplyTo<Signer>,
+    },
 }
 
 pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
@@ -398,6 +434,10 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
 
     fn get_key(&self) -> Result<AnySigningKeyPair, Error>;
 
+    /// Return the keys of all the wallets which submit transactions to this chain,
+    /// starting with the one of `key_name`.
+    fn get_wallet_keys(&self) -> Result<Vec<AnySigningKeyPair>, Error>;
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error>;
 
     /// Return the version of the IBC protocol that this chain is running, if known.
@@ -503,6 +543,23 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade in progress for the given
+    /// channel end. A proof can optionally be returned along with the result.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the error receipt of the last failed
+    /// upgrade of the given channel end. A proof can optionally be returned
+    /// along with the result.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     /// Performs a query to retrieve all the channels of a chain.
     fn query_channels(
         &self,
@@ -644,6 +701,10 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the outcome of the given transactions, in the same order,
+    /// if they were all committed.
+    fn query_tx_outcomes(&self, tx_hashes: Vec<TxHash>) -> Result<Option<Vec<TxOutcome>>, Error>;
+
     fn query_packet_events(
         &self,
         request: QueryPacketEventDataRequest,
@@ -665,4 +726,19 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         &self,
         request: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error>;
+
+    /// Query the ICS-29 fees escrowed for the packets sent on the given channel
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Query the address of the interchain account registered by `owner`
+    /// on the host chain at the other end of the given connection
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<Signer, Error>;
 }
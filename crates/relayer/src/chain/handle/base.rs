@@ -1,9 +1,11 @@
 use core::fmt::{Debug, Display, Error as FmtError, Formatter};
 
 use crossbeam_channel as channel;
+use tendermint::Hash as TxHash;
 use tracing::Span;
 
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -11,6 +13,7 @@ use ibc_relayer_types::{
         ics03_connection::version::Version,
         ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
         ics04_channel::packet::{PacketMsgType, Sequence},
+        ics04_channel::upgrade::{ErrorReceipt, Upgrade},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
@@ -23,7 +26,12 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
-    chain::{client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs},
+    chain::{
+        client::ClientSettings,
+        endpoint::{ChainStatus, TxOutcome},
+        requests::*,
+        tracking::TrackedMsgs,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -134,6 +142,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::GetKey { reply_to })
     }
 
+    fn get_wallet_keys(&self) -> Result<Vec<AnySigningKeyPair>, Error> {
+        self.send(|reply_to| ChainRequest::GetWalletKeys { reply_to })
+    }
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error> {
         self.send(|reply_to| ChainRequest::AddKey {
             key_name,
@@ -275,6 +287,30 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgrade {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgradeError {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
@@ -471,6 +507,13 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketEventDataFromTxs { request, reply_to })
     }
 
+    fn query_tx_outcomes(&self, tx_hashes: Vec<TxHash>) -> Result<Option<Vec<TxOutcome>>, Error> {
+        self.send(|reply_to| ChainRequest::QueryTxOutcomes {
+            tx_hashes,
+            reply_to,
+        })
+    }
+
     fn query_packet_events(
         &self,
         request: QueryPacketEventDataRequest,
@@ -505,4 +548,28 @@ impl ChainHandle for BaseChainHandle {
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
         self.send(|reply_to| ChainRequest::CrossChainQuery { request, reply_to })
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.send(|reply_to| ChainRequest::QueryIncentivizedPackets {
+            channel_id,
+            port_id,
+            reply_to,
+        })
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<Signer, Error> {
+        self.send(|reply_to| ChainRequest::QueryInterchainAccount {
+            owner,
+            connection_id,
+            reply_to,
+        })
+    }
 }
@@ -2,10 +2,12 @@ use alloc::sync::Arc;
 use std::thread;
 
 use crossbeam_channel as channel;
+use tendermint::Hash as TxHash;
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{error, Span};
 
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -16,6 +18,7 @@ use ibc_relayer_types::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -42,7 +45,7 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    endpoint::{ChainEndpoint, ChainStatus, HealthCheck, TxOutcome},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::*,
     tracking::TrackedMsgs,
@@ -179,6 +182,10 @@ where
                             self.get_key(reply_to)?
                         },
 
+                        ChainRequest::GetWalletKeys { reply_to } => {
+                            self.get_wallet_keys(reply_to)?
+                        },
+
                         ChainRequest::AddKey { key_name, key, reply_to } => {
                             self.add_key(key_name, key, reply_to)?
                         },
@@ -323,10 +330,22 @@ where
                             self.query_next_sequence_receive(request, include_proof, reply_to)?
                         },
 
+                        ChainRequest::QueryUpgrade { request, include_proof, reply_to } => {
+                            self.query_upgrade(request, include_proof, reply_to)?
+                        },
+
+                        ChainRequest::QueryUpgradeError { request, include_proof, reply_to } => {
+                            self.query_upgrade_error(request, include_proof, reply_to)?
+                        },
+
                         ChainRequest::QueryPacketEventDataFromTxs { request, reply_to } => {
                             self.query_txs(request, reply_to)?
                         },
 
+                        ChainRequest::QueryTxOutcomes { tx_hashes, reply_to } => {
+                            self.query_tx_outcomes(tx_hashes, reply_to)?
+                        },
+
                         ChainRequest::QueryPacketEventData { request, reply_to } => {
                             self.query_packet_events(request, reply_to)?
                         },
@@ -342,6 +361,14 @@ where
                         ChainRequest::CrossChainQuery { request, reply_to } => {
                             self.cross_chain_query(request, reply_to)?
                         }
+
+                        ChainRequest::QueryIncentivizedPackets { channel_id, port_id, reply_to } => {
+                            self.query_incentivized_packets(&channel_id, &port_id, reply_to)?
+                        }
+
+                        ChainRequest::QueryInterchainAccount { owner, connection_id, reply_to } => {
+                            self.query_interchain_account(&owner, &connection_id, reply_to)?
+                        }
                     }
                 },
             }
@@ -350,14 +377,24 @@ where
         Ok(())
     }
 
+    /// Send the result of a request to the given channel,
+    /// after letting the chain react to its error, if any.
+    fn reply<T>(&self, reply_to: ReplyTo<T>, result: Result<T, Error>) -> Result<(), Error> {
+        if let Err(e) = &result {
+            self.chain.on_error(e);
+        }
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn health_check(&mut self, reply_to: ReplyTo<HealthCheck>) -> Result<(), Error> {
         let result = self.chain.health_check();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn subscribe(&mut self, reply_to: ReplyTo<Subscription>) -> Result<(), Error> {
         let subscription = self.chain.subscribe();
-        reply_to.send(subscription).map_err(Error::send)
+        self.reply(reply_to, subscription)
     }
 
     fn send_messages_and_wait_commit(
@@ -366,7 +403,7 @@ where
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
         let result = self.chain.send_messages_and_wait_commit(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn send_messages_and_wait_check_tx(
@@ -375,7 +412,7 @@ where
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     ) -> Result<(), Error> {
         let result = self.chain.send_messages_and_wait_check_tx(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_balance(
@@ -388,7 +425,7 @@ where
             .chain
             .query_balance(key_name.as_deref(), denom.as_deref());
 
-        reply_to.send(balance).map_err(Error::send)
+        self.reply(reply_to, balance)
     }
 
     fn query_all_balances(
@@ -397,32 +434,41 @@ where
         reply_to: ReplyTo<Vec<Balance>>,
     ) -> Result<(), Error> {
         let balances = self.chain.query_all_balances(key_name.as_deref());
-        reply_to.send(balances).map_err(Error::send)
+        self.reply(reply_to, balances)
     }
 
     fn query_denom_trace(&self, hash: String, reply_to: ReplyTo<DenomTrace>) -> Result<(), Error> {
         let denom_trace = self.chain.query_denom_trace(hash);
-        reply_to.send(denom_trace).map_err(Error::send)
+        self.reply(reply_to, denom_trace)
     }
 
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
-        reply_to.send(latest_timestamp).map_err(Error::send)
+        self.reply(reply_to, latest_timestamp)
     }
 
     fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
         let result = self.chain.get_signer();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn get_config(&self, reply_to: ReplyTo<ChainConfig>) -> Result<(), Error> {
         let result = Ok(self.chain.config().clone());
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn get_key(&mut self, reply_to: ReplyTo<AnySigningKeyPair>) -> Result<(), Error> {
         let result = self.chain.get_key().map(Into::into);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn get_wallet_keys(&mut self, reply_to: ReplyTo<Vec<AnySigningKeyPair>>) -> Result<(), Error> {
+        let result = self
+            .chain
+            .get_wallet_keys()
+            .map(|keys| keys.into_iter().map(Into::into).collect());
+
+        self.reply(reply_to, result)
     }
 
     fn add_key(
@@ -435,12 +481,12 @@ where
             .downcast()
             .ok_or_else(|| Error::invalid_key_type(key.key_type()))?;
         let result = self.chain.add_key(&key_name, key);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn ibc_version(&mut self, reply_to: ReplyTo<Option<semver::Version>>) -> Result<(), Error> {
         let result = self.chain.ibc_version();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_header(
@@ -459,7 +505,7 @@ where
                 (header, support)
             });
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     /// Constructs a client state for the given height
@@ -474,7 +520,7 @@ where
             .build_client_state(height, settings)
             .map(|cs| cs.into());
 
-        reply_to.send(client_state).map_err(Error::send)
+        self.reply(reply_to, client_state)
     }
 
     /// Constructs a consensus state for the given height
@@ -492,7 +538,7 @@ where
             .build_consensus_state(verified)
             .map(|cs| cs.into());
 
-        reply_to.send(consensus_state).map_err(Error::send)
+        self.reply(reply_to, consensus_state)
     }
 
     /// Constructs AnyMisbehaviour for the update event
@@ -504,7 +550,7 @@ where
     ) -> Result<(), Error> {
         let misbehaviour = self.chain.check_misbehaviour(&update_event, &client_state);
 
-        reply_to.send(misbehaviour).map_err(Error::send)
+        self.reply(reply_to, misbehaviour)
     }
 
     fn build_connection_proofs_and_client_state(
@@ -522,7 +568,7 @@ where
             height,
         );
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_clients(
@@ -531,7 +577,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_clients(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_connections(
@@ -540,7 +586,7 @@ where
         reply_to: ReplyTo<Vec<ConnectionId>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_client_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_state(
@@ -551,7 +597,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_client_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_client_state(
@@ -561,7 +607,7 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_client_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_consensus_state_heights(
@@ -570,7 +616,7 @@ where
         reply_to: ReplyTo<Vec<Height>>,
     ) -> Result<(), Error> {
         let heights = self.chain.query_consensus_state_heights(request);
-        reply_to.send(heights).map_err(Error::send)
+        self.reply(reply_to, heights)
     }
 
     fn query_consensus_state(
@@ -581,7 +627,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_consensus_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_consensus_state(
@@ -591,17 +637,17 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_consensus_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_commitment_prefix(&self, reply_to: ReplyTo<CommitmentPrefix>) -> Result<(), Error> {
         let prefix = self.chain.query_commitment_prefix();
-        reply_to.send(prefix).map_err(Error::send)
+        self.reply(reply_to, prefix)
     }
 
     fn query_compatible_versions(&self, reply_to: ReplyTo<Vec<Version>>) -> Result<(), Error> {
         let versions = self.chain.query_compatible_versions();
-        reply_to.send(versions).map_err(Error::send)
+        self.reply(reply_to, versions)
     }
 
     fn query_connection(
@@ -611,7 +657,7 @@ where
         reply_to: ReplyTo<(ConnectionEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let connection_end = self.chain.query_connection(request, include_proof);
-        reply_to.send(connection_end).map_err(Error::send)
+        self.reply(reply_to, connection_end)
     }
 
     fn query_connections(
@@ -620,7 +666,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedConnectionEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_connection_channels(
@@ -629,7 +675,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connection_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channels(
@@ -638,7 +684,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel(
@@ -648,7 +694,7 @@ where
         reply_to: ReplyTo<(ChannelEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel_client_state(
@@ -657,7 +703,7 @@ where
         reply_to: ReplyTo<Option<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel_client_state(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_channel_proofs(
@@ -671,7 +717,7 @@ where
             .chain
             .build_channel_proofs(&port_id, &channel_id, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_packet_proofs(
@@ -687,7 +733,7 @@ where
             self.chain
                 .build_packet_proofs(packet_type, port_id, channel_id, sequence, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitment(
@@ -697,7 +743,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitment(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitments(
@@ -706,7 +752,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitments(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_receipt(
@@ -716,7 +762,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_receipt(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_packets(
@@ -725,7 +771,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_packets(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgement(
@@ -737,7 +783,7 @@ where
         let result = self
             .chain
             .query_packet_acknowledgement(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgements(
@@ -746,7 +792,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_acknowledgement(
@@ -755,7 +801,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_next_sequence_receive(
@@ -767,7 +813,27 @@ where
         let result = self
             .chain
             .query_next_sequence_receive(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade(request, include_proof);
+        self.reply(reply_to, result)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade_error(request, include_proof);
+        self.reply(reply_to, result)
     }
 
     fn query_txs(
@@ -776,7 +842,16 @@ where
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_txs(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn query_tx_outcomes(
+        &self,
+        tx_hashes: Vec<TxHash>,
+        reply_to: ReplyTo<Option<Vec<TxOutcome>>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_tx_outcomes(tx_hashes);
+        self.reply(reply_to, result)
     }
 
     fn query_packet_events(
@@ -786,7 +861,7 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_events(request);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -801,7 +876,7 @@ where
             .query_host_consensus_state(request)
             .map(|h| h.into());
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -817,7 +892,7 @@ where
             self.chain
                 .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -828,7 +903,31 @@ where
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
     ) -> Result<(), Error> {
         let result = self.chain.cross_chain_query(request);
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
+
+        Ok(())
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_incentivized_packets(channel_id, port_id);
+        self.reply(reply_to, result)?;
+
+        Ok(())
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: &Signer,
+        connection_id: &ConnectionId,
+        reply_to: ReplyTo<Signer>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_interchain_account(owner, connection_id);
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
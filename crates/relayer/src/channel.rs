@@ -15,10 +15,20 @@ use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenA
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
@@ -26,10 +36,11 @@ use crate::chain::counterparty::{channel_connection_client, channel_state_on_des
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
     IncludeProof, PageRequest, QueryChannelRequest, QueryConnectionChannelsRequest,
-    QueryConnectionRequest, QueryHeight,
+    QueryConnectionRequest, QueryHeight, QueryUpgradeErrorRequest, QueryUpgradeRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::connection::Connection;
+use crate::error::{Error as RelayerError, ErrorDetail as RelayerErrorDetail};
 use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
 use crate::object::Channel as WorkerChannelObject;
 use crate::supervisor::error::Error as SupervisorError;
@@ -231,6 +242,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         counterparty_chain: ChainB,
         channel_open_event: IbcEvent,
     ) -> Result<Channel<ChainA, ChainB>, ChannelError> {
+        // The channel upgrade events do not include the connection identifier,
+        // so the channel is restored from the state of the channel end instead.
+        if let Some(attributes) = channel_open_event.channel_upgrade_attributes() {
+            let channel = WorkerChannelObject {
+                dst_chain_id: counterparty_chain.id(),
+                src_chain_id: chain.id(),
+                src_channel_id: attributes.channel_id.clone(),
+                src_port_id: attributes.port_id.clone(),
+            };
+
+            let height = chain
+                .query_latest_height()
+                .map_err(|e| ChannelError::query(chain.id(), e))?;
+
+            return Self::restore_from_state(chain, counterparty_chain, channel, height)
+                .map(|(channel, _)| channel);
+        }
+
         let channel_event_attributes = channel_open_event
             .clone()
             .channel_attributes()
@@ -747,13 +776,26 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         &mut self,
         state: State,
     ) -> Result<(Option<IbcEvent>, Next), ChannelError> {
-        let event = match (state, self.counterparty_state()?) {
+        let counterparty_state = self.counterparty_state()?;
+
+        // Once both channel ends are open, the only handshake left to relay is the upgrade one,
+        // if an upgrade is in progress on either of them
+        if (state.is_open() || state.is_upgrading())
+            && (counterparty_state.is_open() || counterparty_state.is_upgrading())
+        {
+            if state.is_upgrading() || counterparty_state.is_upgrading() || self.has_upgrade()? {
+                return self.upgrade_step();
+            }
+
+            return Ok((None, Next::Abort));
+        }
+
+        let event = match (state, counterparty_state) {
             (State::Init, State::Uninitialized) => Some(self.build_chan_open_try_and_send()?),
             (State::Init, State::Init) => Some(self.build_chan_open_try_and_send()?),
             (State::TryOpen, State::Init) => Some(self.build_chan_open_ack_and_send()?),
             (State::TryOpen, State::TryOpen) => Some(self.build_chan_open_ack_and_send()?),
             (State::Open, State::TryOpen) => Some(self.build_chan_open_confirm_and_send()?),
-            (State::Open, State::Open) => return Ok((None, Next::Abort)),
 
             // If the counterparty state is already Open but current state is TryOpen,
             // return anyway as the final step is to be done by the counterparty worker.
@@ -799,6 +841,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             IbcEvent::OpenTryChannel(_) => State::TryOpen,
             IbcEvent::OpenAckChannel(_) => State::Open,
             IbcEvent::OpenConfirmChannel(_) => State::Open,
+            IbcEvent::UpgradeTryChannel(_) | IbcEvent::UpgradeAckChannel(_) => State::Flushing,
+            IbcEvent::UpgradeInitChannel(_)
+            | IbcEvent::UpgradeConfirmChannel(_)
+            | IbcEvent::UpgradeOpenChannel(_)
+            | IbcEvent::UpgradeTimeoutChannel(_)
+            | IbcEvent::UpgradeCancelChannel(_) => State::Open,
             _ => State::Uninitialized,
         };
 
@@ -1455,6 +1503,675 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
+    /// Returns whether an upgrade was initialized on either channel end and is still in progress.
+    pub fn has_upgrade(&self) -> Result<bool, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let src_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        if query_upgrade(
+            self.src_chain(),
+            self.src_port_id(),
+            src_channel_id,
+            src_height,
+            IncludeProof::No,
+        )?
+        .is_some()
+        {
+            return Ok(true);
+        }
+
+        let dst_height = self
+            .dst_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let dst_upgrade = query_upgrade(
+            self.dst_chain(),
+            self.dst_port_id(),
+            dst_channel_id,
+            dst_height,
+            IncludeProof::No,
+        )?;
+
+        Ok(dst_upgrade.is_some())
+    }
+
+    /// Advances the channel upgrade handshake by sending to the destination
+    /// chain the message, if any, called for by the state of both channel ends
+    /// and of the upgrades in progress on them.
+    ///
+    /// Returns `Next::Abort` once there is no upgrade left to relay in this direction.
+    pub fn upgrade_step(&self) -> Result<(Option<IbcEvent>, Next), ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let src_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let dst_height = self
+            .dst_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        // Query at specific heights to bypass the cache of open channel ends
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(src_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (dst_channel, _) = self
+            .dst_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: dst_channel_id.clone(),
+                    height: QueryHeight::Specific(dst_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let src_state = *src_channel.state();
+        let dst_state = *dst_channel.state();
+
+        debug!(
+            "upgrade step with channel end states: {}, {}",
+            src_state, dst_state
+        );
+
+        let dst_upgrade = query_upgrade(
+            self.dst_chain(),
+            self.dst_port_id(),
+            dst_channel_id,
+            dst_height,
+            IncludeProof::No,
+        )?
+        .map(|(upgrade, _)| upgrade);
+
+        if let Some(dst_upgrade) = &dst_upgrade {
+            if let Some(event) =
+                self.abort_upgrade_step(&src_channel, &dst_channel, dst_upgrade, src_height)?
+            {
+                return Ok((Some(event), Next::Continue));
+            }
+        }
+
+        // The upgrade of the source channel end is only needed to start the handshake
+        let has_src_upgrade = src_state.is_open()
+            && dst_state.is_open()
+            && query_upgrade(
+                self.src_chain(),
+                self.src_port_id(),
+                src_channel_id,
+                src_height,
+                IncludeProof::No,
+            )?
+            .is_some();
+
+        let step = next_upgrade_step(src_state, dst_state, has_src_upgrade, dst_upgrade.is_some());
+
+        let event = match step {
+            Some(UpgradeStep::Try) => Some(self.build_chan_upgrade_try_and_send()?),
+            Some(UpgradeStep::Ack) => Some(self.build_chan_upgrade_ack_and_send()?),
+            Some(UpgradeStep::Confirm) => Some(self.build_chan_upgrade_confirm_and_send()?),
+            Some(UpgradeStep::Open) => Some(self.build_chan_upgrade_open_and_send()?),
+            // Both channel ends are open and there is no upgrade to start
+            None if src_state.is_open() && dst_state.is_open() => return Ok((None, Next::Abort)),
+            None => None,
+        };
+
+        // The upgrade is complete once both channel ends are open again
+        match event {
+            Some(IbcEvent::UpgradeOpenChannel(_)) if src_state.is_open() => {
+                Ok((event, Next::Abort))
+            }
+            _ => Ok((event, Next::Continue)),
+        }
+    }
+
+    /// Cancels the upgrade in progress on the destination channel end if it failed
+    /// on the source chain, or times it out if it did not complete in time there.
+    fn abort_upgrade_step(
+        &self,
+        src_channel: &ChannelEnd,
+        dst_channel: &ChannelEnd,
+        dst_upgrade: &Upgrade,
+        src_height: Height,
+    ) -> Result<Option<IbcEvent>, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let error_receipt = query_upgrade_error(
+            self.src_chain(),
+            self.src_port_id(),
+            src_channel_id,
+            src_height,
+            IncludeProof::No,
+        )?;
+
+        if let Some((error_receipt, _)) = error_receipt {
+            if upgrade_failed(&error_receipt, dst_channel) {
+                return self.build_chan_upgrade_cancel_and_send().map(Some);
+            }
+        }
+
+        if can_time_out_upgrade(*src_channel.state(), *dst_channel.state()) {
+            let status = self
+                .src_chain()
+                .query_application_status()
+                .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+            if dst_upgrade
+                .timeout
+                .has_expired(status.height, status.timestamp)
+            {
+                return self.build_chan_upgrade_timeout_and_send().map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Queries the source channel end along with its upgrade at the latest
+    /// height of the source chain, and builds the proofs for both of them.
+    fn build_chan_upgrade_proofs(&self) -> Result<(ChannelEnd, Upgrade, Proofs), ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_channel, maybe_channel_proof) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_upgrade, maybe_upgrade_proof) = self
+            .src_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = Proofs::new(
+            proof_bytes(maybe_channel_proof)?,
+            None,
+            None,
+            Some(proof_bytes(maybe_upgrade_proof)?),
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        Ok((src_channel, src_upgrade, proofs))
+    }
+
+    /// Queries the source channel end at the latest height of the
+    /// source chain, and builds the proof for it.
+    fn build_chan_upgrade_channel_proofs(&self) -> Result<(ChannelEnd, Proofs), ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_channel, maybe_channel_proof) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = Proofs::new(
+            proof_bytes(maybe_channel_proof)?,
+            None,
+            None,
+            None,
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        Ok((src_channel, proofs))
+    }
+
+    pub fn build_chan_upgrade_try(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        // The destination channel end must be open to accept a new upgrade
+        let dst_channel = self.b_channel(Some(dst_channel_id))?;
+        if !dst_channel.is_open() {
+            return Err(ChannelError::invalid_upgrade_state(
+                State::Open,
+                *dst_channel.state(),
+            ));
+        }
+
+        let (src_channel, src_upgrade, proofs) = self.build_chan_upgrade_proofs()?;
+
+        if !src_channel.is_open() {
+            return Err(ChannelError::invalid_upgrade_state(
+                *src_channel.state(),
+                *dst_channel.state(),
+            ));
+        }
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // The upgrade keeps the channel on the same connection
+        let new_msg = MsgChannelUpgradeTry {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            proposed_upgrade_connection_hops: dst_channel.connection_hops().clone(),
+            counterparty_upgrade_fields: src_upgrade.fields,
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_try()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTry", |event| {
+            matches!(event, IbcEvent::UpgradeTryChannel(_))
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeTry {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    pub fn build_chan_upgrade_ack(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, src_upgrade, proofs) = self.build_chan_upgrade_proofs()?;
+
+        if *src_channel.state() != State::Flushing {
+            let dst_channel = self.b_channel(Some(dst_channel_id))?;
+            return Err(ChannelError::invalid_upgrade_state(
+                *src_channel.state(),
+                *dst_channel.state(),
+            ));
+        }
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeAck {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_upgrade: src_upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_ack()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeAck", |event| {
+            matches!(event, IbcEvent::UpgradeAckChannel(_))
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeAck {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    pub fn build_chan_upgrade_confirm(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, src_upgrade, proofs) = self.build_chan_upgrade_proofs()?;
+
+        if !src_channel.state().is_upgrading() {
+            let dst_channel = self.b_channel(Some(dst_channel_id))?;
+            return Err(ChannelError::invalid_upgrade_state(
+                *src_channel.state(),
+                *dst_channel.state(),
+            ));
+        }
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeConfirm {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: *src_channel.state(),
+            counterparty_upgrade: src_upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_confirm()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeConfirm", |event| {
+            matches!(
+                event,
+                IbcEvent::UpgradeConfirmChannel(_) | IbcEvent::UpgradeOpenChannel(_)
+            )
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeConfirm {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    pub fn build_chan_upgrade_open(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, proofs) = self.build_chan_upgrade_channel_proofs()?;
+
+        if !matches!(src_channel.state(), State::Open | State::FlushComplete) {
+            let dst_channel = self.b_channel(Some(dst_channel_id))?;
+            return Err(ChannelError::invalid_upgrade_state(
+                *src_channel.state(),
+                *dst_channel.state(),
+            ));
+        }
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeOpen {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: *src_channel.state(),
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_open_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_open()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeOpen", |event| {
+            matches!(event, IbcEvent::UpgradeOpenChannel(_))
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeOpen {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    pub fn build_chan_upgrade_timeout(&self) -> Result<Vec<Any>, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        // The upgrade in progress on the destination holds the timeout set by the source
+        let (dst_upgrade, _) = self
+            .dst_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: dst_channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let status = self
+            .src_chain()
+            .query_application_status()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        if !dst_upgrade
+            .timeout
+            .has_expired(status.height, status.timestamp)
+        {
+            return Err(ChannelError::upgrade_timeout_not_elapsed(
+                self.dst_chain().id(),
+                dst_channel_id.clone(),
+            ));
+        }
+
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(status.height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, status.height)
+            .map_err(ChannelError::channel_proof)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeTimeout {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel: src_channel,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_timeout_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_timeout()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTimeout", |event| {
+            matches!(event, IbcEvent::UpgradeTimeoutChannel(_))
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeTimeout {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    pub fn build_chan_upgrade_cancel(&self) -> Result<Vec<Any>, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (error_receipt, maybe_error_receipt_proof) = self
+            .src_chain()
+            .query_upgrade_error(
+                QueryUpgradeErrorRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = Proofs::new(
+            proof_bytes(maybe_error_receipt_proof)?,
+            None,
+            None,
+            None,
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeCancel {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            error_receipt,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_cancel_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_cancel()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeCancel", |event| {
+            matches!(event, IbcEvent::UpgradeCancelChannel(_))
+        })
+        .map_err(|e| {
+            error!("failed ChanUpgradeCancel {}: {}", self.b_side, e);
+            e
+        })
+    }
+
+    /// Submits the given channel upgrade messages to the destination chain,
+    /// and returns the first event matching `is_expected_event`.
+    fn send_chan_upgrade_msgs(
+        &self,
+        dst_msgs: Vec<Any>,
+        tracking_id: &'static str,
+        is_expected_event: impl Fn(&IbcEvent) -> bool,
+    ) -> Result<IbcEvent, ChannelError> {
+        let tm = TrackedMsgs::new_static(dst_msgs, tracking_id);
+
+        let events = self
+            .dst_chain()
+            .send_messages_and_wait_commit(tm)
+            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+
+        let result = events
+            .into_iter()
+            .find(|event_with_height| {
+                is_expected_event(&event_with_height.event)
+                    || matches!(event_with_height.event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::missing_event(format!("no {} event was in the response", tracking_id))
+            })?;
+
+        match &result.event {
+            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
+            _ => {
+                info!("🎊  {} => {}", self.dst_chain().id(), result);
+                Ok(result.event)
+            }
+        }
+    }
+
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
         self,
         mapper_a: impl Fn(ChainA) -> ChainC,
@@ -1480,6 +2197,105 @@ pub fn extract_channel_id(event: &IbcEvent) -> Result<&ChannelId, ChannelError>
     .ok_or_else(|| ChannelError::missing_event("cannot extract channel_id from result".to_string()))
 }
 
+/// Queries the upgrade in progress on the given channel end, if any.
+fn query_upgrade<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    height: Height,
+    include_proof: IncludeProof,
+) -> Result<Option<(Upgrade, Option<MerkleProof>)>, ChannelError> {
+    let result = chain.query_upgrade(
+        QueryUpgradeRequest {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height: QueryHeight::Specific(height),
+        },
+        include_proof,
+    );
+
+    match result {
+        Ok(upgrade) => Ok(Some(upgrade)),
+        Err(e) if is_empty_response(&e) => Ok(None),
+        Err(e) => Err(ChannelError::query(chain.id(), e)),
+    }
+}
+
+/// Queries the error receipt of the last failed upgrade of the given channel end, if any.
+fn query_upgrade_error<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    height: Height,
+    include_proof: IncludeProof,
+) -> Result<Option<(ErrorReceipt, Option<MerkleProof>)>, ChannelError> {
+    let result = chain.query_upgrade_error(
+        QueryUpgradeErrorRequest {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height: QueryHeight::Specific(height),
+        },
+        include_proof,
+    );
+
+    match result {
+        Ok(error_receipt) => Ok(Some(error_receipt)),
+        Err(e) if is_empty_response(&e) => Ok(None),
+        Err(e) => Err(ChannelError::query(chain.id(), e)),
+    }
+}
+
+/// The message of the channel upgrade handshake to send to the destination chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum UpgradeStep {
+    Try,
+    Ack,
+    Confirm,
+    Open,
+}
+
+/// Returns the message of the channel upgrade handshake called for by the states
+/// of the source and destination channel ends, and by the upgrades in progress on them.
+fn next_upgrade_step(
+    src_state: State,
+    dst_state: State,
+    has_src_upgrade: bool,
+    has_dst_upgrade: bool,
+) -> Option<UpgradeStep> {
+    match (src_state, dst_state) {
+        (State::Open, State::Open) if has_src_upgrade => Some(UpgradeStep::Try),
+        (State::Flushing, State::Open) if has_dst_upgrade => Some(UpgradeStep::Ack),
+        (State::Flushing | State::FlushComplete, State::Flushing) => Some(UpgradeStep::Confirm),
+        (State::Open | State::FlushComplete, State::FlushComplete) => Some(UpgradeStep::Open),
+        _ => None,
+    }
+}
+
+/// Returns whether the upgrade in progress on the destination channel end
+/// failed on the source chain, as recorded by the given error receipt.
+fn upgrade_failed(error_receipt: &ErrorReceipt, dst_channel: &ChannelEnd) -> bool {
+    error_receipt.sequence >= dst_channel.upgrade_sequence
+}
+
+/// Returns whether the upgrade in progress on the destination channel end
+/// can be timed out, once its timeout has expired on the source chain.
+fn can_time_out_upgrade(src_state: State, dst_state: State) -> bool {
+    dst_state.is_upgrading() && matches!(src_state, State::Open | State::Flushing)
+}
+
+fn is_empty_response(e: &RelayerError) -> bool {
+    matches!(e.detail(), RelayerErrorDetail::EmptyResponseValue(_))
+}
+
+fn proof_bytes(maybe_proof: Option<MerkleProof>) -> Result<CommitmentProofBytes, ChannelError> {
+    maybe_proof
+        .ok_or_else(RelayerError::empty_response_proof)
+        .and_then(|proof| {
+            CommitmentProofBytes::try_from(proof).map_err(RelayerError::malformed_proof)
+        })
+        .map_err(ChannelError::channel_proof)
+}
+
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ChannelMsgType {
@@ -1513,3 +2329,80 @@ fn check_destination_channel_state(
         Err(ChannelError::channel_already_exist(channel_id.clone()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+
+    #[test]
+    fn upgrade_step_transitions() {
+        use State::*;
+
+        let cases = [
+            // No upgrade in progress
+            (Open, Open, false, false, None),
+            // Upgrade initialized on the source only
+            (Open, Open, true, false, Some(UpgradeStep::Try)),
+            // Crossing hellos: the upgrade is initialized on both ends
+            (Open, Open, true, true, Some(UpgradeStep::Try)),
+            (Flushing, Open, true, true, Some(UpgradeStep::Ack)),
+            // The destination upgrade was cancelled or timed out
+            (Flushing, Open, true, false, None),
+            (Flushing, Flushing, true, true, Some(UpgradeStep::Confirm)),
+            (
+                FlushComplete,
+                Flushing,
+                true,
+                true,
+                Some(UpgradeStep::Confirm),
+            ),
+            (
+                FlushComplete,
+                FlushComplete,
+                true,
+                true,
+                Some(UpgradeStep::Open),
+            ),
+            (Open, FlushComplete, false, true, Some(UpgradeStep::Open)),
+            // The next step is on the source chain
+            (Open, Flushing, false, true, None),
+            (Flushing, FlushComplete, true, true, None),
+        ];
+
+        for (src_state, dst_state, has_src_upgrade, has_dst_upgrade, expected) in cases {
+            assert_eq!(
+                next_upgrade_step(src_state, dst_state, has_src_upgrade, has_dst_upgrade),
+                expected,
+                "upgrade step for channel end states {src_state}, {dst_state}"
+            );
+        }
+    }
+
+    #[test]
+    fn abort_upgrade_step_transitions() {
+        let dst_channel = ChannelEnd {
+            state: State::Flushing,
+            upgrade_sequence: Sequence::from(2),
+            ..Default::default()
+        };
+
+        let error_receipt = |sequence: u64| ErrorReceipt {
+            sequence: Sequence::from(sequence),
+            message: "upgrade failed".to_string(),
+        };
+
+        // Only the failure of the current upgrade or a later one cancels it
+        assert!(!upgrade_failed(&error_receipt(1), &dst_channel));
+        assert!(upgrade_failed(&error_receipt(2), &dst_channel));
+        assert!(upgrade_failed(&error_receipt(3), &dst_channel));
+
+        // The upgrade can only time out while the destination end is flushing
+        // and the source end has not moved past it
+        assert!(can_time_out_upgrade(State::Open, State::Flushing));
+        assert!(can_time_out_upgrade(State::Flushing, State::FlushComplete));
+        assert!(!can_time_out_upgrade(State::FlushComplete, State::Flushing));
+        assert!(!can_time_out_upgrade(State::Open, State::Open));
+    }
+}
@@ -118,6 +118,26 @@ define_error! {
                     e.port_channel_id, e.chain_id)
             },
 
+        InvalidUpgradeState
+            {
+                state: State,
+                counterparty_state: State,
+            }
+            | e | {
+                format_args!("the channel upgrade cannot be advanced when the channel ends are in states ({0}, {1})",
+                    e.state, e.counterparty_state)
+            },
+
+        UpgradeTimeoutNotElapsed
+            {
+                chain_id: ChainId,
+                channel_id: ChannelId,
+            }
+            | e | {
+                format_args!("the upgrade of channel '{0}' on chain '{1}' has not timed out yet",
+                    e.channel_id, e.chain_id)
+            },
+
         ChannelAlreadyExist
             { channel_id: ChannelId }
             |e| { format_args!("channel '{}' already exist in an incompatible state", e.channel_id) },
@@ -1,7 +1,11 @@
 //! Relayer configuration
 
+pub mod dynamic_gas;
 pub mod error;
+pub mod excluded_sequences;
+pub mod fee_filter;
 pub mod filter;
+pub mod gas_estimation;
 pub mod gas_multiplier;
 pub mod proof_specs;
 pub mod types;
@@ -13,7 +17,12 @@ use core::{
     str::FromStr,
     time::Duration,
 };
-use std::{fs, fs::File, io::Write, path::Path};
+use std::{
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use ibc_proto::google::protobuf::Any;
 use serde_derive::{Deserialize, Serialize};
@@ -24,6 +33,10 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId
 use ibc_relayer_types::timestamp::ZERO_DURATION;
 
 use crate::chain::ChainType;
+use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::excluded_sequences::ExcludedSequences;
+use crate::config::fee_filter::FeeFilter;
+use crate::config::gas_estimation::GasEstimation;
 use crate::config::gas_multiplier::GasMultiplier;
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
 use crate::error::Error as RelayerError;
@@ -178,6 +191,10 @@ pub mod default {
     pub fn auto_register_counterparty_payee() -> bool {
         false
     }
+
+    pub fn event_source_poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -191,6 +208,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub store: StoreConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -356,6 +375,8 @@ pub struct TelemetryConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    #[serde(default)]
+    pub buckets: TelemetryBuckets,
 }
 
 /// Default values for the telemetry configuration.
@@ -367,16 +388,44 @@ impl Default for TelemetryConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3001,
+            buckets: TelemetryBuckets::default(),
         }
     }
 }
 
+/// The upper bounds of the buckets of the latency histograms, in milliseconds.
+///
+/// The histograms whose buckets are not configured use the default
+/// buckets of the telemetry, see `ibc_telemetry::LatencyBuckets`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryBuckets {
+    /// Buckets for the latency between receiving a batch of events
+    /// and submitting the corresponding transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_submitted: Option<Vec<u64>>,
+
+    /// Buckets for the latency between receiving a batch of events
+    /// and confirming the corresponding transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_confirmed: Option<Vec<u64>>,
+
+    /// Buckets for the end-to-end latency of relaying packets, from the block
+    /// including their SendPacket event until the confirmation of their RecvPacket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet_latency: Option<Vec<u64>>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    /// The token to authenticate the requests to the write endpoints with.
+    /// These endpoints are disabled if no token is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
 }
 
 impl Default for RestConfig {
@@ -385,10 +434,23 @@ impl Default for RestConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3000,
+            auth_token: None,
         }
     }
 }
 
+/// Configuration of the on-disk store in which the relayer records the transactions
+/// it submitted until they are confirmed, along with the last height at which the
+/// packets of each path were cleared, so that it can resume from them after a restart.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StoreConfig {
+    pub enabled: bool,
+    /// The directory of the store, `$HOME/.hermes/store` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
 /// It defines the address generation method
 /// TODO: Ethermint `pk_type` to be restricted
 /// after the Cosmos SDK release with ethsecp256k1
@@ -420,6 +482,35 @@ impl Display for AddressType {
     }
 }
 
+/// Specifies how the relayer collects the events emitted by a chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase", tag = "mode", deny_unknown_fields)]
+pub enum EventSource {
+    /// Subscribe to the events pushed by the full node over the WebSocket endpoint
+    /// at `websocket_addr`.
+    #[default]
+    Push,
+
+    /// Periodically poll the `/block_results` RPC endpoint of the full node at `rpc_addr`,
+    /// keeping track of the last processed height so that no block is ever skipped.
+    Pull {
+        #[serde(
+            default = "default::event_source_poll_interval",
+            with = "humantime_serde"
+        )]
+        interval: Duration,
+    },
+}
+
+impl Display for EventSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Push => write!(f, "push"),
+            Self::Pull { interval } => write!(f, "pull (every {:?})", interval),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChainConfig {
@@ -429,10 +520,26 @@ pub struct ChainConfig {
     pub rpc_addr: tendermint_rpc::Url,
     pub websocket_addr: tendermint_rpc::Url,
     pub grpc_addr: tendermint_rpc::Url,
+    /// RPC addresses of the witness nodes against which the headers verified by the
+    /// light client are cross-checked, in order to detect forks of the chain.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub witness_addrs: Vec<tendermint_rpc::Url>,
+    /// RPC, gRPC and WebSocket addresses to fail over to when the endpoint in use
+    /// is unreachable, or lags behind the other endpoints of the same kind.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_addrs: Vec<tendermint_rpc::Url>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_grpc_addrs: Vec<tendermint_rpc::Url>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_websocket_addrs: Vec<tendermint_rpc::Url>,
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
     pub rpc_timeout: Duration,
     pub account_prefix: String,
     pub key_name: String,
+    /// Names of additional keys which submit transactions concurrently with the key above,
+    /// each with its own account sequence. See [`crate::chain::cosmos::wallet`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_key_names: Vec<String>,
     #[serde(default)]
     pub key_store_type: Store,
     pub store_prefix: String,
@@ -493,12 +600,26 @@ pub struct ChainConfig {
     pub trust_threshold: TrustThreshold,
 
     pub gas_price: GasPrice,
+    #[serde(default)]
+    pub dynamic_gas_price: DynamicGasPrice,
+    #[serde(default)]
+    pub gas_estimation: GasEstimation,
 
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    /// Only relay the packets sent from this chain which pay enough ICS-29 fees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_filter: Option<FeeFilter>,
+
+    /// Sequences of the packets sent from this chain which must not be relayed nor cleared
+    #[serde(default, skip_serializing_if = "ExcludedSequences::is_empty")]
+    pub excluded_sequences: ExcludedSequences,
+
     #[serde(default)]
     pub address_type: AddressType,
+    #[serde(default)]
+    pub event_source: EventSource,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
 }
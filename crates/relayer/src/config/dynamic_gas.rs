@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use tendermint_rpc::Url;
+
+flex_error::define_error! {
+    Error {
+        MultiplierTooSmall
+            { value: f64 }
+            |e| {
+                format_args!("`dynamic_gas_price.multiplier` must be greater than or equal to {}, found {}",
+                    DynamicGasPrice::MIN_MULTIPLIER, e.value)
+            },
+
+        InvalidMax
+            { value: f64 }
+            |e| {
+                format_args!("`dynamic_gas_price.max` must be a positive number, found {}",
+                    e.value)
+            },
+    }
+}
+
+/// Configuration for querying the gas price from the fee market module
+/// of the chain before submitting each transaction, instead of using
+/// the static `gas_price`.
+///
+/// The price returned by the fee market module is multiplied by `multiplier`,
+/// and capped at `max`. If the query fails, the static `gas_price` is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawDynamicGasPrice")]
+pub struct DynamicGasPrice {
+    pub enabled: bool,
+    pub multiplier: f64,
+    pub max: f64,
+    /// The gRPC endpoint to query the gas price from, defaults to the chain's `grpc_addr`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc_addr: Option<Url>,
+}
+
+impl DynamicGasPrice {
+    const DEFAULT_MULTIPLIER: f64 = 1.1;
+    const DEFAULT_MAX: f64 = 0.6;
+    const MIN_MULTIPLIER: f64 = 1.0;
+
+    pub fn enabled(multiplier: f64, max: f64) -> Result<Self, Error> {
+        Self::new(true, multiplier, max, None)
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            multiplier: Self::DEFAULT_MULTIPLIER,
+            max: Self::DEFAULT_MAX,
+            grpc_addr: None,
+        }
+    }
+
+    fn new(
+        enabled: bool,
+        multiplier: f64,
+        max: f64,
+        grpc_addr: Option<Url>,
+    ) -> Result<Self, Error> {
+        if multiplier < Self::MIN_MULTIPLIER {
+            return Err(Error::multiplier_too_small(multiplier));
+        }
+
+        if !max.is_finite() || max <= 0.0 {
+            return Err(Error::invalid_max(max));
+        }
+
+        Ok(Self {
+            enabled,
+            multiplier,
+            max,
+            grpc_addr,
+        })
+    }
+
+    /// Apply the multiplier and the cap to the price returned by the fee market module.
+    pub fn adjust(&self, base_price: f64) -> f64 {
+        (base_price * self.multiplier).min(self.max)
+    }
+}
+
+impl Default for DynamicGasPrice {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDynamicGasPrice {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_multiplier")]
+    multiplier: f64,
+    #[serde(default = "default_max")]
+    max: f64,
+    #[serde(default)]
+    grpc_addr: Option<Url>,
+}
+
+fn default_multiplier() -> f64 {
+    DynamicGasPrice::DEFAULT_MULTIPLIER
+}
+
+fn default_max() -> f64 {
+    DynamicGasPrice::DEFAULT_MAX
+}
+
+impl TryFrom<RawDynamicGasPrice> for DynamicGasPrice {
+    type Error = Error;
+
+    fn try_from(raw: RawDynamicGasPrice) -> Result<Self, Self::Error> {
+        Self::new(raw.enabled, raw.multiplier, raw.max, raw.grpc_addr)
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)] // the field of the struct `DummyConfig` defined below is never accessed
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+    use test_log::test;
+
+    #[derive(Debug, Deserialize)]
+    struct DummyConfig {
+        dynamic_gas_price: DynamicGasPrice,
+    }
+
+    #[test]
+    fn parse_dynamic_gas_price() {
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas_price = { enabled = true, multiplier = 1.2, max = 0.1 }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.dynamic_gas_price,
+            DynamicGasPrice::enabled(1.2, 0.1).unwrap()
+        );
+
+        let config =
+            toml::from_str::<DummyConfig>("dynamic_gas_price = { enabled = true }").unwrap();
+        assert_eq!(config.dynamic_gas_price.multiplier, 1.1);
+        assert_eq!(config.dynamic_gas_price.max, 0.6);
+    }
+
+    #[test]
+    fn parse_invalid_dynamic_gas_price() {
+        let err = toml::from_str::<DummyConfig>(
+            "dynamic_gas_price = { enabled = true, multiplier = 0.9 }",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("must be greater than or equal to"));
+
+        assert!(
+            toml::from_str::<DummyConfig>("dynamic_gas_price = { enabled = true, max = 0.0 }")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn adjust_gas_price() {
+        let dynamic_gas_price = DynamicGasPrice::enabled(1.5, 0.6).unwrap();
+
+        assert_eq!(dynamic_gas_price.adjust(0.2), 0.30000000000000004);
+        assert_eq!(dynamic_gas_price.adjust(0.5), 0.6);
+    }
+}
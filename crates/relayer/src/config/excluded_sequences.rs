@@ -0,0 +1,110 @@
+//! Configuration of the packet sequences excluded from relaying.
+
+use alloc::collections::BTreeSet;
+
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use serde_derive::{Deserialize, Serialize};
+
+/// The sequences of the packets sent from a chain which must be neither relayed
+/// nor cleared, per port and channel. This is useful to skip a packet which
+/// cannot be relayed, eg. because its `MsgRecvPacket` always fails, and which
+/// would otherwise hold back the clearing of the packets sent after it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ExcludedSequences(Vec<ChannelExcludedSequences>);
+
+/// The sequences excluded from relaying on a given port and channel.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelExcludedSequences {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequences: Vec<Sequence>,
+}
+
+impl ExcludedSequences {
+    /// Returns true if no sequence is excluded on any channel, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|c| c.sequences.is_empty())
+    }
+
+    /// The sequences excluded on the given port and channel.
+    pub fn for_channel(&self, port_id: &PortId, channel_id: &ChannelId) -> BTreeSet<Sequence> {
+        self.0
+            .iter()
+            .filter(|c| &c.port_id == port_id && &c.channel_id == channel_id)
+            .flat_map(|c| c.sequences.iter().copied())
+            .collect()
+    }
+
+    /// Add the given sequences to the ones excluded on the given port and channel.
+    pub fn exclude(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequences: impl IntoIterator<Item = Sequence>,
+    ) {
+        let mut excluded = self.for_channel(port_id, channel_id);
+        excluded.extend(sequences);
+
+        self.0
+            .retain(|c| &c.port_id != port_id || &c.channel_id != channel_id);
+
+        self.0.push(ChannelExcludedSequences {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequences: excluded.into_iter().collect(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclude_sequences() {
+        let mut excluded: ExcludedSequences = toml::from_str::<Wrapper>(
+            r#"
+            [[excluded_sequences]]
+            port_id = 'transfer'
+            channel_id = 'channel-0'
+            sequences = [3, 1]
+            "#,
+        )
+        .unwrap()
+        .excluded_sequences;
+
+        let transfer = PortId::transfer();
+        let (channel_0, channel_1) = (ChannelId::new(0), ChannelId::new(1));
+
+        assert!(!excluded.is_empty());
+        assert_eq!(
+            excluded.for_channel(&transfer, &channel_0),
+            BTreeSet::from([Sequence::from(1), Sequence::from(3)])
+        );
+        assert!(excluded.for_channel(&transfer, &channel_1).is_empty());
+
+        excluded.exclude(
+            &transfer,
+            &channel_0,
+            [Sequence::from(2), Sequence::from(3)],
+        );
+        excluded.exclude(&transfer, &channel_1, [Sequence::from(7)]);
+
+        assert_eq!(
+            excluded.for_channel(&transfer, &channel_0),
+            BTreeSet::from([1, 2, 3].map(Sequence::from))
+        );
+        assert_eq!(
+            excluded.for_channel(&transfer, &channel_1),
+            BTreeSet::from([Sequence::from(7)])
+        );
+    }
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        excluded_sequences: ExcludedSequences,
+    }
+}
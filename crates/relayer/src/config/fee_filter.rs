@@ -0,0 +1,250 @@
+//! Configuration of the ICS-29 fee filter, which restricts relaying
+//! to the packets paying at least a minimum amount of fees.
+
+use ibc_relayer_types::applications::ics29_fee::packet_fee::PacketFee;
+use ibc_relayer_types::applications::transfer::amount::Amount;
+use ibc_relayer_types::applications::transfer::coin::RawCoin;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::config::filter::{ChannelFilterMatch, PortFilterMatch};
+
+/// The kind of fee paid to the relayer, depending on the message it relays.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FeeKind {
+    /// Fee paid for relaying a `MsgRecvPacket`
+    Recv,
+    /// Fee paid for relaying a `MsgAcknowledgement`
+    Ack,
+    /// Fee paid for relaying a `MsgTimeout` or `MsgTimeoutOnClose`
+    Timeout,
+}
+
+impl FeeKind {
+    const ALL: [Self; 3] = [Self::Recv, Self::Ack, Self::Timeout];
+}
+
+/// Only relay the packets sent from a chain whose fees, escrowed on that chain
+/// by the ICS-29 fee module, meet the configured minimum amounts.
+///
+/// The minimums apply per denomination, and a packet must pay at least the
+/// minimum amount in every denomination listed, for each kind of fee. Since the
+/// receive, acknowledgement and timeout fees of a packet are escrowed together,
+/// all three minimums are conditions on relaying its `MsgRecvPacket`.
+/// Packets which are not incentivized at all are never received. The
+/// acknowledgements and timeouts of all packets are still relayed, so that
+/// every packet received on the counterparty chain completes, and every packet
+/// which timed out is refunded.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeFilter {
+    /// Minimum receive fees of the packets to relay
+    #[serde(default)]
+    pub recv: Vec<RawCoin>,
+    /// Minimum acknowledgement fees of the packets to relay
+    #[serde(default)]
+    pub ack: Vec<RawCoin>,
+    /// Minimum timeout fees of the packets to relay
+    #[serde(default)]
+    pub timeout: Vec<RawCoin>,
+    /// Minimum fees overriding the ones above for specific channels.
+    /// The first entry matching the channel of a packet is used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<ChannelFeeFilter>,
+}
+
+/// Minimum fees for the packets sent on the channels matching the given port and channel.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelFeeFilter {
+    pub port_id: PortFilterMatch,
+    pub channel_id: ChannelFilterMatch,
+    #[serde(default)]
+    pub recv: Vec<RawCoin>,
+    #[serde(default)]
+    pub ack: Vec<RawCoin>,
+    #[serde(default)]
+    pub timeout: Vec<RawCoin>,
+}
+
+impl FeeFilter {
+    /// The minimum fees of the given kind required from a packet
+    /// sent on the given port and channel for relaying its `MsgRecvPacket`.
+    pub fn min_fees(&self, kind: FeeKind, port_id: &PortId, channel_id: &ChannelId) -> &[RawCoin] {
+        let channel = self
+            .channels
+            .iter()
+            .find(|c| c.port_id.matches(port_id) && c.channel_id.matches(channel_id));
+
+        let (recv, ack, timeout) = match channel {
+            Some(c) => (&c.recv, &c.ack, &c.timeout),
+            None => (&self.recv, &self.ack, &self.timeout),
+        };
+
+        match kind {
+            FeeKind::Recv => recv,
+            FeeKind::Ack => ack,
+            FeeKind::Timeout => timeout,
+        }
+    }
+
+    /// Returns true if the fees escrowed for a packet sent on the given port and channel
+    /// meet the receive, acknowledgement and timeout minimums, so that its `MsgRecvPacket`
+    /// can be relayed, false otherwise.
+    ///
+    /// `packet_fees` is `None` if the packet is not incentivized.
+    pub fn is_allowed(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        packet_fees: Option<&[PacketFee]>,
+    ) -> bool {
+        let packet_fees = match packet_fees {
+            Some(packet_fees) if !packet_fees.is_empty() => packet_fees,
+            _ => return false,
+        };
+
+        FeeKind::ALL.iter().all(|&kind| {
+            self.min_fees(kind, port_id, channel_id)
+                .iter()
+                .all(|min| total_fee(kind, packet_fees, &min.denom) >= min.amount)
+        })
+    }
+}
+
+/// Sum the fees of the given kind paid in the given denomination,
+/// across all the fees escrowed for a packet.
+fn total_fee(kind: FeeKind, packet_fees: &[PacketFee], denom: &str) -> Amount {
+    packet_fees
+        .iter()
+        .flat_map(|packet_fee| match kind {
+            FeeKind::Recv => &packet_fee.fee.recv_fee,
+            FeeKind::Ack => &packet_fee.fee.ack_fee,
+            FeeKind::Timeout => &packet_fee.fee.timeout_fee,
+        })
+        .filter(|coin| coin.denom == denom)
+        .fold(Amount::from(0u64), |total, coin| {
+            total.checked_add(coin.amount).unwrap_or(total)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    use ibc_relayer_types::applications::ics29_fee::packet_fee::Fee;
+    use ibc_relayer_types::signer::Signer;
+
+    fn coin(amount: u64, denom: &str) -> RawCoin {
+        RawCoin::new(denom.to_string(), amount)
+    }
+
+    fn packet_fee(recv: Vec<RawCoin>) -> PacketFee {
+        packet_fees(recv, vec![], vec![coin(10, "uatom")])
+    }
+
+    fn packet_fees(recv: Vec<RawCoin>, ack: Vec<RawCoin>, timeout: Vec<RawCoin>) -> PacketFee {
+        PacketFee {
+            fee: Fee {
+                recv_fee: recv,
+                ack_fee: ack,
+                timeout_fee: timeout,
+            },
+            refund_address: Signer::from_str("cosmos1refund").unwrap(),
+        }
+    }
+
+    #[test]
+    fn fee_filter_min_fees() {
+        let filter: FeeFilter = toml::from_str(
+            r#"
+            recv = [{ denom = 'uatom', amount = '100' }, { denom = 'uosmo', amount = '5' }]
+
+            [[channels]]
+            port_id = 'transfer'
+            channel_id = 'channel-1*'
+            recv = [{ denom = 'uatom', amount = '1000' }]
+            "#,
+        )
+        .unwrap();
+
+        let port = PortId::transfer();
+        let channel_0 = ChannelId::new(0);
+        let channel_10 = ChannelId::new(10);
+
+        let enough = [packet_fee(vec![coin(100, "uatom"), coin(5, "uosmo")])];
+        let split = [
+            packet_fee(vec![coin(60, "uatom"), coin(5, "uosmo")]),
+            packet_fee(vec![coin(40, "uatom")]),
+        ];
+        let missing_denom = [packet_fee(vec![coin(1000, "uatom")])];
+
+        assert!(filter.is_allowed(&port, &channel_0, Some(&enough)));
+        assert!(filter.is_allowed(&port, &channel_0, Some(&split)));
+        assert!(!filter.is_allowed(&port, &channel_0, Some(&missing_denom)));
+
+        // Unincentivized packets are never allowed, even without any minimum
+        assert!(!filter.is_allowed(&port, &channel_0, None));
+        assert!(!filter.is_allowed(&port, &channel_0, Some(&[])));
+        assert!(FeeFilter::default().is_allowed(&port, &channel_0, Some(&enough)));
+        assert!(!FeeFilter::default().is_allowed(&port, &channel_0, None));
+
+        // The channel specific minimums override the default ones
+        assert!(!filter.is_allowed(&port, &channel_10, Some(&enough)));
+        assert!(filter.is_allowed(&port, &channel_10, Some(&missing_denom)));
+    }
+
+    #[test]
+    fn fee_filter_min_ack_and_timeout_fees() {
+        let filter: FeeFilter = toml::from_str(
+            r#"
+            recv = [{ denom = 'uatom', amount = '100' }]
+            ack = [{ denom = 'uatom', amount = '50' }]
+            timeout = [{ denom = 'uatom', amount = '20' }]
+
+            [[channels]]
+            port_id = 'transfer'
+            channel_id = 'channel-1'
+            recv = [{ denom = 'uatom', amount = '100' }]
+            "#,
+        )
+        .unwrap();
+
+        let port = PortId::transfer();
+        let channel_0 = ChannelId::new(0);
+        let channel_1 = ChannelId::new(1);
+
+        let enough = [packet_fees(
+            vec![coin(100, "uatom")],
+            vec![coin(50, "uatom")],
+            vec![coin(20, "uatom")],
+        )];
+        let low_ack = [packet_fees(
+            vec![coin(100, "uatom")],
+            vec![coin(49, "uatom")],
+            vec![coin(20, "uatom")],
+        )];
+        let low_timeout = [packet_fees(
+            vec![coin(100, "uatom")],
+            vec![coin(50, "uatom")],
+            vec![coin(19, "uatom")],
+        )];
+        let split_ack = [
+            packet_fees(vec![coin(100, "uatom")], vec![coin(30, "uatom")], vec![]),
+            packet_fees(vec![], vec![coin(20, "uatom")], vec![coin(20, "uatom")]),
+        ];
+
+        assert!(filter.is_allowed(&port, &channel_0, Some(&enough)));
+        assert!(filter.is_allowed(&port, &channel_0, Some(&split_ack)));
+
+        // The receive fee is enough, but the acknowledgement or timeout fee falls short
+        assert!(!filter.is_allowed(&port, &channel_0, Some(&low_ack)));
+        assert!(!filter.is_allowed(&port, &channel_0, Some(&low_timeout)));
+
+        // The channel specific minimums override all three default ones
+        assert!(filter.is_allowed(&port, &channel_1, Some(&low_ack)));
+        assert!(filter.is_allowed(&port, &channel_1, Some(&low_timeout)));
+    }
+}
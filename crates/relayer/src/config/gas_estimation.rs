@@ -0,0 +1,71 @@
+//! Configuration of how the gas needed by a transaction is estimated.
+
+use alloc::collections::BTreeMap;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// How the relayer estimates the gas needed by a transaction before submitting it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasEstimationMode {
+    /// Simulate the transaction, falling back on the static gas table
+    /// when the simulation fails with a potentially recoverable error.
+    #[default]
+    Simulate,
+
+    /// Never simulate the transaction, and only use the static gas table.
+    Static,
+
+    /// Use the gas learned from the transactions previously committed with messages
+    /// of the same types, simulating the transaction until enough has been learned,
+    /// and falling back on the static gas table when the simulation fails.
+    Learned,
+}
+
+/// The gas estimation mode of a chain, along with its static gas table,
+/// which maps the type of a message, without its package, eg. `MsgRecvPacket`,
+/// to the amount of gas needed by each message of this type.
+///
+/// The gas of a transaction is the sum of the gas of its messages, where the messages
+/// whose type is not in the table count for `default_gas`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasEstimation {
+    #[serde(default)]
+    pub mode: GasEstimationMode,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub static_gas: BTreeMap<String, u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GasEstimation, GasEstimationMode};
+
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct DummyConfig {
+        #[serde(default)]
+        gas_estimation: GasEstimation,
+    }
+
+    #[test]
+    fn parse_gas_estimation() {
+        let config: DummyConfig = toml::from_str(
+            r#"
+            gas_estimation = { mode = 'static', static_gas = { MsgUpdateClient = 300000, MsgRecvPacket = 150000 } }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.gas_estimation.mode, GasEstimationMode::Static);
+        assert_eq!(config.gas_estimation.static_gas["MsgUpdateClient"], 300000);
+        assert_eq!(config.gas_estimation.static_gas["MsgRecvPacket"], 150000);
+
+        let config: DummyConfig = toml::from_str("").unwrap();
+        assert_eq!(config.gas_estimation, GasEstimation::default());
+
+        assert!(toml::from_str::<DummyConfig>("gas_estimation = { mode = 'guess' }").is_err());
+    }
+}
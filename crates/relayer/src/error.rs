@@ -105,6 +105,10 @@ define_error! {
             { param: String }
             |e| { format!("missing parameter in GRPC response: {}", e.param) },
 
+        InvalidFeeMarketGasPrice
+            { amount: String }
+            |e| { format!("invalid gas price returned by the fee market module: '{}'", e.amount) },
+
         Decode
             [ TendermintProtoError ]
             |_| { "error decoding protobuf" },
@@ -123,6 +127,17 @@ define_error! {
             [ LightClientIoError ]
             |e| { format!("light client error for RPC address {0}", e.address) },
 
+        LightClientForkDetected
+            {
+                chain_id: String,
+                height: u64,
+                witness: String,
+            }
+            |e| {
+                format!("light client for chain id {0} detected a fork at height {1}: witness {2} disagrees with the primary node",
+                    e.chain_id, e.height, e.witness)
+            },
+
         ChainNotCaughtUp
             {
                 address: String,
@@ -325,6 +340,10 @@ define_error! {
             { reason: String }
             |e| { format!("error raised while submitting the misbehaviour evidence: {0}", e.reason) },
 
+        InvalidEvidence
+            { reason: String }
+            |e| { format!("invalid evidence of misbehaviour: {0}", e.reason) },
+
         InvalidKeyAddress
             { address: String }
             [ TendermintError ]
@@ -554,6 +573,14 @@ define_error! {
                     "Query/DenomTrace RPC returned an empty denom trace for trace hash: {}", e.hash)
             },
 
+        EmptyInterchainAccount
+            { owner: String, connection_id: ConnectionId }
+            |e| {
+                format_args!(
+                    "Query/InterchainAccount RPC returned an empty address for owner {} on connection {}",
+                    e.owner, e.connection_id)
+            },
+
         MessageTooBigForTx
             { len: usize }
             |e| {
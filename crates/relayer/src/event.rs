@@ -14,7 +14,10 @@ use ibc_relayer_types::{
     },
     core::ics04_channel::{
         error::Error as ChannelError,
-        events::{self as channel_events, Attributes as ChannelAttributes},
+        events::{
+            self as channel_events, Attributes as ChannelAttributes,
+            UpgradeAttributes as ChannelUpgradeAttributes,
+        },
         packet::Packet,
         timeout::TimeoutHeight,
     },
@@ -29,6 +32,7 @@ use crate::light_client::decode_header;
 pub mod bus;
 pub mod monitor;
 pub mod rpc;
+pub mod stream;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct IbcEventWithHeight {
@@ -109,6 +113,41 @@ pub fn ibc_event_try_from_abci_event(abci_event: &AbciEvent) -> Result<IbcEvent,
             channel_close_confirm_try_from_abci_event(abci_event)
                 .map_err(IbcEventError::channel)?,
         )),
+        Ok(IbcEventType::UpgradeInitChannel) => Ok(IbcEvent::UpgradeInitChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeTryChannel) => Ok(IbcEvent::UpgradeTryChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeAckChannel) => Ok(IbcEvent::UpgradeAckChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeConfirmChannel) => Ok(IbcEvent::UpgradeConfirmChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeOpenChannel) => Ok(IbcEvent::UpgradeOpenChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeTimeoutChannel) => Ok(IbcEvent::UpgradeTimeoutChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
+        Ok(IbcEventType::UpgradeCancelChannel) => Ok(IbcEvent::UpgradeCancelChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map_err(IbcEventError::channel)?
+                .into(),
+        )),
         Ok(IbcEventType::SendPacket) => Ok(IbcEvent::SendPacket(
             send_packet_try_from_abci_event(abci_event).map_err(IbcEventError::channel)?,
         )),
@@ -393,6 +432,40 @@ fn channel_extract_attributes_from_tx(
     Ok(attr)
 }
 
+fn channel_upgrade_extract_attributes_from_tx(
+    event: &AbciEvent,
+) -> Result<ChannelUpgradeAttributes, ChannelError> {
+    let mut attr = ChannelUpgradeAttributes::default();
+
+    for tag in &event.attributes {
+        let key = tag.key.as_str();
+        let value = tag.value.as_str();
+        match key {
+            channel_events::PORT_ID_ATTRIBUTE_KEY => {
+                attr.port_id = value.parse().map_err(ChannelError::identifier)?
+            }
+            channel_events::CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.channel_id = value.parse().map_err(ChannelError::identifier)?;
+            }
+            channel_events::COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_port_id = value.parse().map_err(ChannelError::identifier)?;
+            }
+            channel_events::COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_channel_id = value.parse().ok();
+            }
+            channel_events::UPGRADE_SEQUENCE_ATTRIBUTE_KEY => {
+                attr.upgrade_sequence = value
+                    .parse::<u64>()
+                    .map_err(|e| ChannelError::invalid_string_as_sequence(value.to_string(), e))?
+                    .into();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(attr)
+}
+
 fn extract_packet_and_write_ack_from_tx(
     event: &AbciEvent,
 ) -> Result<(Packet, Vec<u8>), ChannelError> {
@@ -560,6 +633,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn channel_upgrade_event_to_abci_event() {
+        let attributes = ChannelUpgradeAttributes {
+            port_id: "test_port".parse().unwrap(),
+            channel_id: "channel-0".parse().unwrap(),
+            counterparty_port_id: "counterparty_test_port".parse().unwrap(),
+            counterparty_channel_id: Some("channel-1".parse().unwrap()),
+            upgrade_sequence: Sequence::from(2),
+        };
+        let abci_events = vec![
+            AbciEvent::from(channel_events::UpgradeInit(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeTry(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeAck(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeConfirm(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeOpen(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeTimeout(attributes.clone())),
+            AbciEvent::from(channel_events::UpgradeCancel(attributes.clone())),
+        ];
+
+        for abci_event in abci_events {
+            let ibc_event = ibc_event_try_from_abci_event(&abci_event).unwrap();
+            assert_eq!(ibc_event.event_type().as_str(), abci_event.kind);
+            assert_eq!(ibc_event.channel_upgrade_attributes(), Some(&attributes));
+        }
+    }
+
     #[test]
     fn packet_event_to_abci_event() {
         let packet = Packet {
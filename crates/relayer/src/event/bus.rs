@@ -25,23 +25,25 @@ impl<T> EventBus<T> {
         rx
     }
 
+    /// Subscribe with a channel holding at most `capacity` values.
+    /// A subscriber which lags behind by that many values is disconnected.
+    pub fn subscribe_bounded(&mut self, capacity: usize) -> channel::Receiver<T> {
+        let (tx, rx) = channel::bounded(capacity);
+        self.txs.push_back(tx);
+        rx
+    }
+
     pub fn broadcast(&mut self, value: T)
     where
         T: Clone,
     {
-        let mut disconnected = Vec::new();
-
-        for (idx, tx) in self.txs.iter().enumerate() {
-            // TODO: Avoid cloning when sending to last subscriber
-            if let Err(channel::SendError(_)) = tx.send(value.clone()) {
-                disconnected.push(idx);
-            }
-        }
+        // TODO: Avoid cloning when sending to last subscriber
+        // Remove all disconnected and lagging subscribers along the way
+        self.txs.retain(|tx| tx.try_send(value.clone()).is_ok());
+    }
 
-        // Remove all disconnected subscribers
-        for idx in disconnected {
-            self.txs.remove(idx);
-        }
+    pub fn has_subscribers(&self) -> bool {
+        !self.txs.is_empty()
     }
 }
 
@@ -117,4 +119,50 @@ mod tests {
 
         assert_eq!(counter(), 20);
     }
+
+    #[test]
+    #[serial]
+    fn disconnected_subscribers() {
+        reset_counter();
+
+        let mut bus = EventBus::new();
+
+        let rx1 = bus.subscribe();
+        let rx2 = bus.subscribe();
+        let rx3 = bus.subscribe();
+
+        drop(rx1);
+        drop(rx2);
+
+        bus.broadcast(Value(42));
+
+        assert_eq!(rx3.recv(), Ok(Value(42)));
+        assert!(bus.has_subscribers());
+
+        drop(rx3);
+        bus.broadcast(Value(113));
+
+        assert!(!bus.has_subscribers());
+    }
+
+    #[test]
+    #[serial]
+    fn lagging_subscribers() {
+        reset_counter();
+
+        let mut bus = EventBus::new();
+
+        let lagging = bus.subscribe_bounded(1);
+        let rx = bus.subscribe_bounded(2);
+
+        bus.broadcast(Value(42));
+        bus.broadcast(Value(113));
+
+        assert_eq!(lagging.recv(), Ok(Value(42)));
+        assert!(lagging.recv().is_err());
+
+        assert_eq!(rx.recv(), Ok(Value(42)));
+        assert_eq!(rx.recv(), Ok(Value(113)));
+        assert!(bus.has_subscribers());
+    }
 }
@@ -1,19 +1,21 @@
 use alloc::sync::Arc;
 use core::cmp::Ordering;
+use core::time::Duration;
 
 use crossbeam_channel as channel;
 use futures::{
+    future::join_all,
     pin_mut,
     stream::{self, select_all, StreamExt},
     Stream, TryStreamExt,
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use tendermint_rpc::{
-    event::Event as RpcEvent, query::Query, Error as RpcError, SubscriptionClient, Url,
-    WebSocketClient, WebSocketClientDriver,
+    event::Event as RpcEvent, query::Query, Error as RpcError, HttpClient, Scheme,
+    SubscriptionClient, Url, WebSocketClient, WebSocketClientDriver,
 };
 
 use ibc_relayer_types::{
@@ -21,7 +23,11 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    chain::{handle::Subscription, tracking::TrackingId},
+    chain::{
+        cosmos::endpoints::{check_rpc, EndpointKind, EndpointPool, EndpointStatus},
+        handle::Subscription,
+        tracking::TrackingId,
+    },
     telemetry,
     util::{
         retry::{retry_with_index, RetryResult},
@@ -32,10 +38,15 @@ use crate::{
 mod error;
 pub use error::*;
 
+pub mod pull;
+
 use super::{bus::EventBus, IbcEventWithHeight};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// How long to wait for the status of a node when choosing the node to fail over to.
+const NODE_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
 mod retry_strategy {
     use crate::util::retry::clamp_total;
     use core::time::Duration;
@@ -117,6 +128,8 @@ pub struct EventMonitor {
     rx_cmd: channel::Receiver<MonitorCmd>,
     /// Node Address
     node_addr: Url,
+    /// All the nodes to connect to, starting with the primary one
+    nodes: EndpointPool<Url>,
     /// Queries
     event_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
@@ -165,24 +178,60 @@ pub mod queries {
 
 impl EventMonitor {
     /// Create an event monitor, and connect to a node
+    pub fn new(
+        chain_id: ChainId,
+        node_addr: Url,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxMonitorCmd)> {
+        Self::with_fallbacks(chain_id, node_addr, Vec::new(), rt)
+    }
+
+    /// Create an event monitor, and connect to the first reachable node among the
+    /// given node and the fallback nodes. The monitor moves on to the next node
+    /// whenever it fails to reconnect to the current one.
     #[instrument(
         name = "event_monitor.create",
         level = "error",
         skip_all,
         fields(chain = %chain_id, addr = %node_addr)
     )]
-    pub fn new(
+    pub fn with_fallbacks(
         chain_id: ChainId,
         node_addr: Url,
+        fallback_addrs: Vec<Url>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxMonitorCmd)> {
         let event_bus = EventBus::new();
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let ws_addr = node_addr.clone();
-        let (client, driver) = rt
-            .block_on(async move { WebSocketClient::new(ws_addr).await })
-            .map_err(|_| Error::client_creation_failed(chain_id.clone(), node_addr.clone()))?;
+        let mut nodes = EndpointPool::new(
+            EndpointKind::WebSocket,
+            core::iter::once(node_addr.clone())
+                .chain(fallback_addrs)
+                .map(|addr| (addr.to_string(), addr))
+                .collect(),
+        );
+
+        let (index, client, driver) = nodes
+            .endpoints()
+            .iter()
+            .enumerate()
+            .find_map(|(index, node)| {
+                match rt.block_on(WebSocketClient::new(node.client.clone())) {
+                    Ok((client, driver)) => Some((index, client, driver)),
+                    Err(e) => {
+                        warn!(
+                            "failed to connect to WebSocket endpoint {}: {}",
+                            node.address, e
+                        );
+                        None
+                    }
+                }
+            })
+            .ok_or_else(|| Error::client_creation_failed(chain_id.clone(), node_addr.clone()))?;
+
+        nodes.set_active(index);
+        let node_addr = nodes.active().client.clone();
 
         let (tx_err, rx_err) = mpsc::unbounded_channel();
         let driver_handle = rt.spawn(run_driver(driver, tx_err.clone()));
@@ -201,6 +250,7 @@ impl EventMonitor {
             tx_err,
             rx_cmd,
             node_addr,
+            nodes,
             subscriptions: Box::new(futures::stream::empty()),
         };
 
@@ -247,12 +297,15 @@ impl EventMonitor {
             self.node_addr
         );
 
-        // Try to reconnect
+        // Try to reconnect, moving on to the next node for the next attempt if this one fails
         let (mut client, driver) = self
             .rt
             .block_on(WebSocketClient::new(self.node_addr.clone()))
             .map_err(|_| {
-                Error::client_creation_failed(self.chain_id.clone(), self.node_addr.clone())
+                let e =
+                    Error::client_creation_failed(self.chain_id.clone(), self.node_addr.clone());
+                self.next_node();
+                e
             })?;
 
         let mut driver_handle = self.rt.spawn(run_driver(driver, self.tx_err.clone()));
@@ -278,6 +331,63 @@ impl EventMonitor {
         Ok(())
     }
 
+    /// Switch to the node with the highest block height among the other nodes, as reported
+    /// by their RPC endpoint, or to the node following the current one if none is healthy.
+    fn next_node(&mut self) {
+        let count = self.nodes.endpoints().len();
+        if count < 2 {
+            return;
+        }
+
+        let current = self.nodes.active_index();
+
+        let statuses = self
+            .rt
+            .block_on(join_all(self.nodes.endpoints().iter().enumerate().map(
+                |(index, node)| async move {
+                    if index == current {
+                        return EndpointStatus::Unhealthy;
+                    }
+
+                    match rpc_addr_of(&node.client).and_then(|addr| HttpClient::new(addr).ok()) {
+                        Some(client) => check_rpc(&client, NODE_STATUS_TIMEOUT).await.0,
+                        None => EndpointStatus::Unknown,
+                    }
+                },
+            )));
+
+        for (index, status) in statuses.into_iter().enumerate() {
+            debug!(
+                "WebSocket endpoint {} status: {:?}",
+                self.nodes.endpoints()[index].address,
+                status
+            );
+
+            self.nodes.set_status(index, status);
+        }
+
+        if self.nodes.select().is_none() {
+            self.nodes.set_active((current + 1) % count);
+        }
+
+        let next = self.nodes.active().client.clone();
+
+        warn!(
+            "failing over from WebSocket endpoint {} to {}",
+            self.node_addr, next
+        );
+
+        telemetry!(
+            endpoint_failover,
+            &self.chain_id,
+            EndpointKind::WebSocket.as_str(),
+            &self.node_addr.to_string(),
+            &next.to_string()
+        );
+
+        self.node_addr = next;
+    }
+
     /// Try to resubscribe to events
     #[instrument(
         name = "event_monitor.try_resubscribe",
@@ -503,6 +613,23 @@ fn sort_events(events: &mut [IbcEventWithHeight]) {
     })
 }
 
+/// The RPC address of the node serving the given WebSocket endpoint,
+/// eg. `http://127.0.0.1:26657` for `ws://127.0.0.1:26657/websocket`.
+fn rpc_addr_of(ws_addr: &Url) -> Option<Url> {
+    let scheme = match ws_addr.scheme() {
+        Scheme::WebSocket => "http",
+        Scheme::SecureWebSocket => "https",
+        _ => return None,
+    };
+
+    let path = ws_addr.path().trim_end_matches('/');
+    let path = path.strip_suffix("/websocket").unwrap_or(path);
+
+    format!("{}://{}:{}{}", scheme, ws_addr.host(), ws_addr.port(), path)
+        .parse()
+        .ok()
+}
+
 async fn run_driver(
     driver: WebSocketClientDriver,
     tx: mpsc::UnboundedSender<tendermint_rpc::Error>,
@@ -518,3 +645,24 @@ pub enum Next {
     Abort,
     Continue,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rpc_addr_of;
+
+    #[test]
+    fn rpc_addr_of_websocket_endpoint() {
+        let rpc_addr =
+            |ws_addr: &str| rpc_addr_of(&ws_addr.parse().unwrap()).map(|a| a.to_string());
+
+        assert_eq!(
+            rpc_addr("ws://127.0.0.1:26657/websocket"),
+            Some("http://127.0.0.1:26657/".to_string())
+        );
+        assert_eq!(
+            rpc_addr("wss://rpc.example.com/cosmoshub/websocket"),
+            Some("https://rpc.example.com/cosmoshub".to_string())
+        );
+        assert_eq!(rpc_addr("http://127.0.0.1:26657"), None);
+    }
+}
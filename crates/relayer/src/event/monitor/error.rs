@@ -15,6 +15,11 @@ define_error! {
             { chain_id: ChainId, address: Url }
             |e| { format!("failed to create WebSocket driver for chain {0} with address {1}", e.chain_id, e.address) },
 
+        RpcClientCreationFailed
+            { chain_id: ChainId, address: Url }
+            [ TraceError<RpcError> ]
+            |e| { format!("failed to create RPC client for chain {0} with address {1}", e.chain_id, e.address) },
+
         ClientTerminationFailed
             [ TraceError<tokio::task::JoinError> ]
             |_| { "failed to terminate previous WebSocket driver" },
@@ -48,6 +53,10 @@ define_error! {
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
+
+        BlockResultsUnavailable
+            { height: u64, attempts: u32, reason: String }
+            |e| { format!("failed to fetch the results of block {0} after {1} attempts, skipping it: {2}", e.height, e.attempts, e.reason) },
     }
 }
 
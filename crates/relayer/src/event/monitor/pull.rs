@@ -0,0 +1,353 @@
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error, instrument, trace, warn};
+
+use tendermint::block::Height as TmHeight;
+use tendermint_rpc::{Client, HttpClient, Url};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::{
+    chain::{cosmos::endpoints::Endpoints, tracking::TrackingId},
+    event::bus::EventBus,
+};
+
+use super::{Error, EventBatch, MonitorCmd, Result, TxMonitorCmd};
+
+/// The maximum number of blocks to fetch in a single polling round,
+/// so that the monitor remains responsive to commands while catching up.
+const MAX_BLOCKS_PER_POLL: u64 = 100;
+
+/// The maximum number of attempts at fetching the results of a block,
+/// after which the block is skipped.
+const MAX_FETCH_ATTEMPTS: u32 = 5;
+
+/// The maximum delay between two attempts at fetching the results of a block.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Periodically poll the `/block_results` RPC endpoint of a Tendermint node,
+/// and broadcast the IBC events found in each block as [`EventBatch`]es.
+///
+/// This is an alternative to the WebSocket-based [`EventMonitor`](super::EventMonitor)
+/// for nodes whose WebSocket endpoint is unreliable or disabled.
+///
+/// The monitor keeps track of the height of the last block it has processed,
+/// and fetches every block from there up to the latest one on each polling round.
+/// Blocks which were produced while the node was unreachable are therefore
+/// never skipped, but processed as soon as the node is reachable again.
+///
+/// A block whose results cannot be fetched is retried with an exponential backoff,
+/// up to [`MAX_FETCH_ATTEMPTS`] times. It is then skipped, and an error is broadcast
+/// in place of its events, so that the subscribers clear the pending packets.
+pub struct PullEventMonitor {
+    chain_id: ChainId,
+    /// RPC client to poll the node with
+    client: HttpClient,
+    /// Node address
+    node_addr: Url,
+    /// The RPC endpoints of the chain, to poll the one in use instead of the node above
+    endpoints: Option<Arc<Endpoints>>,
+    /// Interval between two polling rounds
+    poll_interval: Duration,
+    /// Height of the last block whose events have been broadcast
+    last_height: Option<TmHeight>,
+    /// The block whose results could not be fetched yet, along with the number of attempts
+    failing: Option<(TmHeight, u32)>,
+    /// Event bus for broadcasting events
+    event_bus: EventBus<Arc<Result<EventBatch>>>,
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<MonitorCmd>,
+    /// Tokio runtime
+    rt: Arc<TokioRuntime>,
+}
+
+impl PullEventMonitor {
+    /// Create a pull-based event monitor for the node at the given RPC address.
+    #[instrument(
+        name = "pull_event_monitor.create",
+        level = "error",
+        skip_all,
+        fields(chain = %chain_id, addr = %node_addr)
+    )]
+    pub fn new(
+        chain_id: ChainId,
+        node_addr: Url,
+        poll_interval: Duration,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxMonitorCmd)> {
+        let client = HttpClient::new(node_addr.clone()).map_err(|e| {
+            Error::rpc_client_creation_failed(chain_id.clone(), node_addr.clone(), e)
+        })?;
+
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let monitor = Self {
+            chain_id,
+            client,
+            node_addr,
+            endpoints: None,
+            poll_interval,
+            last_height: None,
+            failing: None,
+            event_bus: EventBus::new(),
+            rx_cmd,
+            rt,
+        };
+
+        Ok((monitor, TxMonitorCmd(tx_cmd)))
+    }
+
+    /// Poll the RPC endpoint in use among the given ones, following
+    /// their failovers, instead of the node given on creation.
+    pub fn with_endpoints(mut self, endpoints: Arc<Endpoints>) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// The client for the node to poll, along with its address.
+    fn client(&self) -> (HttpClient, Url) {
+        match &self.endpoints {
+            Some(endpoints) => {
+                let rpc = endpoints.rpc();
+                (rpc.client, rpc.url)
+            }
+            None => (self.client.clone(), self.node_addr.clone()),
+        }
+    }
+
+    /// Event monitor loop
+    #[instrument(
+        name = "pull_event_monitor",
+        level = "error",
+        skip_all,
+        fields(chain = %self.chain_id)
+    )]
+    pub fn run(mut self) {
+        debug!(
+            "starting pull-based event monitor for {} (polling every {:?})",
+            self.node_addr, self.poll_interval
+        );
+
+        let mut next_poll = Instant::now();
+
+        loop {
+            if let Ok(cmd) = self.rx_cmd.try_recv() {
+                match cmd {
+                    MonitorCmd::Shutdown => break,
+                    MonitorCmd::Subscribe(tx) => tx.send(self.event_bus.subscribe()).unwrap(),
+                }
+            }
+
+            if Instant::now() < next_poll {
+                // Sleep in small increments so that commands are handled promptly
+                thread::sleep(self.poll_interval.min(Duration::from_millis(100)));
+                continue;
+            }
+
+            if let Err(e) = self.poll() {
+                error!("failed to poll {} for new blocks: {}", self.client().1, e);
+            }
+
+            next_poll = Instant::now() + self.next_poll_delay();
+        }
+
+        debug!("pull-based event monitor is shutting down");
+    }
+
+    /// Fetch the events of all the blocks produced since the last processed one,
+    /// up to [`MAX_BLOCKS_PER_POLL`] blocks.
+    fn poll(&mut self) -> Result<()> {
+        let (client, _) = self.client();
+
+        let latest_height = self
+            .rt
+            .block_on(client.status())
+            .map_err(Error::rpc)?
+            .sync_info
+            .latest_block_height;
+
+        let mut height = match self.last_height {
+            Some(last_height) => last_height.increment(),
+            None => latest_height,
+        };
+
+        if height.value() + MAX_BLOCKS_PER_POLL <= latest_height.value() {
+            warn!(
+                "node is {} blocks ahead of the last processed block {}, catching up",
+                latest_height.value() - height.value(),
+                height
+            );
+        }
+
+        let mut fetched = 0;
+
+        while height <= latest_height && fetched < MAX_BLOCKS_PER_POLL {
+            let batch = match self.fetch_batch(&client, height) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    self.fetch_failed(height, e);
+                    return Ok(());
+                }
+            };
+
+            self.failing = None;
+
+            trace!(
+                "fetched {} events at height {}",
+                batch.events.len(),
+                batch.height
+            );
+
+            self.process_batch(batch);
+
+            self.last_height = Some(height);
+            height = height.increment();
+            fetched += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the results of the block at the given height,
+    /// and collect the IBC events it contains into an [`EventBatch`].
+    fn fetch_batch(&self, client: &HttpClient, height: TmHeight) -> Result<EventBatch> {
+        let response = self
+            .rt
+            .block_on(client.block_results(height))
+            .map_err(Error::rpc)?;
+
+        let events = crate::event::rpc::get_all_events_from_block_results(&self.chain_id, response)
+            .map_err(Error::collect_events_failed)?;
+
+        let height = events
+            .first()
+            .map(|ev| ev.height)
+            .expect("internal error: missing NewBlock event"); // SAFETY: upheld by `get_all_events_from_block_results`
+
+        Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events,
+        })
+    }
+
+    /// Record a failed attempt at fetching the results of the block at the given height.
+    ///
+    /// Once the block has failed [`MAX_FETCH_ATTEMPTS`] times, it is skipped and the error
+    /// is propagated to the subscribers, which then clear the packets whose events were missed.
+    fn fetch_failed(&mut self, height: TmHeight, e: Error) {
+        let attempts = match self.failing {
+            Some((failing_height, attempts)) if failing_height == height => attempts + 1,
+            _ => 1,
+        };
+
+        if attempts < MAX_FETCH_ATTEMPTS {
+            warn!(
+                "failed to fetch the results of block {} (attempt {}/{}), retrying: {}",
+                height, attempts, MAX_FETCH_ATTEMPTS, e
+            );
+
+            self.failing = Some((height, attempts));
+            return;
+        }
+
+        let error = Error::block_results_unavailable(height.value(), attempts, e.to_string());
+        error!("{}", error);
+
+        self.failing = None;
+        self.last_height = Some(height);
+        self.event_bus.broadcast(Arc::new(Err(error)));
+    }
+
+    /// The delay until the next polling round, which backs off exponentially
+    /// from the polling interval while the results of a block cannot be fetched.
+    fn next_poll_delay(&self) -> Duration {
+        match self.failing {
+            Some((_, attempts)) => retry_delay(self.poll_interval, attempts),
+            None => self.poll_interval,
+        }
+    }
+
+    fn process_batch(&mut self, batch: EventBatch) {
+        self.event_bus.broadcast(Arc::new(Ok(batch)));
+    }
+}
+
+/// The delay before retrying to fetch a block after the given number of failed attempts.
+fn retry_delay(poll_interval: Duration, attempts: u32) -> Duration {
+    poll_interval
+        .checked_mul(2u32.saturating_pow(attempts))
+        .unwrap_or(MAX_RETRY_DELAY)
+        .min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> PullEventMonitor {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let (monitor, _) = PullEventMonitor::new(
+            ChainId::new("chain".to_string(), 0),
+            "http://127.0.0.1:1".parse().unwrap(),
+            Duration::from_secs(1),
+            rt,
+        )
+        .unwrap();
+
+        monitor
+    }
+
+    fn rpc_error() -> Error {
+        Error::rpc(tendermint_rpc::Error::client_internal(
+            "unavailable".to_string(),
+        ))
+    }
+
+    #[test]
+    fn back_off_when_fetching_fails() {
+        let interval = Duration::from_secs(1);
+
+        assert_eq!(retry_delay(interval, 1), Duration::from_secs(2));
+        assert_eq!(retry_delay(interval, 3), Duration::from_secs(8));
+        assert_eq!(retry_delay(interval, 10), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(interval, u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn skip_block_failing_repeatedly() {
+        let mut monitor = monitor();
+        let subscription = monitor.event_bus.subscribe();
+
+        let height = TmHeight::from(10_u32);
+        monitor.last_height = Some(TmHeight::from(9_u32));
+
+        for attempts in 1..MAX_FETCH_ATTEMPTS {
+            monitor.fetch_failed(height, rpc_error());
+
+            assert_eq!(monitor.failing, Some((height, attempts)));
+            assert_eq!(monitor.last_height, Some(TmHeight::from(9_u32)));
+            assert_eq!(
+                monitor.next_poll_delay(),
+                retry_delay(monitor.poll_interval, attempts)
+            );
+            assert!(subscription.try_recv().is_err());
+        }
+
+        monitor.fetch_failed(height, rpc_error());
+
+        assert_eq!(monitor.failing, None);
+        assert_eq!(monitor.last_height, Some(height));
+        assert_eq!(monitor.next_poll_delay(), monitor.poll_interval);
+
+        let result = subscription.try_recv().unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use alloc::collections::BTreeMap as HashMap;
 use core::convert::TryFrom;
 
-use tendermint_rpc::{event::Event as RpcEvent, event::EventData as RpcEventData};
+use tendermint::abci::Event as AbciEvent;
+use tendermint_rpc::{
+    endpoint::block_results, event::Event as RpcEvent, event::EventData as RpcEventData,
+};
 
 use ibc_relayer_types::applications::ics31_icq::events::CrossChainQueryPacket;
 use ibc_relayer_types::core::ics02_client::{events as ClientEvents, height::Height};
@@ -189,6 +192,64 @@ pub fn get_all_events(
     Ok(events_with_height)
 }
 
+/// Extract IBC events from the result of the `/block_results` RPC endpoint.
+///
+/// This is used by the [pull-based event monitor](crate::event::monitor::pull),
+/// and yields the same events as the ones collected over the WebSocket subscriptions
+/// by [`get_all_events`], in the order in which they were emitted by the chain:
+/// a `NewBlock` event first, followed by the `BeginBlock` events, the events of each
+/// successful transaction, and finally the `EndBlock` events.
+pub fn get_all_events_from_block_results(
+    chain_id: &ChainId,
+    response: block_results::Response,
+) -> Result<Vec<IbcEventWithHeight>, String> {
+    let height = Height::new(
+        ChainId::chain_version(chain_id.to_string().as_str()),
+        u64::from(response.height),
+    )
+    .map_err(|_| String::from("block_results.height: invalid header height of 0"))?;
+
+    let mut events_with_height = vec![IbcEventWithHeight::new(
+        ClientEvents::NewBlock::new(height).into(),
+        height,
+    )];
+
+    let tx_events = response
+        .txs_results
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|tx_result| tx_result.code.is_ok())
+        .flat_map(|tx_result| tx_result.events);
+
+    let abci_events = response
+        .begin_block_events
+        .unwrap_or_default()
+        .into_iter()
+        .chain(tx_events)
+        .chain(response.end_block_events.unwrap_or_default());
+
+    events_with_height.extend(
+        abci_events
+            .filter_map(|abci_event| ibc_event_from_abci_event(&abci_event))
+            .map(|ibc_event| IbcEventWithHeight::new(ibc_event, height)),
+    );
+
+    Ok(events_with_height)
+}
+
+/// Convert the given ABCI event into an IBC event, but only if the latter is one of the
+/// events the relayer is interested in, ie. one of the events the event monitor subscribes to.
+fn ibc_event_from_abci_event(abci_event: &AbciEvent) -> Option<IbcEvent> {
+    let ibc_event = ibc_event_try_from_abci_event(abci_event).ok()?;
+
+    let relevant = event_is_type_client(&ibc_event)
+        || event_is_type_connection(&ibc_event)
+        || event_is_type_channel(&ibc_event)
+        || event_is_type_cross_chain_query(&ibc_event);
+
+    relevant.then_some(ibc_event)
+}
+
 fn event_is_type_client(ev: &IbcEvent) -> bool {
     matches!(
         ev,
@@ -218,6 +279,13 @@ fn event_is_type_channel(ev: &IbcEvent) -> bool {
             | IbcEvent::OpenConfirmChannel(_)
             | IbcEvent::CloseInitChannel(_)
             | IbcEvent::CloseConfirmChannel(_)
+            | IbcEvent::UpgradeInitChannel(_)
+            | IbcEvent::UpgradeTryChannel(_)
+            | IbcEvent::UpgradeAckChannel(_)
+            | IbcEvent::UpgradeConfirmChannel(_)
+            | IbcEvent::UpgradeOpenChannel(_)
+            | IbcEvent::UpgradeTimeoutChannel(_)
+            | IbcEvent::UpgradeCancelChannel(_)
             | IbcEvent::SendPacket(_)
             | IbcEvent::ReceivePacket(_)
             | IbcEvent::WriteAcknowledgement(_)
@@ -311,3 +379,59 @@ fn extract_block_events(
 
     events
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics03_connection::events::{
+        Attributes as ConnectionAttributes, OpenInit as ConnectionOpenInit,
+    };
+    use tendermint::abci::{self, response::DeliverTx};
+
+    fn connection_open_init() -> ConnectionOpenInit {
+        ConnectionOpenInit::from(ConnectionAttributes {
+            connection_id: Some("connection-0".parse().unwrap()),
+            client_id: "07-tendermint-0".parse().unwrap(),
+            counterparty_connection_id: None,
+            counterparty_client_id: "07-tendermint-1".parse().unwrap(),
+        })
+    }
+
+    #[test]
+    fn extract_events_from_block_results() {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let open_init = connection_open_init();
+
+        let successful_tx = DeliverTx {
+            events: vec![
+                abci::Event::new("transfer", vec![("amount", "100stake")]),
+                AbciEvent::from(open_init.clone()),
+            ],
+            ..Default::default()
+        };
+
+        let failed_tx = DeliverTx {
+            code: 1.into(),
+            events: vec![AbciEvent::from(open_init.clone())],
+            ..Default::default()
+        };
+
+        let response = block_results::Response {
+            height: 42_u32.into(),
+            txs_results: Some(vec![successful_tx, failed_tx]),
+            begin_block_events: None,
+            end_block_events: None,
+            validator_updates: vec![],
+            consensus_param_updates: None,
+        };
+
+        let events = get_all_events_from_block_results(&chain_id, response).unwrap();
+        let height = Height::new(0, 42).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|ev| ev.height == height));
+        assert!(matches!(events[0].event, IbcEvent::NewBlock(_)));
+        assert!(matches!(&events[1].event, IbcEvent::OpenInitConnection(e) if *e == open_init));
+    }
+}
@@ -0,0 +1,343 @@
+//! A process-wide stream of the events observed and produced by the relayer,
+//! namely the event batches of all the chains it is connected to, along with
+//! the transactions it submits. The REST API forwards it to its subscribers.
+
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+use crossbeam_channel as channel;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::Height;
+
+use crate::event::bus::EventBus;
+use crate::event::IbcEventWithHeight;
+
+pub type EventReceiver = channel::Receiver<Arc<StreamEvent>>;
+
+/// The number of events a subscriber may lag behind before being disconnected.
+pub const SUBSCRIPTION_CAPACITY: usize = 1000;
+
+static BUS: Lazy<Mutex<EventBus<Arc<StreamEvent>>>> = Lazy::new(|| Mutex::new(EventBus::new()));
+
+/// An event streamed to the subscribers of the relayer's events.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// A batch of IBC events emitted by a chain at the given height
+    ChainEvents {
+        chain_id: ChainId,
+        height: Height,
+        events: Vec<IbcEventWithHeight>,
+    },
+
+    /// Transactions relaying packets on the given channel
+    /// were submitted to the chain, and passed `CheckTx`
+    TxSubmitted {
+        chain_id: ChainId,
+        counterparty_chain_id: ChainId,
+        channel_id: ChannelId,
+        port_id: PortId,
+        tracking_id: String,
+        tx_hashes: Vec<String>,
+    },
+
+    /// Transactions relaying packets on the given channel were committed
+    TxConfirmed {
+        chain_id: ChainId,
+        counterparty_chain_id: ChainId,
+        channel_id: ChannelId,
+        port_id: PortId,
+        tracking_id: String,
+        tx_hashes: Vec<String>,
+    },
+
+    /// A transaction relaying packets on the given channel failed,
+    /// either rejected by `CheckTx` or committed with an error.
+    ///
+    /// The hash of the transaction is only known when it was committed.
+    TxFailed {
+        chain_id: ChainId,
+        counterparty_chain_id: ChainId,
+        channel_id: ChannelId,
+        port_id: PortId,
+        tracking_id: String,
+        tx_hash: Option<String>,
+        code: Option<u32>,
+        sdk_error: Option<String>,
+        log: String,
+    },
+
+    /// A witness node disagrees with the primary node on the header of the chain
+    /// at the given height, and the header was therefore not submitted
+    ForkDetected {
+        chain_id: ChainId,
+        height: Height,
+        primary_hash: String,
+        witness: String,
+        witness_hash: String,
+    },
+}
+
+impl StreamEvent {
+    pub fn chain_id(&self) -> &ChainId {
+        match self {
+            Self::ChainEvents { chain_id, .. }
+            | Self::TxSubmitted { chain_id, .. }
+            | Self::TxConfirmed { chain_id, .. }
+            | Self::TxFailed { chain_id, .. }
+            | Self::ForkDetected { chain_id, .. } => chain_id,
+        }
+    }
+}
+
+/// Subscribe to the events of the relayer.
+///
+/// The subscription ends when the returned receiver is dropped, or when the subscriber
+/// lags behind by more than [`SUBSCRIPTION_CAPACITY`] events, in which case the
+/// receiver is disconnected once it has received the events it holds.
+pub fn subscribe() -> EventReceiver {
+    BUS.lock().unwrap().subscribe_bounded(SUBSCRIPTION_CAPACITY)
+}
+
+/// Broadcast the event built by the given closure to the subscribers, if any.
+///
+/// The event is only built when there are subscribers,
+/// so as not to clone whole event batches for nothing.
+pub fn publish_with(f: impl FnOnce() -> StreamEvent) {
+    let mut bus = BUS.lock().unwrap();
+
+    if bus.has_subscribers() {
+        bus.broadcast(Arc::new(f()));
+    }
+}
+
+/// Filters the streamed events by chain, channel and event type.
+///
+/// The events of a batch are filtered individually, so that only the events
+/// on the given channel and of the given types are kept in the batch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamFilter {
+    pub chain_id: Option<ChainId>,
+    pub channel_id: Option<ChannelId>,
+    /// The types of the events to keep, e.g. `send_packet` or `tx_failed`.
+    /// All events are kept when empty.
+    pub event_types: Vec<String>,
+}
+
+impl StreamFilter {
+    /// Returns the part of the given event which passes the filter, if any.
+    pub fn apply(&self, event: &StreamEvent) -> Option<StreamEvent> {
+        if let Some(chain_id) = &self.chain_id {
+            if event.chain_id() != chain_id {
+                return None;
+            }
+        }
+
+        match event {
+            StreamEvent::ChainEvents {
+                chain_id,
+                height,
+                events,
+            } => {
+                let events: Vec<_> = events
+                    .iter()
+                    .filter(|e| {
+                        self.matches_type(e.event.event_type().as_str())
+                            && self.matches_ibc_event_channel(&e.event)
+                    })
+                    .cloned()
+                    .collect();
+
+                (!events.is_empty()).then(|| StreamEvent::ChainEvents {
+                    chain_id: chain_id.clone(),
+                    height: *height,
+                    events,
+                })
+            }
+            StreamEvent::TxSubmitted { channel_id, .. } => {
+                self.matches_tx_event("tx_submitted", channel_id, event)
+            }
+            StreamEvent::TxConfirmed { channel_id, .. } => {
+                self.matches_tx_event("tx_confirmed", channel_id, event)
+            }
+            StreamEvent::TxFailed { channel_id, .. } => {
+                self.matches_tx_event("tx_failed", channel_id, event)
+            }
+            StreamEvent::ForkDetected { .. } => (self.matches_type("fork_detected")
+                && self.channel_id.is_none())
+            .then(|| event.clone()),
+        }
+    }
+
+    fn matches_tx_event(
+        &self,
+        event_type: &str,
+        channel_id: &ChannelId,
+        event: &StreamEvent,
+    ) -> Option<StreamEvent> {
+        (self.matches_type(event_type) && self.matches_channel(channel_id)).then(|| event.clone())
+    }
+
+    fn matches_type(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type)
+    }
+
+    fn matches_channel(&self, channel_id: &ChannelId) -> bool {
+        self.channel_id.as_ref().map_or(true, |c| c == channel_id)
+    }
+
+    /// Packet, channel handshake and channel upgrade events match the channel
+    /// filter if either of their ends is on the given channel. Other events
+    /// never match it.
+    fn matches_ibc_event_channel(&self, event: &IbcEvent) -> bool {
+        let channel_id = match &self.channel_id {
+            Some(channel_id) => channel_id,
+            None => return true,
+        };
+
+        if let Some(packet) = event.packet() {
+            return &packet.source_channel == channel_id
+                || &packet.destination_channel == channel_id;
+        }
+
+        if let Some(attrs) = event.channel_upgrade_attributes() {
+            return &attrs.channel_id == channel_id
+                || attrs.counterparty_channel_id.as_ref() == Some(channel_id);
+        }
+
+        match event.clone().channel_attributes() {
+            Some(attrs) => {
+                attrs.channel_id.as_ref() == Some(channel_id)
+                    || attrs.counterparty_channel_id.as_ref() == Some(channel_id)
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::events::IbcEvent;
+
+    fn send_packet(channel: u64) -> IbcEventWithHeight {
+        let packet = Packet {
+            source_channel: ChannelId::new(channel),
+            destination_channel: ChannelId::new(channel + 10),
+            ..Packet::default()
+        };
+
+        IbcEventWithHeight::new(
+            IbcEvent::SendPacket(SendPacket { packet }),
+            Height::new(0, 1).unwrap(),
+        )
+    }
+
+    fn batch() -> StreamEvent {
+        StreamEvent::ChainEvents {
+            chain_id: ChainId::from_string("chain-a"),
+            height: Height::new(0, 1).unwrap(),
+            events: vec![send_packet(0), send_packet(1)],
+        }
+    }
+
+    fn tx_failed() -> StreamEvent {
+        StreamEvent::TxFailed {
+            chain_id: ChainId::from_string("chain-b"),
+            counterparty_chain_id: ChainId::from_string("chain-a"),
+            channel_id: ChannelId::new(0),
+            port_id: PortId::transfer(),
+            tracking_id: "tracking-id".to_string(),
+            tx_hash: None,
+            code: Some(11),
+            sdk_error: None,
+            log: "out of gas".to_string(),
+        }
+    }
+
+    fn fork_detected() -> StreamEvent {
+        StreamEvent::ForkDetected {
+            chain_id: ChainId::from_string("chain-a"),
+            height: Height::new(0, 1).unwrap(),
+            primary_hash: "primary-hash".to_string(),
+            witness: "witness".to_string(),
+            witness_hash: "witness-hash".to_string(),
+        }
+    }
+
+    #[test]
+    fn filter_by_chain() {
+        let filter = StreamFilter {
+            chain_id: Some(ChainId::from_string("chain-b")),
+            ..StreamFilter::default()
+        };
+
+        assert!(filter.apply(&batch()).is_none());
+        assert!(filter.apply(&tx_failed()).is_some());
+    }
+
+    #[test]
+    fn filter_by_channel() {
+        let filter = StreamFilter {
+            channel_id: Some(ChannelId::new(1)),
+            ..StreamFilter::default()
+        };
+
+        match filter.apply(&batch()) {
+            Some(StreamEvent::ChainEvents { events, .. }) => {
+                assert_eq!(events.len(), 1);
+                assert_eq!(
+                    events[0].event.packet().unwrap().source_channel,
+                    ChannelId::new(1)
+                );
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        assert!(filter.apply(&tx_failed()).is_none());
+    }
+
+    #[test]
+    fn filter_by_event_type() {
+        let filter = StreamFilter {
+            event_types: vec!["tx_failed".to_string()],
+            ..StreamFilter::default()
+        };
+
+        assert!(filter.apply(&batch()).is_none());
+        assert!(filter.apply(&tx_failed()).is_some());
+
+        let filter = StreamFilter {
+            event_types: vec!["send_packet".to_string()],
+            ..StreamFilter::default()
+        };
+
+        assert!(filter.apply(&batch()).is_some());
+        assert!(filter.apply(&tx_failed()).is_none());
+    }
+
+    #[test]
+    fn filter_fork_detected() {
+        let filter = StreamFilter {
+            event_types: vec!["fork_detected".to_string()],
+            ..StreamFilter::default()
+        };
+
+        assert!(filter.apply(&fork_detected()).is_some());
+        assert!(filter.apply(&tx_failed()).is_none());
+
+        let filter = StreamFilter {
+            channel_id: Some(ChannelId::new(0)),
+            ..StreamFilter::default()
+        };
+
+        assert!(filter.apply(&fork_detected()).is_none());
+    }
+}
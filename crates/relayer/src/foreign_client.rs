@@ -1421,7 +1421,14 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
     /// Then the latest header from A is fetched, let it be `h1`, with a timestamp of `t1`.
     /// If `t1 >= t2` then evidence of misbehavior is submitted to A.
     ///
-    /// 3 - BFT time violation for existing headers (TODO):
+    /// 3 - Conflicting header reached through skipping verification:
+    /// The header `h2` in the client update was verified by skipping from its trusted height.
+    /// The verification trace from that trusted height up to the height of `h2` is rebuilt on
+    /// chain A, and its first block `h1` which conflicts with `h2` (same height and different
+    /// hash, or lower height and timestamp not lower than the one of `h2`) is included in the
+    /// evidence along with the headers of the trace needed to trust it.
+    ///
+    /// 4 - BFT time violation for existing headers (TODO):
     /// Ensure that consensus state times are monotonically increasing with height.
     ///
     /// Other notes:
@@ -1,21 +1,29 @@
 pub mod errors;
+pub mod remote_signer;
 pub use any_signing_key_pair::AnySigningKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
+pub use encrypted_key_file::{EncryptedKeyFile, KdfParams};
 pub use key_type::KeyType;
+pub use remote_signer::{RemoteSigner, SignHash};
+pub use sdk_key_file::{SdkKey, SdkKeyAlgo};
 pub use secp256k1_key_pair::Secp256k1KeyPair;
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
 mod any_signing_key_pair;
 mod ed25519_key_pair;
+mod encrypted_key_file;
 mod key_type;
 mod key_utils;
 mod pub_key;
+mod sdk_key_file;
 mod secp256k1_key_pair;
 mod signing_key_pair;
 
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use core::fmt;
 use std::ffi::OsStr;
 use std::fs::{self, File};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -23,11 +31,21 @@ use serde::{Deserialize, Serialize};
 
 use crate::{chain::ChainType, config::ChainConfig};
 use errors::Error;
+use key_utils::decode_bech32;
+use sdk_key_file::Item;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".hermes/keys/";
 pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_FILE_BACKEND: &str = "keyring-file";
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
+/// Environment variable holding the passphrase of the encrypted key stores
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "HERMES_KEYRING_PASSPHRASE";
+/// Environment variable holding the path to a file containing
+/// the passphrase of the encrypted key stores
+pub const KEYSTORE_PASSPHRASE_FILE_ENV: &str = "HERMES_KEYRING_PASSPHRASE_FILE";
+
 /// JSON key seed file
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyFile {
@@ -181,10 +199,312 @@ impl<S: SigningKeyPairSized> KeyStore<S> for Test {
     }
 }
 
+/// Key store compatible with the `file` keyring backend of the Cosmos SDK, which keeps
+/// each key in its own file, encrypted with a key derived from a passphrase.
+/// See [`sdk_key_file`] for the file format.
+///
+/// Only local secp256k1 and ethsecp256k1 keys can be stored.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SdkFile {
+    account_prefix: String,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: String,
+    iterations: u32,
+}
+
+impl SdkFile {
+    const INFO_SUFFIX: &'static str = ".info";
+    const ADDRESS_SUFFIX: &'static str = ".address";
+
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: String) -> Self {
+        Self::with_iterations(
+            account_prefix,
+            store,
+            passphrase,
+            sdk_key_file::DEFAULT_ITERATIONS,
+        )
+    }
+
+    pub fn with_iterations(
+        account_prefix: String,
+        store: PathBuf,
+        passphrase: String,
+        iterations: u32,
+    ) -> Self {
+        Self {
+            account_prefix,
+            store,
+            passphrase,
+            iterations,
+        }
+    }
+
+    fn item_path(&self, item_key: &str) -> PathBuf {
+        self.store.join(sdk_key_file::filename_escape(item_key))
+    }
+
+    fn info_key(key_name: &str) -> String {
+        format!("{key_name}{}", Self::INFO_SUFFIX)
+    }
+
+    /// The key of the item mapping the given account to the name of its key.
+    fn address_key(account: &str) -> Result<String, Error> {
+        let address = decode_bech32(account)?;
+        Ok(format!("{}{}", hex::encode(address), Self::ADDRESS_SUFFIX))
+    }
+
+    fn read_item(&self, item_key: &str) -> Result<Item, Error> {
+        let path = self.item_path(item_key);
+
+        if !path.exists() {
+            return Err(Error::key_file_not_found(format!("{}", path.display())));
+        }
+
+        let jwe = fs::read_to_string(&path).map_err(|e| {
+            Error::key_file_io(
+                path.display().to_string(),
+                "failed to read file".to_string(),
+                e,
+            )
+        })?;
+
+        Item::decrypt(&jwe, &self.passphrase)
+    }
+
+    fn write_item(&self, item: &Item) -> Result<(), Error> {
+        let path = self.item_path(&item.key);
+        let file_path = path.display().to_string();
+
+        let jwe = item.encrypt(&self.passphrase, self.iterations)?;
+
+        let mut file = create_private_file(&path).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to create file".to_string(), e)
+        })?;
+
+        file.write_all(jwe.as_bytes())
+            .map_err(|e| Error::key_file_io(file_path, "failed to write file".to_string(), e))
+    }
+}
+
+// Do not leak the passphrase in logs
+impl fmt::Debug for SdkFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SdkFile")
+            .field("account_prefix", &self.account_prefix)
+            .field("store", &self.store)
+            .field("iterations", &self.iterations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for SdkFile {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        let item = self.read_item(&Self::info_key(key_name))?;
+        let key = sdk_key_file::decode_record(&item.data)?;
+
+        S::from_sdk_key(key, &self.account_prefix)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: S) -> Result<(), Error> {
+        let info_key = Self::info_key(key_name);
+        let record = sdk_key_file::encode_record(key_name, &key_entry.to_sdk_key()?);
+
+        self.write_item(&Item::new(info_key.clone(), record))?;
+
+        // The SDK looks keys up by address through this item
+        let address_key = Self::address_key(&key_entry.account())?;
+        self.write_item(&Item::new(address_key, info_key.into_bytes()))
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let info_key = Self::info_key(key_name);
+
+        // Find the address of the key to remove its address item as well
+        let address_key = KeyStore::<S>::get_key(self, key_name)
+            .and_then(|key| Self::address_key(&key.account()))
+            .ok();
+
+        let path = self.item_path(&info_key);
+        fs::remove_file(&path).map_err(|e| Error::remove_io_fail(path.display().to_string(), e))?;
+
+        if let Some(address_key) = address_key {
+            let path = self.item_path(&address_key);
+            fs::remove_file(&path)
+                .map_err(|e| Error::remove_io_fail(path.display().to_string(), e))?;
+        }
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        dir.into_iter()
+            .flatten()
+            .flat_map(|entry| entry.file_name().into_string())
+            .map(|filename| sdk_key_file::filename_unescape(&filename))
+            .flat_map(|item_key| {
+                item_key
+                    .strip_suffix(Self::INFO_SUFFIX)
+                    .map(ToString::to_string)
+            })
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+/// Key store which keeps each key in its own file, encrypted with a key
+/// derived from a passphrase. See [`EncryptedKeyFile`] for the file format,
+/// which is specific to Hermes: the key files can neither be read nor written
+/// by the `file` keyring backend of the Cosmos SDK.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EncryptedFile {
+    account_prefix: String,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: String,
+    kdf_params: KdfParams,
+}
+
+impl EncryptedFile {
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: String) -> Self {
+        Self::with_kdf_params(account_prefix, store, passphrase, KdfParams::default())
+    }
+
+    pub fn with_kdf_params(
+        account_prefix: String,
+        store: PathBuf,
+        passphrase: String,
+        kdf_params: KdfParams,
+    ) -> Self {
+        Self {
+            account_prefix,
+            store,
+            passphrase,
+            kdf_params,
+        }
+    }
+
+    fn key_file_path(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+        key_file
+    }
+}
+
+// Do not leak the passphrase in logs
+impl fmt::Debug for EncryptedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedFile")
+            .field("account_prefix", &self.account_prefix)
+            .field("store", &self.store)
+            .field("kdf_params", &self.kdf_params)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for EncryptedFile {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        let key_file = self.key_file_path(key_name);
+
+        if !key_file.as_path().exists() {
+            return Err(Error::key_file_not_found(format!("{}", key_file.display())));
+        }
+
+        let file = File::open(&key_file).map_err(|e| {
+            Error::key_file_io(
+                key_file.display().to_string(),
+                "failed to open file".to_string(),
+                e,
+            )
+        })?;
+
+        let encrypted: EncryptedKeyFile = serde_json::from_reader(file)
+            .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e))?;
+
+        let plaintext = encrypted.decrypt(&self.passphrase)?;
+
+        let key_entry = serde_json::from_slice(&plaintext)
+            .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e))?;
+
+        Ok(key_entry)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: S) -> Result<(), Error> {
+        let filename = self.key_file_path(key_name);
+        let file_path = filename.display().to_string();
+
+        let plaintext = serde_json::to_vec(&key_entry).map_err(Error::encode)?;
+        let encrypted = EncryptedKeyFile::encrypt(&plaintext, &self.passphrase, self.kdf_params)?;
+
+        let file = create_private_file(&filename).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to create file".to_string(), e)
+        })?;
+
+        serde_json::to_writer_pretty(file, &encrypted)
+            .map_err(|e| Error::key_file_encode(file_path, e))?;
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let filename = self.key_file_path(key_name);
+
+        fs::remove_file(filename.clone())
+            .map_err(|e| Error::remove_io_fail(filename.display().to_string(), e))?;
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+        dir.into_iter()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(ext))
+            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Store {
     Memory,
     Test,
+    File,
+    Encrypted,
+}
+
+impl Store {
+    /// The store in which keys are managed from the command line.
+    ///
+    /// Keys added to the `Memory` store would be lost as soon as the
+    /// command exits, so the `Test` store is used instead in that case.
+    pub fn persistent(self) -> Self {
+        match self {
+            Self::Memory | Self::Test => Self::Test,
+            Self::File => Self::File,
+            Self::Encrypted => Self::Encrypted,
+        }
+    }
 }
 
 impl Default for Store {
@@ -197,6 +517,8 @@ impl Default for Store {
 pub enum KeyRing<S> {
     Memory(Memory<S>),
     Test(Test),
+    File(SdkFile),
+    Encrypted(EncryptedFile),
 }
 
 impl<S: SigningKeyPairSized> KeyRing<S> {
@@ -221,6 +543,44 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
                     keys_folder,
                 )))
             }
+
+            Store::File => {
+                let passphrase = read_passphrase()?;
+                let keys_folder = sdk_file_store_path(chain_id.as_str())?;
+
+                create_private_dir(&keys_folder).map_err(|e| {
+                    Error::key_file_io(
+                        keys_folder.display().to_string(),
+                        "failed to create keys folder".to_string(),
+                        e,
+                    )
+                })?;
+
+                Ok(Self::File(SdkFile::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                    passphrase,
+                )))
+            }
+
+            Store::Encrypted => {
+                let passphrase = read_passphrase()?;
+                let keys_folder = encrypted_store_path(chain_id.as_str())?;
+
+                create_private_dir(&keys_folder).map_err(|e| {
+                    Error::key_file_io(
+                        keys_folder.display().to_string(),
+                        "failed to create keys folder".to_string(),
+                        e,
+                    )
+                })?;
+
+                Ok(Self::Encrypted(EncryptedFile::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                    passphrase,
+                )))
+            }
         }
     }
 
@@ -228,6 +588,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.get_key(key_name),
             Self::Test(d) => d.get_key(key_name),
+            Self::File(f) => f.get_key(key_name),
+            Self::Encrypted(f) => f.get_key(key_name),
         }
     }
 
@@ -235,6 +597,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.add_key(key_name, key_entry),
             Self::Test(d) => d.add_key(key_name, key_entry),
+            Self::File(f) => f.add_key(key_name, key_entry),
+            Self::Encrypted(f) => f.add_key(key_name, key_entry),
         }
     }
 
@@ -242,6 +606,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.remove_key(key_name),
             Self::Test(d) => <Test as KeyStore<S>>::remove_key(d, key_name),
+            Self::File(f) => <SdkFile as KeyStore<S>>::remove_key(f, key_name),
+            Self::Encrypted(f) => <EncryptedFile as KeyStore<S>>::remove_key(f, key_name),
         }
     }
 
@@ -249,6 +615,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.keys(),
             Self::Test(d) => d.keys(),
+            Self::File(f) => f.keys(),
+            Self::Encrypted(f) => f.keys(),
         }
     }
 
@@ -256,6 +624,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => &m.account_prefix,
             Self::Test(d) => &d.account_prefix,
+            Self::File(f) => &f.account_prefix,
+            Self::Encrypted(f) => &f.account_prefix,
         }
     }
 }
@@ -283,7 +653,11 @@ impl KeyRing<Ed25519KeyPair> {
 pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
     let keys = match config.r#type {
         ChainType::CosmosSdk => {
-            let keyring = KeyRing::new_secp256k1(Store::Test, &config.account_prefix, &config.id)?;
+            let keyring = KeyRing::new_secp256k1(
+                config.key_store_type.persistent(),
+                &config.account_prefix,
+                &config.id,
+            )?;
             keyring
                 .keys()?
                 .into_iter()
@@ -304,3 +678,186 @@ fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
 
     Ok(folder)
 }
+
+fn sdk_file_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+    let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
+
+    let folder = Path::new(home.as_path())
+        .join(KEYSTORE_DEFAULT_FOLDER)
+        .join(folder_name)
+        .join(KEYSTORE_FILE_BACKEND);
+
+    Ok(folder)
+}
+
+fn encrypted_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+    let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
+
+    let folder = Path::new(home.as_path())
+        .join(KEYSTORE_DEFAULT_FOLDER)
+        .join(folder_name)
+        .join(KEYSTORE_ENCRYPTED_BACKEND);
+
+    Ok(folder)
+}
+
+/// Read the passphrase of the encrypted key stores, either directly from the
+/// [`KEYSTORE_PASSPHRASE_ENV`] environment variable, or from the file
+/// whose path is given by the [`KEYSTORE_PASSPHRASE_FILE_ENV`] environment variable.
+fn read_passphrase() -> Result<String, Error> {
+    if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    if let Ok(path) = std::env::var(KEYSTORE_PASSPHRASE_FILE_ENV) {
+        let contents =
+            fs::read_to_string(&path).map_err(|e| Error::passphrase_file_io(path.clone(), e))?;
+
+        // Ignore the trailing newline most editors add to the file
+        return Ok(contents.trim_end_matches(&['\r', '\n'][..]).to_string());
+    }
+
+    Err(Error::passphrase_unavailable(
+        KEYSTORE_PASSPHRASE_ENV.to_string(),
+        KEYSTORE_PASSPHRASE_FILE_ENV.to_string(),
+    ))
+}
+
+/// Create the given folder, only accessible by the current user.
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    }
+
+    Ok(())
+}
+
+/// Create (or truncate) the given file, only readable and writable by the current user.
+fn create_private_file(path: &Path) -> std::io::Result<File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use hdpath::StandardHDPath;
+
+    use super::{EncryptedFile, KdfParams, KeyStore, SdkFile, Secp256k1KeyPair, SigningKeyPair};
+    use crate::config::AddressType;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn encrypted_file_store() {
+        let store = std::env::temp_dir().join(format!("hermes-keyring-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&store).unwrap();
+
+        let kdf_params = KdfParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+
+        let mut keyring = EncryptedFile::with_kdf_params(
+            "cosmos".to_string(),
+            store.clone(),
+            "passphrase".to_string(),
+            kdf_params,
+        );
+
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let key_pair =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos")
+                .unwrap();
+
+        keyring.add_key("relayer", key_pair.clone()).unwrap();
+
+        // The mnemonic-derived secrets must not be stored in plaintext
+        let contents = std::fs::read_to_string(store.join("relayer.json")).unwrap();
+        assert!(!contents.contains(&key_pair.account()));
+
+        let keys: Vec<(String, Secp256k1KeyPair)> = keyring.keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, "relayer");
+        assert_eq!(keys[0].1.account(), key_pair.account());
+
+        let wrong_passphrase = EncryptedFile::with_kdf_params(
+            "cosmos".to_string(),
+            store.clone(),
+            "wrong passphrase".to_string(),
+            kdf_params,
+        );
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&wrong_passphrase, "relayer").is_err());
+
+        KeyStore::<Secp256k1KeyPair>::remove_key(&mut keyring, "relayer").unwrap();
+        assert!(KeyStore::<Secp256k1KeyPair>::keys(&keyring)
+            .unwrap()
+            .is_empty());
+
+        std::fs::remove_dir_all(store).unwrap();
+    }
+
+    #[test]
+    fn sdk_file_store() {
+        let store = std::env::temp_dir().join(format!("hermes-keyring-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&store).unwrap();
+
+        let mut keyring = SdkFile::with_iterations(
+            "cosmos".to_string(),
+            store.clone(),
+            "passphrase".to_string(),
+            16,
+        );
+
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let key_pair =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos")
+                .unwrap();
+
+        keyring.add_key("relayer", key_pair.clone()).unwrap();
+
+        // The layout is the one of the SDK: a `.info` file for the key, and an `.address` file
+        // for its hex-encoded address, both holding a JWE in compact serialization
+        let address = super::decode_bech32(&key_pair.account()).unwrap();
+        let address_file = store.join(format!("{}.address", hex::encode(address)));
+        let contents = std::fs::read_to_string(store.join("relayer.info")).unwrap();
+        assert_eq!(contents.split('.').count(), 5);
+        assert!(address_file.exists());
+
+        let keys: Vec<(String, Secp256k1KeyPair)> = keyring.keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, "relayer");
+        assert_eq!(keys[0].1.account(), key_pair.account());
+        assert_eq!(keys[0].1.public_key, key_pair.public_key);
+
+        let wrong_passphrase = SdkFile::with_iterations(
+            "cosmos".to_string(),
+            store.clone(),
+            "wrong passphrase".to_string(),
+            16,
+        );
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&wrong_passphrase, "relayer").is_err());
+
+        KeyStore::<Secp256k1KeyPair>::remove_key(&mut keyring, "relayer").unwrap();
+        assert!(KeyStore::<Secp256k1KeyPair>::keys(&keyring)
+            .unwrap()
+            .is_empty());
+        assert!(!address_file.exists());
+
+        std::fs::remove_dir_all(store).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use serde::{Deserialize, Serialize};
+
+use super::errors::Error;
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const KEY_LENGTH: usize = 32;
+
+/// Parameters of the scrypt key derivation function used to derive
+/// the encryption key from the passphrase.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        let params = scrypt::Params::recommended();
+
+        Self {
+            log_n: params.log_n(),
+            r: params.r(),
+            p: params.p(),
+        }
+    }
+}
+
+/// Key file whose contents are encrypted with AES-256-GCM, using a key
+/// derived from a passphrase with scrypt.
+///
+/// Every file uses its own random salt and nonce, which are stored
+/// alongside the ciphertext, hex-encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKeyFile {
+    kdf: String,
+    kdf_params: KdfParams,
+    salt: String,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl EncryptedKeyFile {
+    const KDF: &'static str = "scrypt";
+    const CIPHER: &'static str = "aes-256-gcm";
+
+    pub fn encrypt(
+        plaintext: &[u8],
+        passphrase: &str,
+        kdf_params: KdfParams,
+    ) -> Result<Self, Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let cipher = derive_cipher(passphrase, &salt, kdf_params)?;
+
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| Error::encryption("failed to encrypt key".to_string()))?;
+
+        Ok(Self {
+            kdf: Self::KDF.to_string(),
+            kdf_params,
+            salt: hex::encode(salt),
+            cipher: Self::CIPHER.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, Error> {
+        if self.kdf != Self::KDF || self.cipher != Self::CIPHER {
+            return Err(Error::encryption(format!(
+                "unsupported encryption scheme {}/{}",
+                self.kdf, self.cipher
+            )));
+        }
+
+        let salt = decode_hex("salt", &self.salt)?;
+        let nonce = decode_hex("nonce", &self.nonce)?;
+        let ciphertext = decode_hex("ciphertext", &self.ciphertext)?;
+
+        if nonce.len() != NONCE_LENGTH {
+            return Err(Error::encryption(format!(
+                "invalid nonce length {}, expected {}",
+                nonce.len(),
+                NONCE_LENGTH
+            )));
+        }
+
+        let cipher = derive_cipher(passphrase, &salt, self.kdf_params)?;
+
+        // AES-GCM is authenticated, so decryption fails if the passphrase is wrong
+        // or if the file has been tampered with.
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| Error::decryption_failed())
+    }
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8], kdf_params: KdfParams) -> Result<Aes256Gcm, Error> {
+    let params = scrypt::Params::new(kdf_params.log_n, kdf_params.r, kdf_params.p)
+        .map_err(|e| Error::encryption(format!("invalid scrypt parameters: {}", e)))?;
+
+    let mut key = [0u8; KEY_LENGTH];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| Error::encryption(format!("failed to derive encryption key: {}", e)))?;
+
+    Aes256Gcm::new_from_slice(&key)
+        .map_err(|_| Error::encryption("invalid encryption key length".to_string()))
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value).map_err(|e| Error::encryption(format!("invalid {}: {}", field, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptedKeyFile, KdfParams};
+
+    // Cheap parameters to keep the tests fast
+    const TEST_KDF_PARAMS: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let plaintext = b"some secret mnemonic";
+
+        let file = EncryptedKeyFile::encrypt(plaintext, "passphrase", TEST_KDF_PARAMS).unwrap();
+        assert!(!file.ciphertext.contains(&hex::encode(plaintext)));
+
+        let decrypted = file.decrypt("passphrase").unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        assert!(file.decrypt("wrong passphrase").is_err());
+    }
+}
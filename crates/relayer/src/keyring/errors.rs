@@ -92,6 +92,47 @@ define_error! {
                     e.file_path)
             },
 
+        Encryption
+            { reason: String }
+            |e| { format!("key encryption error: {}", e.reason) },
+
+        DecryptionFailed
+            |_| { "failed to decrypt key: wrong passphrase or corrupted key file" },
+
+        InvalidSdkKeyFile
+            { reason: String }
+            |e| { format!("invalid key file of the Cosmos SDK `file` keyring: {}", e.reason) },
+
+        UnsupportedSdkKey
+            { reason: String }
+            |e| { format!("key not supported by the Cosmos SDK `file` keyring: {}", e.reason) },
+
+        PassphraseUnavailable
+            { env_var: String, file_env_var: String }
+            |e| {
+                format!("no passphrase for the encrypted key store: set either `{}` to the passphrase, or `{}` to the path of a file containing it",
+                    e.env_var, e.file_env_var)
+            },
+
+        PassphraseFileIo
+            { file_path: String }
+            [ TraceError<IoError> ]
+            |e| {
+                format!("I/O error while reading the passphrase file at '{}'",
+                    e.file_path)
+            },
+
+        RemoteSigner
+            { url: String, reason: String }
+            |e| { format!("remote signer at '{}' failed: {}", e.url, e.reason) },
+
+        RemoteSignerPublicKeyMismatch
+            { url: String, key_name: String }
+            |e| {
+                format!("remote signer at '{}' signed with another key than the one registered for key '{}'",
+                    e.url, e.key_name)
+            },
+
         HomeLocationUnavailable
             |_| { "home location is unavailable" },
 
@@ -0,0 +1,182 @@
+//! Client for a remote signer, which holds private keys on behalf of the relayer.
+//!
+//! The remote signer speaks a small JSON protocol over HTTP:
+//!
+//! - `POST /pubkey` with `{ "key_name": <name> }` replies with
+//!   `{ "pub_key": <hex-encoded compressed secp256k1 public key> }`.
+//! - `POST /sign` with `{ "key_name": <name>, "sign_bytes": <hex>, "hash": "sha256" | "keccak256" }`
+//!   replies with `{ "signature": <hex-encoded 64 bytes compact signature>, "pub_key": <hex> }`.
+//!
+//! The remote signer is expected to hash the sign bytes with the given hash
+//! function before signing them, so that the relayer never needs to hold the private key.
+//! Errors are reported with a non-2xx status code and a `{ "error": <message> }` body.
+
+use core::time::Duration;
+
+use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::errors::Error;
+
+/// How long to wait for a reply from the remote signer
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The hash function to apply to the sign bytes before signing them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignHash {
+    Sha256,
+    Keccak256,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PubKeyRequest {
+    pub key_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PubKeyResponse {
+    pub pub_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub key_name: String,
+    pub sign_bytes: String,
+    pub hash: SignHash,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignResponse {
+    pub signature: String,
+    pub pub_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// A key held by a remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSigner {
+    /// Address of the remote signer, eg. `http://127.0.0.1:3500`
+    pub url: String,
+    /// Name of the key on the remote signer
+    pub key_name: String,
+}
+
+impl RemoteSigner {
+    pub fn new(url: String, key_name: String) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            key_name,
+        }
+    }
+
+    /// Fetch the public key of this key from the remote signer.
+    pub fn public_key(&self) -> Result<PublicKey, Error> {
+        let response: PubKeyResponse = self.call(
+            "/pubkey",
+            &PubKeyRequest {
+                key_name: self.key_name.clone(),
+            },
+        )?;
+
+        decode_public_key(&self.url, &response.pub_key)
+    }
+
+    /// Ask the remote signer to sign the given message, and check that the signature
+    /// it returns is valid for the `expected` public key before returning it.
+    pub fn sign(
+        &self,
+        message: &[u8],
+        hashed_message: &[u8],
+        hash: SignHash,
+        expected: &PublicKey,
+    ) -> Result<Vec<u8>, Error> {
+        let response: SignResponse = self.call(
+            "/sign",
+            &SignRequest {
+                key_name: self.key_name.clone(),
+                sign_bytes: hex::encode(message),
+                hash,
+            },
+        )?;
+
+        let public_key = decode_public_key(&self.url, &response.pub_key)?;
+        if &public_key != expected {
+            return Err(Error::remote_signer_public_key_mismatch(
+                self.url.clone(),
+                self.key_name.clone(),
+            ));
+        }
+
+        let signature = hex::decode(&response.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_compact(&bytes).ok())
+            .ok_or_else(|| {
+                Error::remote_signer(
+                    self.url.clone(),
+                    format!("invalid signature '{}'", response.signature),
+                )
+            })?;
+
+        // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`
+        let message = Message::from_slice(hashed_message).unwrap();
+
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, expected)
+            .map_err(|_| {
+                Error::remote_signer(
+                    self.url.clone(),
+                    format!(
+                        "signature returned for key '{}' does not verify",
+                        self.key_name
+                    ),
+                )
+            })?;
+
+        Ok(signature.serialize_compact().to_vec())
+    }
+
+    fn call<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> Result<Res, Error> {
+        let body = serde_json::to_string(request).map_err(Error::encode)?;
+
+        let response = ureq::post(&format!("{}{}", self.url, path))
+            .timeout(REMOTE_SIGNER_TIMEOUT)
+            .set("Content-Type", "application/json")
+            .send_string(&body);
+
+        let body = match response {
+            Ok(response) => response.into_string(),
+            Err(ureq::Error::Status(code, response)) => {
+                let reason = response
+                    .into_string()
+                    .ok()
+                    .and_then(|body| serde_json::from_str::<ErrorResponse>(&body).ok())
+                    .map_or_else(|| format!("status code {}", code), |e| e.error);
+
+                return Err(Error::remote_signer(self.url.clone(), reason));
+            }
+            Err(e) => return Err(Error::remote_signer(self.url.clone(), e.to_string())),
+        }
+        .map_err(|e| Error::remote_signer(self.url.clone(), e.to_string()))?;
+
+        serde_json::from_str(&body)
+            .map_err(|e| Error::remote_signer(self.url.clone(), format!("invalid reply: {}", e)))
+    }
+}
+
+fn decode_public_key(url: &str, pub_key: &str) -> Result<PublicKey, Error> {
+    hex::decode(pub_key)
+        .ok()
+        .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+        .ok_or_else(|| {
+            Error::remote_signer(url.to_string(), format!("invalid public key '{}'", pub_key))
+        })
+}
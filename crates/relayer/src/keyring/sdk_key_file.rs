@@ -0,0 +1,478 @@
+//! Format of the key files of the `file` keyring backend of the Cosmos SDK.
+//!
+//! Each key is kept in a `<name>.info` file, which holds a JSON item whose data is the
+//! Protobuf-encoded `cosmos.crypto.keyring.v1.Record` of the key. The item is encrypted
+//! as a JWE in compact serialization, with the `PBES2-HS256+A128KW` key management
+//! algorithm and the `A256GCM` content encryption algorithm, using the passphrase of
+//! the key store. A `<address>.address` file, encrypted the same way, maps the
+//! hex-encoded address of the key to the name of its `.info` file.
+//!
+//! The SDK also keeps a bcrypt hash of the passphrase in a `keyhash` file, which it only
+//! uses to check the passphrase entered at its prompt. Hermes neither reads nor writes
+//! that file: a wrong passphrase is detected when decrypting the key files instead.
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt};
+use aes::Aes128;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hmac::Hmac;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use super::errors::Error;
+
+/// Default number of PBKDF2 iterations, the same as the SDK.
+pub const DEFAULT_ITERATIONS: u32 = 8192;
+
+const ALG: &str = "PBES2-HS256+A128KW";
+const ENC: &str = "A256GCM";
+
+const SALT_LENGTH: usize = 12;
+const NONCE_LENGTH: usize = 12;
+const CEK_LENGTH: usize = 32;
+const KEK_LENGTH: usize = 16;
+
+const SECP256K1_PRIV_KEY: &str = "/cosmos.crypto.secp256k1.PrivKey";
+const SECP256K1_PUB_KEY: &str = "/cosmos.crypto.secp256k1.PubKey";
+const ETH_SECP256K1_PRIV_KEY: &str = "/ethermint.crypto.v1.ethsecp256k1.PrivKey";
+const ETH_SECP256K1_PUB_KEY: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// The algorithm of a key stored by the `file` keyring backend of the SDK.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SdkKeyAlgo {
+    Secp256k1,
+    EthSecp256k1,
+}
+
+impl SdkKeyAlgo {
+    fn type_urls(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Secp256k1 => (SECP256K1_PRIV_KEY, SECP256K1_PUB_KEY),
+            Self::EthSecp256k1 => (ETH_SECP256K1_PRIV_KEY, ETH_SECP256K1_PUB_KEY),
+        }
+    }
+}
+
+/// A key as stored in the record of the `file` keyring backend of the SDK.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SdkKey {
+    pub algo: SdkKeyAlgo,
+    pub private_key: Vec<u8>,
+    /// The compressed public key
+    pub public_key: Vec<u8>,
+}
+
+/// The `cosmos.crypto.keyring.v1.Record` of a key. Only records of local keys are supported,
+/// whose item is the `local` field of the `item` one-of.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Record {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, optional, tag = "2")]
+    pub_key: Option<Any>,
+    #[prost(message, optional, tag = "3")]
+    local: Option<LocalRecord>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct LocalRecord {
+    #[prost(message, optional, tag = "1")]
+    priv_key: Option<Any>,
+}
+
+/// The `PrivKey` and `PubKey` messages of both secp256k1 and ethsecp256k1 keys.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RawKey {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+}
+
+/// Encode the record of the given key.
+pub fn encode_record(name: &str, key: &SdkKey) -> Vec<u8> {
+    let (priv_key_type, pub_key_type) = key.algo.type_urls();
+
+    let raw_key = |type_url: &str, key: &[u8]| Any {
+        type_url: type_url.to_string(),
+        value: RawKey { key: key.to_vec() }.encode_to_vec(),
+    };
+
+    Record {
+        name: name.to_string(),
+        pub_key: Some(raw_key(pub_key_type, &key.public_key)),
+        local: Some(LocalRecord {
+            priv_key: Some(raw_key(priv_key_type, &key.private_key)),
+        }),
+    }
+    .encode_to_vec()
+}
+
+/// Decode the key of the given record.
+pub fn decode_record(bytes: &[u8]) -> Result<SdkKey, Error> {
+    let record = Record::decode(bytes).map_err(|e| Error::invalid_sdk_key_file(e.to_string()))?;
+
+    let priv_key = record
+        .local
+        .and_then(|local| local.priv_key)
+        .ok_or_else(|| {
+            Error::unsupported_sdk_key("only the records of local keys are supported".to_string())
+        })?;
+
+    let pub_key = record
+        .pub_key
+        .ok_or_else(|| Error::invalid_sdk_key_file("missing public key".to_string()))?;
+
+    let algo = match priv_key.type_url.as_str() {
+        SECP256K1_PRIV_KEY => SdkKeyAlgo::Secp256k1,
+        ETH_SECP256K1_PRIV_KEY => SdkKeyAlgo::EthSecp256k1,
+        other => {
+            return Err(Error::unsupported_sdk_key(format!(
+                "unsupported private key type: {other}"
+            )))
+        }
+    };
+
+    let raw_key = |any: &Any| {
+        RawKey::decode(any.value.as_slice())
+            .map(|raw| raw.key)
+            .map_err(|e| Error::invalid_sdk_key_file(e.to_string()))
+    };
+
+    Ok(SdkKey {
+        algo,
+        private_key: raw_key(&priv_key)?,
+        public_key: raw_key(&pub_key)?,
+    })
+}
+
+/// An item of the keyring, in the JSON encoding of the SDK.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    #[serde(rename = "Key")]
+    pub key: String,
+    #[serde(rename = "Data", with = "base64_std")]
+    pub data: Vec<u8>,
+    #[serde(rename = "Label", default)]
+    pub label: String,
+    #[serde(rename = "Description", default)]
+    pub description: String,
+    #[serde(rename = "KeychainNotTrustApplication", default)]
+    pub keychain_not_trust_application: bool,
+    #[serde(rename = "KeychainNotSynchronizable", default)]
+    pub keychain_not_synchronizable: bool,
+}
+
+impl Item {
+    pub fn new(key: String, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            data,
+            label: String::new(),
+            description: String::new(),
+            keychain_not_trust_application: false,
+            keychain_not_synchronizable: false,
+        }
+    }
+
+    pub fn encrypt(&self, passphrase: &str, iterations: u32) -> Result<String, Error> {
+        let plaintext = serde_json::to_vec(self).map_err(Error::encode)?;
+        encrypt(&plaintext, passphrase, iterations)
+    }
+
+    pub fn decrypt(jwe: &str, passphrase: &str) -> Result<Self, Error> {
+        let plaintext = decrypt(jwe, passphrase)?;
+        serde_json::from_slice(&plaintext).map_err(Error::encode)
+    }
+}
+
+/// The name of the file of the given item key, escaped as by the SDK.
+pub fn filename_escape(key: &str) -> String {
+    key.replace('%', "%25").replace('/', "%2F")
+}
+
+/// The item key of the given file name, see [`filename_escape`].
+pub fn filename_unescape(filename: &str) -> String {
+    filename.replace("%2F", "/").replace("%25", "%")
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    enc: String,
+    p2c: u32,
+    p2s: String,
+}
+
+/// Encrypt the given plaintext as a JWE in compact serialization.
+fn encrypt(plaintext: &[u8], passphrase: &str, iterations: u32) -> Result<String, Error> {
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut cek = [0u8; CEK_LENGTH];
+    OsRng.fill_bytes(&mut cek);
+
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+
+    let header = Header {
+        alg: ALG.to_string(),
+        enc: ENC.to_string(),
+        p2c: iterations,
+        p2s: base64url(&salt),
+    };
+    let header = base64url(&serde_json::to_vec(&header).map_err(Error::encode)?);
+
+    let kek = derive_kek(passphrase, &salt, iterations);
+    let encrypted_key = aes_key_wrap(&kek, &cek);
+
+    let mut ciphertext = Aes256Gcm::new_from_slice(&cek)
+        .map_err(|_| Error::encryption("invalid content encryption key".to_string()))?
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: header.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::encryption("failed to encrypt key".to_string()))?;
+
+    let tag = ciphertext.split_off(ciphertext.len() - 16);
+
+    Ok([
+        header,
+        base64url(&encrypted_key),
+        base64url(&nonce),
+        base64url(&ciphertext),
+        base64url(&tag),
+    ]
+    .join("."))
+}
+
+/// Decrypt the given JWE in compact serialization.
+fn decrypt(jwe: &str, passphrase: &str) -> Result<Vec<u8>, Error> {
+    let parts: Vec<&str> = jwe.trim().split('.').collect();
+
+    let [header_b64, encrypted_key, nonce, ciphertext, tag] = parts[..] else {
+        return Err(Error::invalid_sdk_key_file(
+            "not a JWE in compact serialization".to_string(),
+        ));
+    };
+
+    let header: Header = serde_json::from_slice(&from_base64url(header_b64)?)
+        .map_err(|e| Error::invalid_sdk_key_file(e.to_string()))?;
+
+    if header.alg != ALG || header.enc != ENC {
+        return Err(Error::invalid_sdk_key_file(format!(
+            "unsupported encryption algorithms: {}, {}",
+            header.alg, header.enc
+        )));
+    }
+
+    let kek = derive_kek(passphrase, &from_base64url(&header.p2s)?, header.p2c);
+    let cek = aes_key_unwrap(&kek, &from_base64url(encrypted_key)?)?;
+
+    let nonce = from_base64url(nonce)?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(Error::invalid_sdk_key_file("invalid nonce".to_string()));
+    }
+
+    let mut msg = from_base64url(ciphertext)?;
+    msg.extend(from_base64url(tag)?);
+
+    Aes256Gcm::new_from_slice(&cek)
+        .map_err(|_| Error::decryption_failed())?
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &msg,
+                aad: header_b64.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::decryption_failed())
+}
+
+/// Derive the key encryption key from the passphrase, as specified by RFC 7518, section 4.8.
+fn derive_kek(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; KEK_LENGTH] {
+    let salt_input = [ALG.as_bytes(), &[0], salt].concat();
+
+    let mut kek = [0u8; KEK_LENGTH];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), &salt_input, iterations, &mut kek);
+
+    kek
+}
+
+const KEY_WRAP_IV: [u8; 8] = [0xA6; 8];
+
+/// Wrap the given key with AES-128, as specified by RFC 3394.
+fn aes_key_wrap(kek: &[u8; KEK_LENGTH], key: &[u8]) -> Vec<u8> {
+    let cipher = Aes128::new(GenericArray::from_slice(kek));
+
+    let n = key.len() / 8;
+    let mut a = KEY_WRAP_IV;
+    let mut r: Vec<[u8; 8]> = key
+        .chunks_exact(8)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect();
+
+    for j in 0..6 {
+        for (i, r_i) in r.iter_mut().enumerate() {
+            let mut block = GenericArray::clone_from_slice(&[a, *r_i].concat());
+            cipher.encrypt_block(&mut block);
+
+            let t = ((n * j) + i + 1) as u64;
+            a = xor(block[..8].try_into().unwrap(), t);
+            r_i.copy_from_slice(&block[8..]);
+        }
+    }
+
+    [a].iter().chain(r.iter()).flatten().copied().collect()
+}
+
+/// Unwrap the given key with AES-128, as specified by RFC 3394.
+fn aes_key_unwrap(kek: &[u8; KEK_LENGTH], wrapped: &[u8]) -> Result<Vec<u8>, Error> {
+    if wrapped.len() % 8 != 0 || wrapped.len() < 24 {
+        return Err(Error::invalid_sdk_key_file(
+            "invalid encrypted key".to_string(),
+        ));
+    }
+
+    let cipher = Aes128::new(GenericArray::from_slice(kek));
+
+    let n = wrapped.len() / 8 - 1;
+    let mut a: [u8; 8] = wrapped[..8].try_into().unwrap();
+    let mut r: Vec<[u8; 8]> = wrapped[8..]
+        .chunks_exact(8)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect();
+
+    for j in (0..6).rev() {
+        for (i, r_i) in r.iter_mut().enumerate().rev() {
+            let t = ((n * j) + i + 1) as u64;
+
+            let mut block = GenericArray::clone_from_slice(&[xor(a, t), *r_i].concat());
+            cipher.decrypt_block(&mut block);
+
+            a = block[..8].try_into().unwrap();
+            r_i.copy_from_slice(&block[8..]);
+        }
+    }
+
+    // A wrong passphrase derives a wrong key encryption key, which fails the integrity check
+    if a != KEY_WRAP_IV {
+        return Err(Error::decryption_failed());
+    }
+
+    Ok(r.concat())
+}
+
+fn xor(a: [u8; 8], t: u64) -> [u8; 8] {
+    (u64::from_be_bytes(a) ^ t).to_be_bytes()
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+fn from_base64url(s: &str) -> Result<Vec<u8>, Error> {
+    base64::decode_config(s, base64::URL_SAFE_NO_PAD)
+        .map_err(|e| Error::invalid_sdk_key_file(e.to_string()))
+}
+
+/// Go encodes byte slices in JSON as padded standard base64.
+mod base64_std {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        base64::decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aes_key_wrap_rfc3394_vector() {
+        // RFC 3394, section 4.1: wrap 128 bits of key data with a 128-bit KEK
+        let kek: [u8; 16] = hex::decode("000102030405060708090A0B0C0D0E0F")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let key = hex::decode("00112233445566778899AABBCCDDEEFF").unwrap();
+        let wrapped = hex::decode("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5").unwrap();
+
+        assert_eq!(aes_key_wrap(&kek, &key), wrapped);
+        assert_eq!(aes_key_unwrap(&kek, &wrapped).unwrap(), key);
+
+        let mut corrupted = wrapped;
+        corrupted[0] ^= 1;
+        assert!(aes_key_unwrap(&kek, &corrupted).is_err());
+    }
+
+    #[test]
+    fn pbes2_rfc7517_vector() {
+        // RFC 7517, appendix C: key encryption of a JWK with PBES2-HS256+A128KW
+        let kek = derive_kek(
+            "Thus from my lips, by yours, my sin is purged.",
+            &from_base64url("2WCTcJZ1Rvd_CJuJripQ1w").unwrap(),
+            4096,
+        );
+        assert_eq!(
+            kek,
+            [110, 171, 169, 92, 129, 92, 109, 117, 233, 242, 116, 233, 170, 14, 24, 75]
+        );
+
+        let cek = [
+            111, 27, 25, 52, 66, 29, 20, 78, 92, 176, 56, 240, 65, 208, 82, 112, 161, 131, 36, 55,
+            202, 236, 185, 172, 129, 23, 153, 194, 195, 48, 253, 182,
+        ];
+        let encrypted_key =
+            from_base64url("TrqXOwuNUfDV9VPTNbyGvEJ9JMjefAVn-TR1uIxR9p6hsRQh9Tk7BA").unwrap();
+
+        assert_eq!(aes_key_wrap(&kek, &cek), encrypted_key);
+        assert_eq!(aes_key_unwrap(&kek, &encrypted_key).unwrap(), cek);
+    }
+
+    #[test]
+    fn jwe_round_trip() {
+        let jwe = encrypt(b"secret", "passphrase", 16).unwrap();
+
+        assert_eq!(jwe.split('.').count(), 5);
+        assert_eq!(decrypt(&jwe, "passphrase").unwrap(), b"secret");
+        assert!(decrypt(&jwe, "wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let key = SdkKey {
+            algo: SdkKeyAlgo::EthSecp256k1,
+            private_key: vec![1; 32],
+            public_key: vec![2; 33],
+        };
+
+        let record = encode_record("relayer", &key);
+        let decoded = decode_record(&record).unwrap();
+
+        assert!(decoded == key);
+    }
+
+    #[test]
+    fn item_json_encoding() {
+        let item = Item::new("relayer.info".to_string(), vec![0, 1, 2]);
+        let json = serde_json::to_string(&item).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"Key":"relayer.info","Data":"AAEC","Label":"","Description":"","KeychainNotTrustApplication":false,"KeychainNotSynchronizable":false}"#
+        );
+        assert_eq!(serde_json::from_str::<Item>(&json).unwrap(), item);
+    }
+}
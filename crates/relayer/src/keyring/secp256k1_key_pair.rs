@@ -18,7 +18,8 @@ use super::{
     errors::Error,
     key_utils::{decode_bech32, encode_bech32, keccak256_hash},
     pub_key::EncodedPubKey,
-    KeyFile, KeyType, SigningKeyPair,
+    remote_signer::{RemoteSigner, SignHash},
+    KeyFile, KeyType, SdkKey, SdkKeyAlgo, SigningKeyPair,
 };
 use crate::config::AddressType;
 
@@ -135,13 +136,29 @@ fn encode_address(account_prefix: &str, address: &[u8]) -> Result<String, Error>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(try_from = "VersionedKeyPair")]
 pub struct Secp256k1KeyPair {
-    private_key: SecretKey,
+    #[serde(flatten)]
+    signer: Secp256k1Signer,
     pub public_key: PublicKey,
     address: [u8; 20],
     address_type: Secp256k1AddressType,
     account: String,
 }
 
+/// Where the private key of a [`Secp256k1KeyPair`] lives.
+///
+/// Flattened into the key pair when serialized, so that the format of
+/// local keys is the same as `KeyPairV2`.
+#[derive(Clone, Debug, Serialize)]
+enum Secp256k1Signer {
+    /// The private key is held by the relayer
+    #[serde(rename = "private_key")]
+    Local(SecretKey),
+
+    /// The private key is held by a remote signer
+    #[serde(rename = "remote_signer")]
+    Remote(RemoteSigner),
+}
+
 // The old `KeyEntry` type
 #[derive(Debug, Deserialize)]
 struct KeyPairV1 {
@@ -160,6 +177,16 @@ struct KeyPairV2 {
     account: String,
 }
 
+// A key pair whose private key is held by a remote signer
+#[derive(Debug, Deserialize)]
+struct RemoteKeyPair {
+    remote_signer: RemoteSigner,
+    public_key: PublicKey,
+    address: [u8; 20],
+    address_type: Secp256k1AddressType,
+    account: String,
+}
+
 // Note: Since this uses Serde's untagged enums, the serialized formats between
 // versions must be incompatible with each other.
 #[derive(Debug, Deserialize)]
@@ -167,6 +194,7 @@ struct KeyPairV2 {
 enum VersionedKeyPair {
     V1(KeyPairV1),
     V2(KeyPairV2),
+    Remote(RemoteKeyPair),
 }
 
 impl TryFrom<VersionedKeyPair> for Secp256k1KeyPair {
@@ -185,7 +213,7 @@ impl TryFrom<VersionedKeyPair> for Secp256k1KeyPair {
                     .map_err(|address_bytes| Error::invalid_address_length(address_bytes, 20))?;
                 let address_type = Secp256k1AddressType::derive(&public_key.public_key, &address)?;
                 Ok(Self {
-                    private_key: private_key.private_key,
+                    signer: Secp256k1Signer::Local(private_key.private_key),
                     public_key: public_key.public_key,
                     address,
                     address_type,
@@ -199,7 +227,20 @@ impl TryFrom<VersionedKeyPair> for Secp256k1KeyPair {
                 address_type,
                 account,
             }) => Ok(Self {
-                private_key,
+                signer: Secp256k1Signer::Local(private_key),
+                public_key,
+                address,
+                address_type,
+                account,
+            }),
+            VersionedKeyPair::Remote(RemoteKeyPair {
+                remote_signer,
+                public_key,
+                address,
+                address_type,
+                account,
+            }) => Ok(Self {
+                signer: Secp256k1Signer::Remote(remote_signer),
                 public_key,
                 address,
                 address_type,
@@ -222,13 +263,42 @@ impl Secp256k1KeyPair {
         let account = encode_address(account_prefix, &address)?;
 
         Ok(Self {
-            private_key: private_key.private_key,
+            signer: Secp256k1Signer::Local(private_key.private_key),
             public_key: public_key.public_key,
             address,
             address_type,
             account,
         })
     }
+
+    /// Build a key pair whose private key is held by the given remote signer,
+    /// fetching its public key from the signer.
+    pub fn from_remote_signer(
+        remote_signer: RemoteSigner,
+        address_type: &AddressType,
+        account_prefix: &str,
+    ) -> Result<Self, Error> {
+        let address_type = address_type.try_into()?;
+        let public_key = remote_signer.public_key()?;
+        let address = get_address(&public_key, address_type);
+        let account = encode_address(account_prefix, &address)?;
+
+        Ok(Self {
+            signer: Secp256k1Signer::Remote(remote_signer),
+            public_key,
+            address,
+            address_type,
+            account,
+        })
+    }
+
+    /// The remote signer holding the private key of this key pair, if any.
+    pub fn remote_signer(&self) -> Option<&RemoteSigner> {
+        match &self.signer {
+            Secp256k1Signer::Local(_) => None,
+            Secp256k1Signer::Remote(remote_signer) => Some(remote_signer),
+        }
+    }
 }
 
 impl SigningKeyPair for Secp256k1KeyPair {
@@ -268,7 +338,7 @@ impl SigningKeyPair for Secp256k1KeyPair {
         let address_type = Secp256k1AddressType::derive(&derived_pubkey.public_key, &address)?;
 
         Ok(Self {
-            private_key: private_key.private_key,
+            signer: Secp256k1Signer::Local(private_key.private_key),
             public_key: derived_pubkey.public_key,
             address,
             address_type,
@@ -285,6 +355,57 @@ impl SigningKeyPair for Secp256k1KeyPair {
         Self::from_mnemonic_internal(mnemonic, hd_path, address_type.try_into()?, account_prefix)
     }
 
+    fn to_sdk_key(&self) -> Result<SdkKey, Error> {
+        let private_key = match &self.signer {
+            Secp256k1Signer::Local(private_key) => private_key,
+            Secp256k1Signer::Remote(_) => {
+                return Err(Error::unsupported_sdk_key(
+                    "the private key is held by a remote signer".to_string(),
+                ))
+            }
+        };
+
+        let algo = match self.address_type {
+            Secp256k1AddressType::Cosmos => SdkKeyAlgo::Secp256k1,
+            Secp256k1AddressType::Ethermint => SdkKeyAlgo::EthSecp256k1,
+        };
+
+        Ok(SdkKey {
+            algo,
+            private_key: private_key.secret_bytes().to_vec(),
+            public_key: self.public_key.serialize().to_vec(),
+        })
+    }
+
+    fn from_sdk_key(key: SdkKey, account_prefix: &str) -> Result<Self, Error> {
+        let private_key = SecretKey::from_slice(&key.private_key)
+            .map_err(|e| Error::invalid_sdk_key_file(e.to_string()))?;
+        let public_key = PublicKey::from_secret_key(&Secp256k1::signing_only(), &private_key);
+
+        // Ensure that the public key in the record matches the private key
+        if public_key.serialize().as_slice() != key.public_key.as_slice() {
+            return Err(Error::public_key_mismatch(
+                key.public_key,
+                public_key.serialize().to_vec(),
+            ));
+        }
+
+        let address_type = match key.algo {
+            SdkKeyAlgo::Secp256k1 => Secp256k1AddressType::Cosmos,
+            SdkKeyAlgo::EthSecp256k1 => Secp256k1AddressType::Ethermint,
+        };
+        let address = get_address(&public_key, address_type);
+        let account = encode_address(account_prefix, &address)?;
+
+        Ok(Self {
+            signer: Secp256k1Signer::Local(private_key),
+            public_key,
+            address,
+            address_type,
+            account,
+        })
+    }
+
     fn account(&self) -> String {
         self.account.to_owned()
     }
@@ -297,22 +418,75 @@ impl SigningKeyPair for Secp256k1KeyPair {
     // - https://github.com/evmos/ethermint/blob/main/crypto/ethsecp256k1/ethsecp256k1.go
     // - informalsystems/hermes#2863.
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
-        let hashed_message: GenericArray<u8, U32> = match self.address_type {
-            Secp256k1AddressType::Ethermint => keccak256_hash(message).into(),
-            Secp256k1AddressType::Cosmos => Sha256::digest(message),
+        let (hashed_message, hash): (GenericArray<u8, U32>, _) = match self.address_type {
+            Secp256k1AddressType::Ethermint => {
+                (keccak256_hash(message).into(), SignHash::Keccak256)
+            }
+            Secp256k1AddressType::Cosmos => (Sha256::digest(message), SignHash::Sha256),
         };
 
-        // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`,
-        // so `unwrap` is safe.
-        let message = Message::from_slice(&hashed_message).unwrap();
+        match &self.signer {
+            Secp256k1Signer::Local(private_key) => {
+                // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`,
+                // so `unwrap` is safe.
+                let message = Message::from_slice(&hashed_message).unwrap();
 
-        Ok(Secp256k1::signing_only()
-            .sign_ecdsa(&message, &self.private_key)
-            .serialize_compact()
-            .to_vec())
+                Ok(Secp256k1::signing_only()
+                    .sign_ecdsa(&message, private_key)
+                    .serialize_compact()
+                    .to_vec())
+            }
+            Secp256k1Signer::Remote(remote_signer) => {
+                remote_signer.sign(message, &hashed_message, hash, &self.public_key)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn remote_key_pair_round_trip() {
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let local =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos")
+                .unwrap();
+
+        let remote_signer =
+            RemoteSigner::new("http://127.0.0.1:3500/".to_string(), "relayer".to_string());
+
+        let remote = Secp256k1KeyPair {
+            signer: Secp256k1Signer::Remote(remote_signer.clone()),
+            ..local.clone()
+        };
+
+        let serialized = serde_json::to_string(&remote).unwrap();
+        assert!(serialized.contains("remote_signer"));
+        assert!(!serialized.contains("private_key"));
+
+        let versioned: VersionedKeyPair = serde_json::from_str(&serialized).unwrap();
+        assert!(matches!(versioned, VersionedKeyPair::Remote(_)));
+
+        let restored = Secp256k1KeyPair::try_from(versioned).unwrap();
+        assert_eq!(restored.remote_signer(), Some(&remote_signer));
+        assert_eq!(restored.public_key, local.public_key);
+        assert_eq!(restored.address, local.address);
+        assert_eq!(restored.address_type, local.address_type);
+        assert_eq!(restored.account, local.account);
+
+        // Local key pairs still deserialize as `KeyPairV2`
+        let serialized = serde_json::to_string(&local).unwrap();
+        let versioned: VersionedKeyPair = serde_json::from_str(&serialized).unwrap();
+        assert!(matches!(versioned, VersionedKeyPair::V2(_)));
+    }
+}
@@ -3,7 +3,7 @@ use core::any::Any;
 use hdpath::StandardHDPath;
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::{errors::Error, KeyFile, KeyType};
+use super::{errors::Error, KeyFile, KeyType, SdkKey};
 use crate::config::AddressType;
 
 pub trait SigningKeyPair {
@@ -30,6 +30,25 @@ pub trait SigningKeyPair {
     where
         Self: Sized;
 
+    /// The key as stored in the `file` keyring backend of the Cosmos SDK.
+    fn to_sdk_key(&self) -> Result<SdkKey, Error> {
+        Err(Error::unsupported_sdk_key(format!(
+            "keys of type {} are not supported",
+            Self::KEY_TYPE
+        )))
+    }
+
+    /// Restore the key pair from a key stored in the `file` keyring backend of the Cosmos SDK.
+    fn from_sdk_key(_key: SdkKey, _account_prefix: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Err(Error::unsupported_sdk_key(format!(
+            "keys of type {} are not supported",
+            Self::KEY_TYPE
+        )))
+    }
+
     fn account(&self) -> String;
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
 
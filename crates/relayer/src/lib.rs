@@ -44,6 +44,7 @@ pub mod registry;
 pub mod rest;
 pub mod sdk_error;
 pub mod spawn;
+pub mod store;
 pub mod supervisor;
 pub mod telemetry;
 pub mod transfer;
@@ -1,10 +1,16 @@
 use itertools::Itertools;
 
+use tendermint::block::signed_header::SignedHeader;
 use tendermint_light_client::{
-    components::{self, io::AtHeight},
+    components::{
+        self,
+        io::{AtHeight, Io},
+    },
+    fork_detector::{Fork, ForkDetection, ForkDetector, ProdForkDetector},
     light_client::LightClient as TmLightClient,
     state::State as LightClientState,
     store::{memory::MemoryStore, LightStore},
+    supervisor::Instance,
 };
 use tendermint_light_client_verifier::operations;
 use tendermint_light_client_verifier::options::Options as TmOptions;
@@ -23,10 +29,14 @@ use ibc_relayer_types::{
     },
     downcast, Height as ICSHeight,
 };
-use tracing::trace;
+use tracing::{error, trace, warn};
 
 use crate::{
-    chain::cosmos::CosmosSdkChain, client_state::AnyClientState, config::ChainConfig, error::Error,
+    chain::cosmos::CosmosSdkChain,
+    client_state::AnyClientState,
+    config::ChainConfig,
+    error::Error,
+    event::stream::{self, StreamEvent},
     misbehaviour::MisbehaviourEvidence,
 };
 
@@ -34,6 +44,14 @@ use super::Verified;
 
 pub struct LightClient {
     chain_id: ChainId,
+    primary: rpc::Url,
+    peer_id: PeerId,
+    io: components::io::ProdIo,
+    witnesses: Vec<Witness>,
+}
+
+/// A node against which the blocks fetched from the primary node are cross-checked.
+struct Witness {
     peer_id: PeerId,
     io: components::io::ProdIo,
 }
@@ -46,6 +64,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         client_state: &AnyClientState,
     ) -> Result<Verified<TmHeader>, Error> {
         let Verified { target, supporting } = self.verify(trusted, target, client_state)?;
+        self.detect_forks(trusted, &target, client_state)?;
         let (target, supporting) = self.adjust_headers(trusted, target, supporting)?;
         Ok(Verified { target, supporting })
     }
@@ -92,10 +111,13 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
     }
 
     /// Given a client update event that includes the header used in a client update,
-    /// look for misbehaviour by fetching a header at same or latest height.
+    /// look for misbehaviour by rebuilding the verification trace from the trusted height
+    /// of the update header up to the same or latest height on the primary node, and by
+    /// checking the update header against every block of that trace.
     ///
-    /// ## TODO
-    /// - [ ] Return intermediate headers as well
+    /// This catches conflicting headers at the update height (fork), as well as headers
+    /// reached through skipping verification whose timestamp is not greater than the
+    /// one of a block at a lower height in the trace (BFT time violation).
     fn check_misbehaviour(
         &mut self,
         update: &UpdateClient,
@@ -143,8 +165,21 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         let Verified { target, supporting } =
             self.verify(trusted_height, target_height, client_state)?;
 
-        if !headers_compatible(&target.signed_header, &update_header.signed_header) {
-            let (witness, supporting) = self.adjust_headers(trusted_height, target, supporting)?;
+        // The full verification trace, sorted by ascending height and ending with the target
+        let mut trace = supporting;
+        trace.push(target);
+
+        if let Some(index) = find_conflicting_block(&update_header.signed_header, &trace) {
+            // Only the blocks below the conflicting one are needed to trust it
+            trace.truncate(index + 1);
+            let conflicting = trace.pop().expect("trace contains the conflicting block");
+
+            trace!(
+                trusted = %trusted_height, conflicting = %conflicting.height(),
+                "found block conflicting with update header at height {}", update_header.height()
+            );
+
+            let (witness, supporting) = self.adjust_headers(trusted_height, conflicting, trace)?;
 
             let misbehaviour = TmMisbehaviour {
                 client_id: update.client_id().clone(),
@@ -163,49 +198,217 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
     }
 }
 
+/// Returns the index of the first block in the given verification trace which conflicts
+/// with the given header, ie. which either has the same height but a different hash, or
+/// which violates the monotonicity of the BFT time with respect to the header.
+fn find_conflicting_block(header: &SignedHeader, trace: &[LightBlock]) -> Option<usize> {
+    trace
+        .iter()
+        .position(|block| !headers_compatible(&block.signed_header, header))
+}
+
+/// Check the given block, verified from the given trusted block, against each witness in turn.
+///
+/// A witness which cannot be reached does not prevent checking the others.
+fn check_witnesses(
+    chain_id: &ChainId,
+    target: &LightBlock,
+    trusted_block: &LightBlock,
+    witnesses: &[Instance],
+) -> Result<(), Error> {
+    let detector = ProdForkDetector::default();
+
+    let mut result = Ok(());
+
+    for witness in witnesses {
+        let forks = match detector.detect_forks(target, trusted_block, vec![witness]) {
+            Ok(ForkDetection::NotDetected) => continue,
+            Ok(ForkDetection::Detected(forks)) => forks,
+            Err(e) => {
+                warn!(
+                    chain = %chain_id, witness = %witness.light_client.peer,
+                    "failed to fetch the block at height {} from witness: {}", target.height(), e
+                );
+                continue;
+            }
+        };
+
+        for fork in forks {
+            match fork {
+                Fork::Forked { primary, witness } => {
+                    let height = ICSHeight::new(chain_id.version(), primary.height().into())
+                        .map_err(|_| Error::invalid_height_no_source())?;
+
+                    error!(
+                        chain = %chain_id, %height, witness = %witness.provider,
+                        "FORK DETECTED: witness disagrees with the primary node, refusing to use the header"
+                    );
+
+                    stream::publish_with(|| StreamEvent::ForkDetected {
+                        chain_id: chain_id.clone(),
+                        height,
+                        primary_hash: primary.signed_header.header.hash().to_string(),
+                        witness: witness.provider.to_string(),
+                        witness_hash: witness.signed_header.header.hash().to_string(),
+                    });
+
+                    result = Err(Error::light_client_fork_detected(
+                        chain_id.to_string(),
+                        height.revision_height(),
+                        witness.provider.to_string(),
+                    ));
+                }
+                Fork::Faulty(block, e) => {
+                    warn!(
+                        chain = %chain_id, witness = %block.provider,
+                        "witness is faulty at height {}: {}", block.height(), e
+                    );
+                }
+                Fork::Timeout(peer_id, e) => {
+                    warn!(
+                        chain = %chain_id, witness = %peer_id,
+                        "witness timed out: {}", e
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The verification options of the given client state.
+fn light_client_options(client_state: &AnyClientState) -> Result<TmOptions, Error> {
+    let client_state = downcast!(client_state => AnyClientState::Tendermint).ok_or_else(|| {
+        Error::client_type_mismatch(ClientType::Tendermint, client_state.client_type())
+    })?;
+
+    Ok(TmOptions {
+        trust_threshold: client_state
+            .trust_threshold
+            .try_into()
+            .map_err(Error::light_client_state)?,
+        trusting_period: client_state.trusting_period,
+        clock_drift: client_state.max_clock_drift,
+    })
+}
+
+/// A light client fetching its blocks from the given node.
+fn new_light_client(peer_id: PeerId, options: TmOptions, io: impl Io + 'static) -> TmLightClient {
+    TmLightClient::new(
+        peer_id,
+        options,
+        components::clock::SystemClock,
+        components::scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        operations::hasher::ProdHasher,
+        io,
+    )
+}
+
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let rpc_client = rpc::HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+    /// Create a light client for the chain with the given configuration,
+    /// whose primary node has the given peer id and listens at the given address.
+    pub fn from_config(
+        config: &ChainConfig,
+        address: &rpc::Url,
+        peer_id: PeerId,
+    ) -> Result<Self, Error> {
+        let rpc_client =
+            rpc::HttpClient::new(address.clone()).map_err(|e| Error::rpc(address.clone(), e))?;
 
         let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
 
         Ok(Self {
             chain_id: config.id.clone(),
+            primary: address.clone(),
             peer_id,
             io,
+            witnesses: Vec::new(),
         })
     }
 
-    fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
-        let clock = components::clock::SystemClock;
-        let hasher = operations::hasher::ProdHasher;
-        let verifier = ProdVerifier::default();
-        let scheduler = components::scheduler::basic_bisecting_schedule;
-
-        let client_state =
-            downcast!(client_state => AnyClientState::Tendermint).ok_or_else(|| {
-                Error::client_type_mismatch(ClientType::Tendermint, client_state.client_type())
-            })?;
+    /// The address of the primary node.
+    pub fn primary(&self) -> &rpc::Url {
+        &self.primary
+    }
 
-        let params = TmOptions {
-            trust_threshold: client_state
-                .trust_threshold
-                .try_into()
-                .map_err(Error::light_client_state)?,
-            trusting_period: client_state.trusting_period,
-            clock_drift: client_state.max_clock_drift,
-        };
+    /// Fetch the blocks from the node with the given peer id, listening at the given address.
+    pub fn set_primary(
+        &mut self,
+        config: &ChainConfig,
+        address: &rpc::Url,
+        peer_id: PeerId,
+    ) -> Result<(), Error> {
+        let rpc_client =
+            rpc::HttpClient::new(address.clone()).map_err(|e| Error::rpc(address.clone(), e))?;
+
+        self.io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
+        self.primary = address.clone();
+        self.peer_id = peer_id;
+
+        Ok(())
+    }
+
+    /// Cross-check the blocks verified by this light client against the
+    /// witness node with the given peer id, listening at the given address.
+    pub fn add_witness(
+        &mut self,
+        config: &ChainConfig,
+        address: &rpc::Url,
+        peer_id: PeerId,
+    ) -> Result<(), Error> {
+        let rpc_client =
+            rpc::HttpClient::new(address.clone()).map_err(|e| Error::rpc(address.clone(), e))?;
+
+        let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
+
+        self.witnesses.push(Witness { peer_id, io });
+
+        Ok(())
+    }
+
+    /// Check the given block, verified from the given trusted height, against the witnesses.
+    ///
+    /// Fails if any witness holds a different block at the same height which it can verify
+    /// from the same trusted block, in which case the primary node or the witness is on a
+    /// fork of the chain. Witnesses which are faulty or unresponsive are only reported.
+    fn detect_forks(
+        &self,
+        trusted: ICSHeight,
+        target: &LightBlock,
+        client_state: &AnyClientState,
+    ) -> Result<(), Error> {
+        if self.witnesses.is_empty() {
+            return Ok(());
+        }
+
+        crate::time!("light client detect_forks");
+
+        let trusted_height =
+            TMHeight::try_from(trusted.revision_height()).map_err(Error::invalid_height)?;
+        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height))?;
+
+        let options = light_client_options(client_state)?;
+
+        let instances = self
+            .witnesses
+            .iter()
+            .map(|witness| {
+                Instance::new(
+                    new_light_client(witness.peer_id, options, witness.io.clone()),
+                    LightClientState::new(MemoryStore::new()),
+                )
+            })
+            .collect_vec();
+
+        check_witnesses(&self.chain_id, target, &trusted_block, &instances)
+    }
+
+    fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
+        let options = light_client_options(client_state)?;
 
-        Ok(TmLightClient::new(
-            self.peer_id,
-            params,
-            clock,
-            scheduler,
-            verifier,
-            hasher,
-            self.io.clone(),
-        ))
+        Ok(new_light_client(self.peer_id, options, self.io.clone()))
     }
 
     fn prepare_state(&self, trusted: ICSHeight) -> Result<LightClientState, Error> {
@@ -221,8 +424,6 @@ impl LightClient {
     }
 
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
-        use tendermint_light_client::components::io::Io;
-
         self.io
             .fetch_light_block(height)
             .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))
@@ -293,3 +494,181 @@ impl LightClient {
         Ok((target_header, supporting_headers))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+    use std::collections::HashMap;
+
+    use tendermint::Time;
+    use tendermint_light_client::components::io::IoError;
+    use tendermint_light_client_verifier::types::TrustThreshold;
+    use tendermint_testgen::{
+        light_block::default_peer_id, Commit, Generator, Header, LightBlock as TestgenLightBlock,
+        Validator,
+    };
+
+    const CHAIN_ID: &str = "chain-0";
+
+    /// A block at the given height, produced the given number of seconds after the first one.
+    fn block(height: u64, secs: u64) -> LightBlock {
+        let time = (Time::unix_epoch() + Duration::from_secs(1_000_000 + secs)).unwrap();
+
+        let block = TestgenLightBlock::new_default_with_time_and_chain_id(
+            CHAIN_ID.to_string(),
+            time,
+            height,
+        )
+        .generate()
+        .unwrap();
+
+        LightBlock::new(
+            block.signed_header,
+            block.validators,
+            block.next_validators,
+            block.provider,
+        )
+    }
+
+    /// A block at the given height signed by other validators than the ones of [`block`].
+    fn block_of_other_validators(height: u64, secs: u64) -> LightBlock {
+        let time = (Time::unix_epoch() + Duration::from_secs(1_000_000 + secs)).unwrap();
+        let validators = [
+            Validator::new("3").voting_power(50),
+            Validator::new("4").voting_power(50),
+        ];
+
+        let header = Header::new(&validators)
+            .height(height)
+            .chain_id(CHAIN_ID)
+            .next_validators(&validators)
+            .time(time);
+        let commit = Commit::new(header.clone(), 1);
+
+        let block = TestgenLightBlock::new(header, commit)
+            .validators(&validators)
+            .next_validators(&validators)
+            .generate()
+            .unwrap();
+
+        LightBlock::new(
+            block.signed_header,
+            block.validators,
+            block.next_validators,
+            default_peer_id(),
+        )
+    }
+
+    /// A witness serving the given blocks, which fails to serve blocks at any other height.
+    fn witness(blocks: Vec<LightBlock>) -> Instance {
+        let blocks: HashMap<u64, LightBlock> = blocks
+            .into_iter()
+            .map(|block| (block.height().value(), block))
+            .collect();
+
+        let io = move |height: AtHeight| match height {
+            AtHeight::At(height) => blocks
+                .get(&height.value())
+                .cloned()
+                .ok_or_else(IoError::invalid_height),
+            AtHeight::Highest => Err(IoError::invalid_height()),
+        };
+
+        // The test blocks are old, so they must be trusted for long enough
+        let options = TmOptions {
+            trust_threshold: TrustThreshold::ONE_THIRD,
+            trusting_period: Duration::from_secs(100 * 365 * 24 * 3600),
+            clock_drift: Duration::from_secs(5),
+        };
+
+        Instance::new(
+            new_light_client(default_peer_id(), options, io),
+            LightClientState::new(MemoryStore::new()),
+        )
+    }
+
+    /// A verification trace of blocks produced every 10 seconds.
+    fn trace(heights: &[u64]) -> Vec<LightBlock> {
+        heights.iter().map(|&h| block(h, h * 10)).collect()
+    }
+
+    #[test]
+    fn no_conflict_with_compatible_header() {
+        let trace = trace(&[1, 5, 10]);
+
+        let header = block(10, 100).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), None);
+
+        // A header above the trace, produced after its blocks
+        let header = block(12, 120).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), None);
+    }
+
+    #[test]
+    fn conflict_at_same_height() {
+        let trace = trace(&[1, 5, 10]);
+
+        // Same height as the target, but a different block
+        let header = block(10, 101).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), Some(2));
+
+        // Same height as a supporting block
+        let header = block(5, 51).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), Some(1));
+    }
+
+    #[test]
+    fn conflict_with_bft_time() {
+        let trace = trace(&[1, 5, 10]);
+
+        // A header above the block at height 5, but not after it
+        let header = block(7, 50).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), Some(1));
+
+        // A header below the block at height 10, but not before it
+        let header = block(7, 100).signed_header;
+        assert_eq!(find_conflicting_block(&header, &trace), Some(2));
+    }
+
+    #[test]
+    fn witnesses_agreeing_with_primary() {
+        let chain_id = ChainId::from_string(CHAIN_ID);
+        let (trusted, target) = (block(1, 10), block(5, 50));
+
+        let witnesses = [witness(vec![block(1, 10), block(5, 50)])];
+        assert!(check_witnesses(&chain_id, &target, &trusted, &witnesses).is_ok());
+    }
+
+    #[test]
+    fn witness_on_a_fork() {
+        let chain_id = ChainId::from_string(CHAIN_ID);
+        let (trusted, target) = (block(1, 10), block(5, 50));
+
+        // The block of the witness at the target height is signed by the trusted validators
+        let witnesses = [
+            witness(vec![block(1, 10), block(5, 50)]),
+            witness(vec![block(1, 10), block(5, 51)]),
+        ];
+
+        let err = check_witnesses(&chain_id, &target, &trusted, &witnesses).unwrap_err();
+        assert!(err.to_string().contains("fork"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn faulty_and_unreachable_witnesses_are_skipped() {
+        let chain_id = ChainId::from_string(CHAIN_ID);
+        let (trusted, target) = (block(1, 10), block(5, 50));
+
+        let witnesses = [
+            // Serves a block which cannot be verified from the trusted block
+            witness(vec![block(1, 10), block_of_other_validators(5, 51)]),
+            // Serves no block at the target height
+            witness(vec![block(1, 10)]),
+            witness(vec![block(1, 10), block(5, 50)]),
+        ];
+
+        assert!(check_witnesses(&chain_id, &target, &trusted, &witnesses).is_ok());
+    }
+}
@@ -16,6 +16,7 @@ pub mod error;
 pub mod operational_data;
 
 mod packet_events;
+mod packet_fees;
 mod pending;
 mod relay_path;
 mod relay_sender;
@@ -79,6 +80,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
 
         if !a_channel.state_matches(&ChannelState::Open)
             && !a_channel.state_matches(&ChannelState::Closed)
+            && !a_channel.state().is_upgrading()
         {
             return Err(LinkError::invalid_channel_state(
                 a_channel_id.clone(),
@@ -2,6 +2,7 @@ use std::convert::TryInto;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use ibc_relayer_types::core::ics04_channel::channel::Order;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use tracing::{error_span, info};
 
@@ -166,6 +167,80 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         )
     }
 
+    /// Implements the `packet-timeout` CLI
+    ///
+    /// Times out, on the source chain, all the packets sent on the channel which
+    /// were not received on the destination chain before their timeout expired.
+    pub fn relay_timeout_packet_messages(&self) -> Result<Vec<IbcEvent>, LinkError> {
+        let _span = error_span!(
+            "relay_timeout_packet_messages",
+            src_chain = %self.a_to_b.src_chain().id(),
+            src_port = %self.a_to_b.src_port_id(),
+            src_channel = %self.a_to_b.src_channel_id(),
+            dst_chain = %self.a_to_b.dst_chain().id(),
+        )
+        .entered();
+
+        // Find the sequence numbers of unreceived packets, among which are the timed out ones
+        let (sequences, src_response_height) = unreceived_packets(
+            self.a_to_b.dst_chain(),
+            self.a_to_b.src_chain(),
+            &self.a_to_b.path_id,
+        )
+        .map_err(LinkError::supervisor)?;
+
+        if sequences.is_empty() {
+            return Ok(vec![]);
+        }
+
+        info!(
+            "{} unreceived packets found: {} ",
+            sequences.len(),
+            PrettySlice(&sequences)
+        );
+
+        let event_chunks = query_packet_events_with(
+            &sequences,
+            Qualified::SmallerEqual(src_response_height),
+            self.a_to_b.src_chain(),
+            &self.a_to_b.path_id,
+            query_send_packet_events,
+        );
+
+        let tracking_id = TrackingId::new_static("packet-timeout");
+        let mut results = vec![];
+
+        for event_chunk in event_chunks {
+            let tracked_events = TrackedEvents::new(event_chunk, tracking_id);
+
+            let odata = match self
+                .a_to_b
+                .generate_timeout_operational_data(tracked_events)?
+            {
+                Some(odata) => odata,
+                None => continue,
+            };
+
+            // Block until the connection delay, if any, has elapsed
+            let odata = wait_for_conn_delay(
+                odata,
+                &|| self.a_to_b.src_time_latest(),
+                &|| self.a_to_b.src_max_block_time(),
+                &|| self.a_to_b.src_latest_height(),
+            )?;
+
+            self.a_to_b
+                .relay_and_accumulate_results(vec![odata], &mut results)?;
+
+            // On ordered channels, the channel is closed by the first timeout
+            if self.a_to_b.channel().ordering == Order::Ordered {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
     fn relay_packet_messages<QueryFn>(
         &self,
         sequences: Vec<Sequence>,
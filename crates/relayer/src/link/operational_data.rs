@@ -1,3 +1,5 @@
+#[cfg(feature = "telemetry")]
+use alloc::collections::BTreeMap;
 use core::fmt::{Display, Error as FmtError, Formatter};
 use std::ops::Add;
 use std::time::{Duration, Instant};
@@ -6,6 +8,8 @@ use ibc_proto::google::protobuf::Any;
 use tracing::{debug, info};
 
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+#[cfg(feature = "telemetry")]
+use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
@@ -98,6 +102,10 @@ pub struct OperationalData {
     pub tracking_id: TrackingId,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
+    /// The time of the blocks including the SendPacket events of the batch, by height,
+    /// from which the end-to-end latency of relaying their packets is measured.
+    #[cfg(feature = "telemetry")]
+    pub send_packet_times: BTreeMap<Height, Timestamp>,
 }
 
 impl OperationalData {
@@ -119,6 +127,8 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            #[cfg(feature = "telemetry")]
+            send_packet_times: BTreeMap::new(),
         }
     }
 
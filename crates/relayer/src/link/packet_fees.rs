@@ -0,0 +1,183 @@
+use alloc::collections::BTreeMap as HashMap;
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::sync::RwLock;
+use std::time::Instant;
+
+use ibc_proto::google::protobuf::Any;
+use tracing::debug;
+
+use ibc_relayer_types::applications::ics29_fee::packet_fee::PacketFee;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::chain::handle::ChainHandle;
+use crate::config::fee_filter::FeeFilter;
+use crate::link::error::LinkError;
+use crate::telemetry;
+
+/// The interval after which the cached fees of the packets sent on a channel are fetched again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The ICS-29 fees escrowed for the packets sent on a channel,
+/// checked against the fee filter of the chain they were sent from.
+pub struct IncentivizedPackets {
+    chain_id: ChainId,
+    #[cfg(feature = "telemetry")]
+    counterparty_chain_id: ChainId,
+    port_id: PortId,
+    channel_id: ChannelId,
+    fee_filter: FeeFilter,
+    packet_fees: HashMap<Sequence, Vec<PacketFee>>,
+}
+
+impl IncentivizedPackets {
+    /// Fetch the fees escrowed on `chain` for the packets sent on the given port and channel.
+    /// Returns `None` if there is no fee filter configured for `chain`,
+    /// in which case all packets are relayed regardless of their fees.
+    pub fn fetch<Chain: ChainHandle>(
+        chain: &Chain,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_chain_id: &ChainId,
+    ) -> Result<Option<Self>, LinkError> {
+        let fee_filter = match chain.config().map_err(LinkError::relayer)?.fee_filter {
+            Some(fee_filter) => fee_filter,
+            None => return Ok(None),
+        };
+
+        let packet_fees = chain
+            .query_incentivized_packets(channel_id.clone(), port_id.clone())
+            .map_err(|e| LinkError::query(chain.id(), e))?
+            .into_iter()
+            .map(|packet| (packet.packet_id.sequence, packet.packet_fees))
+            .collect();
+
+        #[cfg(not(feature = "telemetry"))]
+        let _ = counterparty_chain_id;
+
+        Ok(Some(Self {
+            chain_id: chain.id(),
+            #[cfg(feature = "telemetry")]
+            counterparty_chain_id: counterparty_chain_id.clone(),
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            fee_filter,
+            packet_fees,
+        }))
+    }
+
+    /// Returns the given `MsgRecvPacket` for the packet with the given sequence
+    /// if the packet pays enough receive, acknowledgement and timeout fees, `None` otherwise.
+    ///
+    /// Only the `MsgRecvPacket` of a packet is filtered: its acknowledgement
+    /// and timeout are relayed regardless of its fees.
+    pub fn filter_recv(&self, sequence: Sequence, msg: Option<Any>) -> Option<Any> {
+        let msg = msg?;
+
+        let packet_fees = self.packet_fees.get(&sequence).map(Vec::as_slice);
+
+        if self
+            .fee_filter
+            .is_allowed(&self.port_id, &self.channel_id, packet_fees)
+        {
+            return Some(msg);
+        }
+
+        debug!(
+            chain = %self.chain_id,
+            port = %self.port_id,
+            channel = %self.channel_id,
+            %sequence,
+            "packet does not pay the minimum fees of the fee filter, skipping it"
+        );
+
+        telemetry!(
+            skipped_unincentivized,
+            &self.chain_id,
+            &self.channel_id,
+            &self.port_id,
+            &self.counterparty_chain_id
+        );
+
+        None
+    }
+}
+
+/// The [`IncentivizedPackets`] of a channel, fetched at most once per [`REFRESH_INTERVAL`]
+/// for the packets up to the highest sequence they were fetched for.
+#[derive(Default)]
+pub struct IncentivizedPacketsCache {
+    cached: RwLock<Option<CachedPackets>>,
+}
+
+struct CachedPackets {
+    fetched_at: Instant,
+    max_sequence: Sequence,
+    packets: Option<Arc<IncentivizedPackets>>,
+}
+
+impl CachedPackets {
+    /// Whether the fees cached at `fetched_at` can be used at `now`, for packets
+    /// up to the given sequence. The fees of a packet are escrowed at the latest
+    /// in the transaction sending it, so the fees fetched after a packet was sent
+    /// include its fees, but not those of the packets sent afterwards.
+    fn is_fresh(&self, now: Instant, max_sequence: Sequence) -> bool {
+        max_sequence <= self.max_sequence
+            && now.saturating_duration_since(self.fetched_at) < REFRESH_INTERVAL
+    }
+}
+
+impl IncentivizedPacketsCache {
+    /// Returns the fees escrowed on `chain` for the packets sent on the given port and channel,
+    /// up to the given sequence, fetching them again if the cached ones are stale.
+    pub fn get_or_fetch<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_chain_id: &ChainId,
+        max_sequence: Sequence,
+    ) -> Result<Option<Arc<IncentivizedPackets>>, LinkError> {
+        let now = Instant::now();
+
+        if let Some(cached) = self.cached.read().unwrap().as_ref() {
+            if cached.is_fresh(now, max_sequence) {
+                return Ok(cached.packets.clone());
+            }
+        }
+
+        let packets =
+            IncentivizedPackets::fetch(chain, port_id, channel_id, counterparty_chain_id)?
+                .map(Arc::new);
+
+        *self.cached.write().unwrap() = Some(CachedPackets {
+            fetched_at: now,
+            max_sequence,
+            packets: packets.clone(),
+        });
+
+        Ok(packets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_packets_freshness() {
+        let fetched_at = Instant::now();
+
+        let cached = CachedPackets {
+            fetched_at,
+            max_sequence: Sequence::from(10),
+            packets: None,
+        };
+
+        assert!(cached.is_fresh(fetched_at, Sequence::from(1)));
+        assert!(cached.is_fresh(fetched_at, Sequence::from(10)));
+        assert!(!cached.is_fresh(fetched_at, Sequence::from(11)));
+        assert!(!cached.is_fresh(fetched_at + REFRESH_INTERVAL, Sequence::from(1)));
+    }
+}
@@ -2,15 +2,19 @@ use core::iter::Iterator;
 use core::time::Duration;
 use std::time::Instant;
 
-use tracing::{debug, error, trace, trace_span};
+use tendermint::abci::Code;
+use tracing::{debug, error, info, trace, trace_span, warn};
 
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 
-use crate::chain::requests::{QueryTxHash, QueryTxRequest};
+use crate::chain::endpoint::TxOutcome;
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
+use crate::event::stream::{self, StreamEvent};
 use crate::link::{error::LinkError, RelayPath};
+use crate::sdk_error::{sdk_error_from_tx_result, sdk_error_from_tx_sync_error_code};
+use crate::store::{PathEnd, Store, StoredTxs};
 use crate::telemetry;
 use crate::util::queue::Queue;
 use crate::{
@@ -20,6 +24,15 @@ use crate::{
 
 pub const TIMEOUT: Duration = Duration::from_secs(300);
 
+/// The IBC events emitted by the given transactions, in order.
+fn events_of(outcomes: Vec<TxOutcome>) -> Vec<IbcEvent> {
+    outcomes
+        .into_iter()
+        .flat_map(|outcome| outcome.events)
+        .map(|ev| ev.event)
+        .collect()
+}
+
 /// A wrapper over an [`OperationalData`] that is pending.
 /// Additionally holds all the necessary information
 /// to query for confirmations:
@@ -49,6 +62,10 @@ pub struct PendingTxs<Chain> {
     pub port_id: PortId,
     pub counterparty_chain_id: ChainId,
     pub pending_queue: Queue<PendingData>,
+    /// The transactions submitted before the relayer restarted, restored
+    /// from the store, whose confirmation is still to be checked.
+    pub recovered_queue: Queue<StoredTxs>,
+    store: Option<Store>,
 }
 
 impl<Chain> PendingTxs<Chain> {
@@ -64,6 +81,8 @@ impl<Chain> PendingTxs<Chain> {
             port_id,
             counterparty_chain_id,
             pending_queue: Queue::new(),
+            recovered_queue: Queue::new(),
+            store: None,
         }
     }
 }
@@ -73,6 +92,103 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.chain.id()
     }
 
+    fn path_end(&self) -> PathEnd {
+        PathEnd {
+            chain_id: self.chain_id(),
+            port_id: self.port_id.clone(),
+            channel_id: self.channel_id.clone(),
+            counterparty_chain_id: self.counterparty_chain_id.clone(),
+        }
+    }
+
+    /// Record the pending transactions in the given store, and resume tracking
+    /// the confirmation of the ones recorded there before the relayer restarted.
+    pub fn set_store(&mut self, store: Store) {
+        let recovered = store.pending_txs(&self.path_end());
+
+        if !recovered.is_empty() {
+            info!(
+                chain = %self.chain_id(),
+                counterparty_chain = %self.counterparty_chain_id,
+                port = %self.port_id,
+                channel = %self.channel_id,
+                "resuming confirmation of {} batches of transactions submitted before restart",
+                recovered.len(),
+            );
+        }
+
+        self.recovered_queue.replace(recovered.into());
+        self.store = Some(store);
+    }
+
+    /// Returns true if some transactions submitted before the relayer
+    /// restarted are still to be confirmed, false otherwise.
+    pub fn has_recovered_txs(&self) -> bool {
+        !self.recovered_queue.is_empty()
+    }
+
+    fn record_submitted(&self, od: &OperationalData, tx_hashes: &TxHashes) {
+        if let Some(store) = &self.store {
+            let txs = StoredTxs::new(od.tracking_id.to_string(), tx_hashes.0.clone());
+
+            if let Err(e) = store.record_submitted(&self.path_end(), txs) {
+                error!("failed to record pending transactions {}: {}", tx_hashes, e);
+            }
+        }
+    }
+
+    fn record_resolved(&self, tx_hashes: &TxHashes) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.record_resolved(&self.path_end(), &tx_hashes.0) {
+                error!(
+                    "failed to record resolved transactions {}: {}",
+                    tx_hashes, e
+                );
+            }
+        }
+    }
+
+    fn publish_submitted(&self, tracking_id: TrackingId, tx_hashes: &TxHashes) {
+        stream::publish_with(|| StreamEvent::TxSubmitted {
+            chain_id: self.chain_id(),
+            counterparty_chain_id: self.counterparty_chain_id.clone(),
+            channel_id: self.channel_id.clone(),
+            port_id: self.port_id.clone(),
+            tracking_id: tracking_id.to_string(),
+            tx_hashes: tx_hashes.0.iter().map(|hash| hash.to_string()).collect(),
+        });
+    }
+
+    /// Publish the confirmation of the given transactions, along with the
+    /// failure of those among them whose `DeliverTx` reports an error.
+    fn publish_confirmed(&self, tracking_id: &str, tx_hashes: &TxHashes, outcomes: &[TxOutcome]) {
+        stream::publish_with(|| StreamEvent::TxConfirmed {
+            chain_id: self.chain_id(),
+            counterparty_chain_id: self.counterparty_chain_id.clone(),
+            channel_id: self.channel_id.clone(),
+            port_id: self.port_id.clone(),
+            tracking_id: tracking_id.to_string(),
+            tx_hashes: tx_hashes.0.iter().map(|hash| hash.to_string()).collect(),
+        });
+
+        for outcome in outcomes.iter().filter(|outcome| !outcome.is_ok()) {
+            stream::publish_with(|| StreamEvent::TxFailed {
+                chain_id: self.chain_id(),
+                counterparty_chain_id: self.counterparty_chain_id.clone(),
+                channel_id: self.channel_id.clone(),
+                port_id: self.port_id.clone(),
+                tracking_id: tracking_id.to_string(),
+                tx_hash: Some(outcome.hash.to_string()),
+                code: Some(outcome.code),
+                sdk_error: Some(
+                    sdk_error_from_tx_result(Code::from(outcome.code), &outcome.codespace)
+                        .to_string(),
+                ),
+                log: outcome.log.clone(),
+            });
+        }
+    }
+
     /// Insert a new pending transaction to the back of the queue.
     pub fn insert_new_pending_tx(&self, r: AsyncReply, od: OperationalData) {
         let mut tx_hashes = Vec::new();
@@ -111,14 +227,35 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                     response.log
                 ));
                 error_events.push(error_event);
+
+                stream::publish_with(|| StreamEvent::TxFailed {
+                    chain_id: self.chain_id(),
+                    counterparty_chain_id: self.counterparty_chain_id.clone(),
+                    channel_id: self.channel_id.clone(),
+                    port_id: self.port_id.clone(),
+                    tracking_id: od.tracking_id.to_string(),
+                    tx_hash: Some(response.hash.to_string()),
+                    code: Some(response.code.value()),
+                    sdk_error: Some(
+                        sdk_error_from_tx_sync_error_code(response.code.value()).to_string(),
+                    ),
+                    log: response.log,
+                });
             } else {
                 tx_hashes.push(response.hash);
             }
         }
 
+        let tx_hashes = TxHashes(tx_hashes);
+
+        if !tx_hashes.0.is_empty() {
+            self.record_submitted(&od, &tx_hashes);
+            self.publish_submitted(od.tracking_id, &tx_hashes);
+        }
+
         let u = PendingData {
             original_od: od,
-            tx_hashes: TxHashes(tx_hashes),
+            tx_hashes,
             submit_time: Instant::now(),
             error_events,
         };
@@ -126,20 +263,28 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.pending_queue.push_back(u);
     }
 
-    fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
-        let mut all_events = Vec::new();
-        for hash in &tx_hashes.0 {
-            let mut events = self
-                .chain
-                .query_txs(QueryTxRequest::Transaction(QueryTxHash(*hash)))?;
+    fn check_tx_outcomes(
+        &self,
+        tx_hashes: &TxHashes,
+    ) -> Result<Option<Vec<TxOutcome>>, RelayerError> {
+        let outcomes = self.chain.query_tx_outcomes(tx_hashes.0.clone())?;
 
-            if events.is_empty() {
-                return Ok(None);
-            } else {
-                all_events.append(&mut events)
-            }
+        telemetry!(self.record_gas(outcomes.as_deref().unwrap_or_default()));
+
+        Ok(outcomes)
+    }
+
+    /// Record the gas of the given transactions, once all of them are confirmed.
+    #[cfg(feature = "telemetry")]
+    fn record_gas(&self, outcomes: &[TxOutcome]) {
+        for outcome in outcomes {
+            crate::chain::cosmos::cost::record_gas(
+                &self.chain_id(),
+                &outcome.messages,
+                outcome.gas_wanted,
+                outcome.gas_used,
+            );
         }
-        Ok(Some(all_events.into_iter().map(|ev| ev.event).collect()))
     }
 
     /// Try and process one pending transaction within the given timeout duration if one
@@ -154,6 +299,12 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         relay_path: &RelayPath<ChainA, ChainB>,
         resubmit: Option<impl FnOnce(OperationalData) -> Result<AsyncReply, LinkError>>,
     ) -> Result<Option<RelaySummary>, LinkError> {
+        // Confirm the transactions submitted before restart first, as the
+        // packets they relay are not cleared until they are confirmed.
+        if self.has_recovered_txs() {
+            return self.process_recovered(timeout);
+        }
+
         // We process pending transactions in a FIFO manner, so take from
         // the front of the queue.
         if let Some(pending) = self.pending_queue.pop_front() {
@@ -178,7 +329,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             trace!("trying to confirm {} ", tx_hashes);
 
             // Check for TX events for the given pending transaction hashes.
-            let relay_summary = match self.check_tx_events(tx_hashes) {
+            let relay_summary = match self.check_tx_outcomes(tx_hashes) {
                 Ok(None) => {
                     // There is no events for the associated transactions.
                     // This means the transaction has not yet been committed.
@@ -203,6 +354,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
 
                                 match new_od.map(f) {
                                     Some(Ok(reply)) => {
+                                        self.record_resolved(tx_hashes);
                                         self.insert_new_pending_tx(reply, pending.original_od);
                                         Ok(None)
                                     }
@@ -212,12 +364,14 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                                     }
                                     None => {
                                         // No operational data was regenerated; nothing to resubmit
+                                        self.record_resolved(tx_hashes);
                                         Ok(None)
                                     }
                                 }
                             }
                             None => {
                                 // `clear_interval != 0` such that resubmission has been disabled
+                                self.record_resolved(tx_hashes);
                                 Ok(None)
                             }
                         }
@@ -229,7 +383,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         Ok(None)
                     }
                 }
-                Ok(Some(mut events)) => {
+                Ok(Some(outcomes)) => {
                     // We get a list of events for the transaction hashes,
                     // Meaning the transaction has been committed successfully
                     // to the chain.
@@ -251,6 +405,17 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
+                    telemetry!(relay_path.record_packet_latency(&pending.original_od));
+
+                    self.record_resolved(tx_hashes);
+                    self.publish_confirmed(
+                        &pending.tracking_id().to_string(),
+                        tx_hashes,
+                        &outcomes,
+                    );
+
+                    let mut events = events_of(outcomes);
+
                     // Append the events corresponding to errors from the pending tx.
                     events.extend(pending.error_events);
 
@@ -287,4 +452,59 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             Ok(None)
         }
     }
+
+    /// Try and confirm one batch of transactions submitted before the relayer restarted.
+    ///
+    /// Unlike the transactions submitted since, these are never resubmitted: if they
+    /// are not confirmed within the given timeout duration, they are dropped and the
+    /// packets they relay are left to packet clearing.
+    fn process_recovered(&self, timeout: Duration) -> Result<Option<RelaySummary>, LinkError> {
+        let recovered = match self.recovered_queue.pop_front() {
+            Some(recovered) => recovered,
+            None => return Ok(None),
+        };
+
+        let tx_hashes = TxHashes(recovered.tx_hashes.clone());
+
+        match self.check_tx_outcomes(&tx_hashes) {
+            Ok(Some(outcomes)) => {
+                debug!(
+                    tracking_id = %recovered.tracking_id,
+                    tx_hashes = %tx_hashes,
+                    "transactions submitted before restart confirmed",
+                );
+
+                self.record_resolved(&tx_hashes);
+                self.publish_confirmed(&recovered.tracking_id, &tx_hashes, &outcomes);
+
+                Ok(Some(RelaySummary::from_events(events_of(outcomes))))
+            }
+            Ok(None) if recovered.elapsed() > timeout => {
+                warn!(
+                    tracking_id = %recovered.tracking_id,
+                    tx_hashes = %tx_hashes,
+                    "timed out while confirming transactions submitted before restart, \
+                     their packets will be relayed again by packet clearing",
+                );
+
+                self.record_resolved(&tx_hashes);
+
+                Ok(None)
+            }
+            Ok(None) => {
+                self.recovered_queue.push_back(recovered);
+                Ok(None)
+            }
+            Err(e) => {
+                error!(
+                    "error querying for tx hashes {}: {}. will retry again later",
+                    tx_hashes, e
+                );
+
+                self.recovered_queue.push_back(recovered);
+
+                Err(LinkError::relayer(e))
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use alloc::collections::BTreeMap as HashMap;
+use alloc::collections::BTreeSet;
 use alloc::collections::VecDeque;
 use std::ops::Sub;
 use std::time::{Duration, Instant};
@@ -14,8 +15,10 @@ use ibc_relayer_types::core::ics04_channel::msgs::{
     acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
     recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 };
-use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
-use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
 use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
@@ -50,11 +53,13 @@ use crate::link::operational_data::{
 use crate::link::packet_events::query_packet_events_with;
 use crate::link::packet_events::query_send_packet_events;
 use crate::link::packet_events::query_write_ack_events;
+use crate::link::packet_fees::IncentivizedPacketsCache;
 use crate::link::pending::PendingTxs;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
+use crate::store::{PathEnd, Store};
 use crate::telemetry;
 use crate::util::collate::CollatedIterExt;
 use crate::util::pretty::PrettyEvents;
@@ -106,6 +111,22 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // The sequences of the packets which must not be relayed, for the packets
+    // sent from the source chain and from the destination chain respectively.
+    src_excluded_sequences: BTreeSet<Sequence>,
+    dst_excluded_sequences: BTreeSet<Sequence>,
+
+    // The ICS-29 fees escrowed for the packets sent from the source chain,
+    // if a fee filter is configured for it.
+    src_packet_fees: IncentivizedPacketsCache,
+
+    // The store recording the pending transactions and the last clearing height, if enabled.
+    store: Option<Store>,
+
+    // Whether the end-to-end latency of relaying packets is recorded, which requires
+    // querying the time of the blocks including their SendPacket events.
+    record_packet_latency: bool,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -132,6 +153,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_excluded_sequences = src_chain
+            .config()
+            .map_err(LinkError::relayer)?
+            .excluded_sequences
+            .for_channel(&src_port_id, &src_channel_id);
+
+        let dst_excluded_sequences = dst_chain
+            .config()
+            .map_err(LinkError::relayer)?
+            .excluded_sequences
+            .for_channel(&dst_port_id, &dst_channel_id);
+
         let path = PathIdentifiers {
             port_id: dst_port_id.clone(),
             channel_id: dst_channel_id.clone(),
@@ -150,9 +183,114 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            src_excluded_sequences,
+            dst_excluded_sequences,
+
+            src_packet_fees: IncentivizedPacketsCache::default(),
+
+            store: None,
+
+            record_packet_latency: false,
         })
     }
 
+    /// Record the pending transactions and the clearing heights of this path in the
+    /// given store, and resume from the state recorded there before the relayer restarted.
+    pub fn set_store(&mut self, store: Store) {
+        self.pending_txs_src.set_store(store.clone());
+        self.pending_txs_dst.set_store(store.clone());
+        self.store = Some(store);
+    }
+
+    /// Record the end-to-end latency of relaying the packets on this path, if the
+    /// telemetry is enabled. The latency is not recorded by default, as it requires
+    /// querying the time of the block including each SendPacket event.
+    pub fn set_record_packet_latency(&mut self, enabled: bool) {
+        self.record_packet_latency = enabled;
+    }
+
+    /// Returns true if some transactions submitted on this path before the relayer
+    /// restarted are still to be confirmed, false otherwise.
+    pub fn has_recovered_txs(&self) -> bool {
+        self.pending_txs_src.has_recovered_txs() || self.pending_txs_dst.has_recovered_txs()
+    }
+
+    /// The last height of the source chain at which the packets of this path were cleared,
+    /// as recorded in the store.
+    pub fn last_cleared_height(&self) -> Option<Height> {
+        self.store
+            .as_ref()
+            .and_then(|store| store.last_cleared_height(&self.src_path_end()))
+    }
+
+    fn src_path_end(&self) -> PathEnd {
+        PathEnd {
+            chain_id: self.src_chain().id(),
+            port_id: self.src_port_id().clone(),
+            channel_id: self.src_channel_id().clone(),
+            counterparty_chain_id: self.dst_chain().id(),
+        }
+    }
+
+    /// Stop relaying the packets with the given sequences sent from the given chain
+    /// on the given port and channel, if these are one of the ends of this path.
+    pub fn exclude_sequences(
+        &mut self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequences: &[Sequence],
+    ) {
+        let excluded = if chain_id == &self.src_chain().id()
+            && port_id == self.src_port_id()
+            && channel_id == self.src_channel_id()
+        {
+            &mut self.src_excluded_sequences
+        } else if chain_id == &self.dst_chain().id()
+            && port_id == self.dst_port_id()
+            && channel_id == self.dst_channel_id()
+        {
+            &mut self.dst_excluded_sequences
+        } else {
+            return;
+        };
+
+        info!(
+            %chain_id,
+            %port_id,
+            %channel_id,
+            sequences = %sequences.iter().copied().collated().format(", "),
+            "excluding packet sequences from relaying"
+        );
+
+        excluded.extend(sequences.iter().copied());
+
+        for queue in [&self.src_operational_data, &self.dst_operational_data] {
+            drop_excluded_messages(
+                queue,
+                &self.src_excluded_sequences,
+                &self.dst_excluded_sequences,
+            );
+        }
+    }
+
+    /// Returns true if the packet with the given sequence sent from the source chain
+    /// (if `from_src` is true) or from the destination chain must not be relayed.
+    fn is_excluded(&self, sequence: Sequence, from_src: bool) -> bool {
+        let excluded = if from_src {
+            self.src_excluded_sequences.contains(&sequence)
+        } else {
+            self.dst_excluded_sequences.contains(&sequence)
+        };
+
+        if excluded {
+            debug!(%sequence, "skipping packet with excluded sequence");
+        }
+
+        excluded
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -431,6 +569,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         debug!(height = ?clear_height, "done relaying pending packets at clear height");
 
+        if let Some(store) = &self.store {
+            let cleared_height = match clear_height {
+                Some(height) => height,
+                None => self.src_latest_height()?,
+            };
+
+            if let Err(e) = store.record_cleared(&self.src_path_end(), cleared_height) {
+                error!("failed to record clearing height {}: {}", cleared_height, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -509,6 +658,27 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let dst_latest_height = dst_latest_info.height;
 
+        // The fees escrowed for the packets sent from the source chain, whose `RecvPacket`
+        // messages are only relayed if they pay enough fees, if a fee filter is configured.
+        let max_send_sequence = input
+            .iter()
+            .filter_map(|ev| match &ev.event {
+                IbcEvent::SendPacket(event) => Some(event.packet.sequence),
+                _ => None,
+            })
+            .max();
+
+        let src_packet_fees = match max_send_sequence {
+            Some(max_sequence) => self.src_packet_fees.get_or_fetch(
+                self.src_chain(),
+                self.src_port_id(),
+                self.src_channel_id(),
+                &self.dst_chain().id(),
+                max_sequence,
+            )?,
+            None => None,
+        };
+
         // Operational data targeting the source chain (e.g., Timeout packets)
         let mut src_od = OperationalData::new(
             dst_latest_height,
@@ -529,6 +699,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             trace!(event = %event_with_height, "processing event");
 
             let (dst_msg, src_msg) = match &event_with_height.event {
+                IbcEvent::SendPacket(ref event)
+                    if self.is_excluded(event.packet.sequence, true) =>
+                {
+                    (None, None)
+                }
+                IbcEvent::WriteAcknowledgement(ref event)
+                    if self.is_excluded(event.packet.sequence, false) =>
+                {
+                    (None, None)
+                }
                 IbcEvent::CloseInitChannel(_) => (
                     Some(self.build_chan_close_confirm_from_event(event_with_height)?),
                     None,
@@ -558,11 +738,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
                         (None, None)
                     } else {
-                        self.build_recv_or_timeout_from_send_packet_event(
-                            event,
-                            &dst_latest_info,
-                            event_with_height.height,
-                        )?
+                        let (dst_msg, src_msg) = self
+                            .build_recv_or_timeout_from_send_packet_event(
+                                event,
+                                &dst_latest_info,
+                                event_with_height.height,
+                            )?;
+
+                        match &src_packet_fees {
+                            Some(fees) => {
+                                (fees.filter_recv(event.packet.sequence, dst_msg), src_msg)
+                            }
+                            None => (dst_msg, src_msg),
+                        }
                     }
                 }
                 IbcEvent::WriteAcknowledgement(ref event) => {
@@ -619,6 +807,51 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok((src_od, dst_od))
     }
 
+    /// Generates the operational data timing out, on the source chain, the packets
+    /// of the given `SendPacket` events which have expired on the destination chain.
+    /// Packets which have not expired yet are ignored, as are excluded ones.
+    pub(crate) fn generate_timeout_operational_data(
+        &self,
+        events: TrackedEvents,
+    ) -> Result<Option<OperationalData>, LinkError> {
+        let dst_latest_info = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let mut src_od = OperationalData::new(
+            dst_latest_info.height,
+            OperationalDataTarget::Source,
+            events.tracking_id(),
+            self.channel.connection_delay,
+        );
+
+        for event_with_height in events.events() {
+            let event = match &event_with_height.event {
+                IbcEvent::SendPacket(event) if !self.is_excluded(event.packet.sequence, true) => {
+                    event
+                }
+                _ => continue,
+            };
+
+            if let Some(msg) = self.build_timeout_from_send_packet_event(event, &dst_latest_info)? {
+                trace!(%msg.type_url, event = %event_with_height, "collected event");
+
+                src_od.batch.push(TransitMessage {
+                    event_with_height: event_with_height.clone(),
+                    msg,
+                });
+
+                // For ordered channels, the first timeout closes the channel
+                if self.ordered_channel() {
+                    break;
+                }
+            }
+        }
+
+        Ok(Some(src_od).filter(|od| !od.batch.is_empty()))
+    }
+
     /// Relays an [`OperationalData`] using a specific
     /// sender, which implements [`relay_sender::Submit`].
     pub(crate) fn relay_from_operational_data<S: relay_sender::Submit>(
@@ -651,6 +884,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                             port_id,
                             &counterparty,
                         );
+
+                        if let Some(height) = odata
+                            .batch
+                            .iter()
+                            .map(|msg| msg.event_with_height.height)
+                            .max()
+                        {
+                            ibc_telemetry::global().packets_relayed(
+                                &self.src_chain().id(),
+                                self.src_channel_id(),
+                                self.src_port_id(),
+                                height,
+                            );
+                        }
                     });
 
                     return Ok(reply);
@@ -659,7 +906,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     // This error means we could retry
                     error!("error {}", e.event);
                     if i + 1 == MAX_RETRIES {
-                        error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES)
+                        error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES);
+
+                        telemetry!(
+                            packet_relaying_failed,
+                            &self.src_chain().id(),
+                            self.src_channel_id(),
+                            self.src_port_id(),
+                            e.event.to_string(),
+                        );
                     } else {
                         // If we haven't exhausted all retries, regenerate the op. data & retry
                         match self.regenerate_operational_data(odata.clone()) {
@@ -669,6 +924,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     }
                 }
                 Err(e) => {
+                    telemetry!(
+                        packet_relaying_failed,
+                        &self.src_chain().id(),
+                        self.src_channel_id(),
+                        self.src_port_id(),
+                        e.to_string(),
+                    );
+
                     // Unrecoverable error, propagate up the stack
                     return Err(e);
                 }
@@ -1088,10 +1351,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         .entered();
 
         // Pull the s.n. of all packets that the destination chain has not yet received.
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
+        sequences.retain(|seq| !self.is_excluded(*seq, true));
+
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
         // Skip: no relevant events found.
@@ -1146,10 +1411,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         )
         .entered();
 
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
+        sequences.retain(|seq| !self.is_excluded(*seq, false));
+
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
         // Skip: no relevant events found.
@@ -1637,6 +1904,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             return Ok(());
         }
 
+        telemetry!(self.fetch_send_packet_times(&mut od));
+
         // Update clients ahead of scheduling the operational data, if the delays are non-zero.
         // If the connection-delay must be taken into account, set the `scheduled_time` to an
         // instant in the past, i.e. when this client update was first processed (`processed_time`)
@@ -1761,6 +2030,76 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 
+    /// Fetch the time of the blocks including the SendPacket events of the given
+    /// operational data targeting the destination chain, so that the latency of
+    /// relaying their packets can be recorded once they are confirmed.
+    ///
+    /// Nothing is queried unless the latency is recorded on this path.
+    #[cfg(feature = "telemetry")]
+    fn fetch_send_packet_times(&self, od: &mut OperationalData) {
+        if !self.record_packet_latency || od.target != OperationalDataTarget::Destination {
+            return;
+        }
+
+        let heights: BTreeSet<Height> = od
+            .batch
+            .iter()
+            .filter(|msg| matches!(msg.event_with_height.event, IbcEvent::SendPacket(_)))
+            .map(|msg| msg.event_with_height.height)
+            .collect();
+
+        for height in heights {
+            match self
+                .src_chain()
+                .query_host_consensus_state(QueryHostConsensusStateRequest {
+                    height: QueryHeight::Specific(height),
+                }) {
+                Ok(consensus_state) => {
+                    od.send_packet_times
+                        .insert(height, consensus_state.timestamp());
+                }
+                Err(e) => {
+                    debug!(
+                        "failed to query the time of the block at height {}, \
+                         not recording the latency of its packets: {}",
+                        height, e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Record the end-to-end latency of relaying the packets whose `MsgRecvPacket` are in
+    /// the given operational data, once the transactions including them are confirmed.
+    ///
+    /// The latency is measured from the time of the block including their SendPacket event,
+    /// fetched when the operational data was scheduled.
+    #[cfg(feature = "telemetry")]
+    pub(crate) fn record_packet_latency(&self, od: &OperationalData) {
+        let confirmed_at = Timestamp::now();
+
+        for msg in &od.batch {
+            if !matches!(msg.event_with_height.event, IbcEvent::SendPacket(_)) {
+                continue;
+            }
+
+            let latency = od
+                .send_packet_times
+                .get(&msg.event_with_height.height)
+                .and_then(|block_time| confirmed_at.duration_since(block_time));
+
+            if let Some(latency) = latency {
+                ibc_telemetry::global().packet_latency(
+                    &self.src_chain().id(),
+                    self.src_channel_id(),
+                    self.src_port_id(),
+                    &self.dst_chain().id(),
+                    latency,
+                );
+            }
+        }
+    }
+
     #[cfg(feature = "telemetry")]
     fn backlog_update(&self, event: &IbcEvent) {
         match event {
@@ -1836,3 +2175,137 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 }
+
+/// Returns the sequence of the packet relayed in response to the given event
+/// if it is excluded, ie. if the packet was sent from the source chain and its
+/// sequence is in `src_excluded`, or if it was acknowledged on the destination
+/// chain and its sequence is in `dst_excluded`.
+fn excluded_sequence(
+    event: &IbcEvent,
+    src_excluded: &BTreeSet<Sequence>,
+    dst_excluded: &BTreeSet<Sequence>,
+) -> Option<Sequence> {
+    let (sequence, excluded) = match event {
+        IbcEvent::SendPacket(event) => (event.packet.sequence, src_excluded),
+        IbcEvent::WriteAcknowledgement(event) => (event.packet.sequence, dst_excluded),
+        _ => return None,
+    };
+
+    excluded.contains(&sequence).then_some(sequence)
+}
+
+/// Removes from the scheduled operational data the messages relaying the packets
+/// with excluded sequences, and drops the operational data left without messages.
+fn drop_excluded_messages(
+    queue: &Queue<OperationalData>,
+    src_excluded: &BTreeSet<Sequence>,
+    dst_excluded: &BTreeSet<Sequence>,
+) {
+    let remaining = queue
+        .take()
+        .into_iter()
+        .filter_map(|mut od| {
+            let info = od.info();
+
+            od.batch.retain(|msg| {
+                let event = &msg.event_with_height.event;
+
+                match excluded_sequence(event, src_excluded, dst_excluded) {
+                    Some(sequence) => {
+                        debug!(
+                            %sequence,
+                            odata = %info,
+                            "dropping scheduled message for excluded sequence"
+                        );
+                        false
+                    }
+                    None => true,
+                }
+            });
+
+            (!od.batch.is_empty()).then_some(od)
+        })
+        .collect();
+
+    queue.replace(remaining);
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use std::time::Duration;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+
+    use super::drop_excluded_messages;
+    use crate::chain::tracking::TrackingId;
+    use crate::event::IbcEventWithHeight;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+    use crate::util::queue::Queue;
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            ..Packet::default()
+        }
+    }
+
+    fn operational_data(events: Vec<IbcEvent>) -> OperationalData {
+        let height = Height::new(0, 10).unwrap();
+        let mut od = OperationalData::new(
+            height,
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        );
+
+        for event in events {
+            od.push(TransitMessage {
+                event_with_height: IbcEventWithHeight::new(event, height),
+                msg: Any::default(),
+            });
+        }
+
+        od
+    }
+
+    fn sequences(od: &OperationalData) -> Vec<u64> {
+        od.batch
+            .iter()
+            .filter_map(|msg| msg.event_with_height.event.packet())
+            .map(|packet| packet.sequence.into())
+            .collect()
+    }
+
+    #[test]
+    fn drop_scheduled_messages_of_excluded_sequences() {
+        let send_packet = |sequence| {
+            IbcEvent::SendPacket(SendPacket {
+                packet: packet(sequence),
+            })
+        };
+        let write_ack = |sequence| {
+            IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                packet: packet(sequence),
+                ack: vec![],
+            })
+        };
+
+        let queue = Queue::new();
+        queue.push_back(operational_data(vec![send_packet(1), send_packet(2)]));
+        queue.push_back(operational_data(vec![send_packet(3)]));
+        queue.push_back(operational_data(vec![write_ack(2), write_ack(3)]));
+
+        let src_excluded = BTreeSet::from([Sequence::from(2), Sequence::from(3)]);
+        let dst_excluded = BTreeSet::from([Sequence::from(3)]);
+
+        drop_excluded_messages(&queue, &src_excluded, &dst_excluded);
+
+        let remaining: Vec<_> = queue.take().iter().map(sequences).collect();
+        assert_eq!(remaining, vec![vec![1], vec![2]]);
+    }
+}
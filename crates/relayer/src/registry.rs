@@ -41,6 +41,13 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Replace the configuration used when spawning new chain runtimes.
+    ///
+    /// Runtimes which have already been spawned are not affected.
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
     /// Return the size of the registry, i.e., the number of distinct chain runtimes.
     pub fn size(&self) -> usize {
         self.handles.len()
@@ -81,16 +88,42 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Spawn a chain runtime for the chain with the given [`ChainId`], using the given
+    /// [`Config`] rather than the one of the registry, without registering its handle.
+    ///
+    /// This allows a new runtime to be checked before it replaces the current one,
+    /// see [`Registry::replace`].
+    pub fn spawn_unregistered(
+        &self,
+        config: &Config,
+        chain_id: &ChainId,
+    ) -> Result<Chain, SpawnError> {
+        spawn_chain_runtime(config, chain_id, self.rt.clone())
+    }
+
+    /// Register the given handle for the chain with the given [`ChainId`],
+    /// shutting down the runtime it replaces, if any.
+    pub fn replace(&mut self, chain_id: &ChainId, handle: Chain) {
+        if let Some(previous) = self.handles.insert(chain_id.clone(), handle) {
+            shutdown_runtime(chain_id, previous);
+        }
+    }
+
     /// Shutdown the runtime associated with the given chain identifier.
     pub fn shutdown(&mut self, chain_id: &ChainId) {
         if let Some(handle) = self.handles.remove(chain_id) {
-            if let Err(e) = handle.shutdown() {
-                warn!(chain = %chain_id, "chain runtime might have failed to shutdown properly: {}", e);
-            }
+            shutdown_runtime(chain_id, handle);
         }
     }
 }
 
+/// Shutdown the runtime behind the given handle.
+pub fn shutdown_runtime<Chain: ChainHandle>(chain_id: &ChainId, handle: Chain) {
+    if let Err(e) = handle.shutdown() {
+        warn!(chain = %chain_id, "chain runtime might have failed to shutdown properly: {}", e);
+    }
+}
+
 impl<Chain: ChainHandle> SharedRegistry<Chain> {
     pub fn new(config: Config) -> Self {
         let registry = Registry::new(config);
@@ -1,11 +1,14 @@
 use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+
 use crate::{
     config::Config,
+    event::stream,
     rest::request::ReplySender,
-    rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    rest::request::{PathBacklog, Request, VersionInfo, WorkerStatus},
+    supervisor::{cmd::ConfigUpdate, dump_state::SupervisorState},
 };
 
 pub mod request;
@@ -25,12 +28,35 @@ pub const VER: &str = env!(
 pub type Receiver = crossbeam_channel::Receiver<Request>;
 
 // TODO: Unify this enum with `SupervisorCmd`
-//  We won't unify yet as it is possible we will never implement
-//  REST API `/chain` adding endpoint; instead of `/chain` we might
-//  implement `/reload` for supporting a broader range of functionality
-//  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    Workers(ReplySender<Vec<WorkerStatus>>),
+    PathBacklog {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<PathBacklog>,
+    },
+    UpdateConfig(ConfigUpdate, ReplySender<()>),
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<()>,
+    },
+    RefreshClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        force: bool,
+        reply_to: ReplySender<()>,
+    },
+    PausePacketWorker {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        paused: bool,
+        reply_to: ReplySender<()>,
+    },
 }
 
 /// Process incoming REST requests.
@@ -82,6 +108,140 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::GetWorkers { reply_to } => {
+                trace!("GetWorkers");
+
+                return Some(Command::Workers(reply_to));
+            }
+
+            Request::GetPathBacklog {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("GetPathBacklog {} {}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::PathBacklog {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    reply_to,
+                });
+            }
+
+            Request::SubscribeEvents { reply_to } => {
+                trace!("SubscribeEvents");
+
+                reply_to
+                    .send(Ok(stream::subscribe()))
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            }
+
+            Request::AddChain {
+                chain_config,
+                reply_to,
+            } => {
+                trace!("AddChain {}", chain_config.id);
+
+                return Some(Command::UpdateConfig(
+                    ConfigUpdate::Add(chain_config),
+                    reply_to,
+                ));
+            }
+
+            Request::UpdateChain {
+                chain_config,
+                reply_to,
+            } => {
+                trace!("UpdateChain {}", chain_config.id);
+
+                return Some(Command::UpdateConfig(
+                    ConfigUpdate::Update(chain_config),
+                    reply_to,
+                ));
+            }
+
+            Request::RemoveChain { chain_id, reply_to } => {
+                trace!("RemoveChain {}", chain_id);
+
+                return Some(Command::UpdateConfig(
+                    ConfigUpdate::Remove(chain_id),
+                    reply_to,
+                ));
+            }
+
+            Request::ClearPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("ClearPackets {} {}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::ClearPackets {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    reply_to,
+                });
+            }
+
+            Request::RefreshClient {
+                chain_id,
+                client_id,
+                force,
+                reply_to,
+            } => {
+                trace!(
+                    "RefreshClient {} {} (force: {})",
+                    chain_id,
+                    client_id,
+                    force
+                );
+
+                return Some(Command::RefreshClient {
+                    chain_id,
+                    client_id,
+                    force,
+                    reply_to,
+                });
+            }
+
+            Request::PausePacketWorker {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("PausePacketWorker {} {}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::PausePacketWorker {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    paused: true,
+                    reply_to,
+                });
+            }
+
+            Request::ResumePacketWorker {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("ResumePacketWorker {} {}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::PausePacketWorker {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    paused: false,
+                    reply_to,
+                });
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -92,3 +252,90 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
     None
 }
+
+/// The backlog of the given end of a path, as tracked by the telemetry.
+#[cfg(feature = "telemetry")]
+pub(crate) fn path_backlog(
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<PathBacklog, RestApiError> {
+    let status = ibc_telemetry::global().path_status(chain_id, channel_id, port_id);
+
+    Ok(backlog_from_status(chain_id, port_id, channel_id, status))
+}
+
+#[cfg(feature = "telemetry")]
+fn backlog_from_status(
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    status: ibc_telemetry::state::PathStatus,
+) -> PathBacklog {
+    PathBacklog {
+        chain_id: chain_id.clone(),
+        channel_id: channel_id.clone(),
+        port_id: port_id.clone(),
+        pending_sequences: status
+            .pending
+            .iter()
+            .map(|(seq, _)| (*seq).into())
+            .collect(),
+        // Packets with lower sequence numbers may have been observed later, eg. by packet clearing
+        oldest_pending_timestamp: status.pending.iter().map(|(_, timestamp)| *timestamp).min(),
+        last_relayed_height: status.last_relayed_height,
+        last_error: status.last_error,
+    }
+}
+
+#[cfg(not(feature = "telemetry"))]
+pub(crate) fn path_backlog(
+    _chain_id: &ChainId,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<PathBacklog, RestApiError> {
+    Err(RestApiError::TelemetryDisabled)
+}
+
+#[cfg(all(test, feature = "telemetry"))]
+mod tests {
+    use super::backlog_from_status;
+
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc_relayer_types::Height;
+    use ibc_telemetry::state::PathStatus;
+
+    #[test]
+    fn backlog_of_path() {
+        let status = PathStatus {
+            pending: vec![(3, 1_670_000_100), (4, 1_670_000_000), (9, 1_670_000_200)],
+            last_relayed_height: Some(Height::new(0, 42).unwrap()),
+            last_error: Some("failed".to_string()),
+        };
+
+        let backlog = backlog_from_status(
+            &ChainId::from_string("ibc-0"),
+            &PortId::transfer(),
+            &ChannelId::new(0),
+            status,
+        );
+
+        assert_eq!(
+            backlog.pending_sequences,
+            vec![3.into(), 4.into(), 9.into()]
+        );
+        assert_eq!(backlog.oldest_pending_timestamp, Some(1_670_000_000));
+        assert_eq!(backlog.last_relayed_height, Height::new(0, 42).ok());
+        assert_eq!(backlog.last_error, Some("failed".to_string()));
+
+        let empty = backlog_from_status(
+            &ChainId::from_string("ibc-0"),
+            &PortId::transfer(),
+            &ChannelId::new(0),
+            PathStatus::default(),
+        );
+
+        assert!(empty.pending_sequences.is_empty());
+        assert_eq!(empty.oldest_pending_timestamp, None);
+    }
+}
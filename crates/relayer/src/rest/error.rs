@@ -1,7 +1,10 @@
 use serde::ser::{Serialize, SerializeMap, Serializer};
 use thiserror::Error;
 
-use ibc_relayer_types::core::ics24_host::{error::ValidationErrorDetail, identifier::ChainId};
+use ibc_relayer_types::core::ics24_host::{
+    error::ValidationErrorDetail,
+    identifier::{ChainId, ChannelId, PortId},
+};
 
 #[derive(Error, Debug)]
 pub enum RestApiError {
@@ -23,6 +26,30 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("the supervisor failed to apply the configuration update: {0}")]
+    ConfigUpdate(String),
+
+    #[error("failed to parse the request parameter {0}: {1}")]
+    InvalidParameter(String, String),
+
+    #[error("no packet worker is relaying the packets sent from chain {0} on {1}/{2}")]
+    PacketWorkerNotFound(ChainId, PortId, ChannelId),
+
+    #[error("failed to refresh the client: {0}")]
+    ClientRefresh(String),
+
+    #[error("the relayer was built without telemetry, which tracks the packet backlogs")]
+    TelemetryDisabled,
+
+    #[error("failed to open a websocket: {0}")]
+    Websocket(String),
+
+    #[error("missing or invalid authentication token")]
+    Unauthorized,
+
+    #[error("write endpoints are disabled, as no authentication token is configured")]
+    WriteDisabled,
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +63,14 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::ConfigUpdate(_) => "ConfigUpdate",
+            RestApiError::InvalidParameter(_, _) => "InvalidParameter",
+            RestApiError::PacketWorkerNotFound(_, _, _) => "PacketWorkerNotFound",
+            RestApiError::ClientRefresh(_) => "ClientRefresh",
+            RestApiError::TelemetryDisabled => "TelemetryDisabled",
+            RestApiError::Websocket(_) => "Websocket",
+            RestApiError::Unauthorized => "Unauthorized",
+            RestApiError::WriteDisabled => "WriteDisabled",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
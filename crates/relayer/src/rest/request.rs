@@ -1,8 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc_relayer_types::Height;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::ChainConfig,
+    event::stream::EventReceiver,
+    object::Object,
+    rest::RestApiError,
+    supervisor::dump_state::SupervisorState,
+    worker::{WorkerData, WorkerId},
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -17,6 +26,33 @@ pub struct VersionInfo {
     pub version: String,
 }
 
+/// The packets pending on one end of a path, that is the packets sent from
+/// the given chain on the given channel which have not been relayed yet,
+/// along with the outcome of the last attempts at relaying from it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathBacklog {
+    pub chain_id: ChainId,
+    pub channel_id: ChannelId,
+    pub port_id: PortId,
+    pub pending_sequences: Vec<Sequence>,
+    /// The local time at which the oldest pending packet was observed
+    /// being sent, in seconds since the Unix epoch
+    pub oldest_pending_timestamp: Option<u64>,
+    pub last_relayed_height: Option<Height>,
+    /// The error which occurred while relaying, if relaying has not succeeded since
+    pub last_error: Option<String>,
+}
+
+/// The status of a worker, along with the backlog of its path for packet workers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerStatus {
+    pub id: WorkerId,
+    pub object: Object,
+    pub data: Option<WorkerData>,
+    pub stopped: bool,
+    pub backlog: Option<PathBacklog>,
+}
+
 /// REST API request variants
 #[derive(Clone, Debug)]
 pub enum Request {
@@ -32,8 +68,73 @@ pub enum Request {
         reply_to: ReplySender<Vec<ChainId>>,
     },
 
+    GetWorkers {
+        reply_to: ReplySender<Vec<WorkerStatus>>,
+    },
+
+    GetPathBacklog {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<PathBacklog>,
+    },
+
+    /// Subscribe to the events of all chains, and to those
+    /// of the transactions submitted by the relayer.
+    SubscribeEvents {
+        reply_to: ReplySender<EventReceiver>,
+    },
+
     GetChain {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    AddChain {
+        chain_config: ChainConfig,
+        reply_to: ReplySender<()>,
+    },
+
+    UpdateChain {
+        chain_config: ChainConfig,
+        reply_to: ReplySender<()>,
+    },
+
+    RemoveChain {
+        chain_id: ChainId,
+        reply_to: ReplySender<()>,
+    },
+
+    /// Schedule the clearing of the packets sent from the given chain on the given channel.
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<()>,
+    },
+
+    /// Schedule the refresh of the given client hosted on the given chain, or its update
+    /// to the latest height of its target chain if `force` is set.
+    RefreshClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        force: bool,
+        reply_to: ReplySender<()>,
+    },
+
+    /// Stop relaying the packets sent from the given chain on the given channel.
+    PausePacketWorker {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<()>,
+    },
+
+    /// Resume relaying the packets sent from the given chain on the given channel.
+    ResumePacketWorker {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<()>,
+    },
 }
@@ -0,0 +1,545 @@
+//! On-disk store of the relaying state which must survive a restart of the relayer.
+//!
+//! The store records, for each end of a path, the transactions submitted to the
+//! chain at that end which are not confirmed yet, along with the last height at
+//! which the packets sent from that end were cleared.
+//!
+//! It is implemented as an append-only log of JSON records, which is replayed
+//! and compacted when the store is opened, and compacted again whenever it grows
+//! much larger than the state it records.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use flex_error::{define_error, TraceError};
+use serde_derive::{Deserialize, Serialize};
+use tendermint::Hash as TxHash;
+use tracing::{debug, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::Height;
+
+use crate::config::StoreConfig;
+
+const LOG_FILE: &str = "store.log";
+const DEFAULT_STORE_DIR: &str = ".hermes/store";
+
+/// Number of records in the log above which it is compacted, provided that
+/// it holds more than twice the number of records needed to rebuild the state.
+const COMPACTION_THRESHOLD: usize = 1000;
+
+define_error! {
+    StoreError {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format_args!("I/O error on store file {}", e.path.display()) },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode store record" },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
+
+/// One end of a path: a channel on a chain, along with the chain at the other end.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PathEnd {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_chain_id: ChainId,
+}
+
+/// Transactions submitted together to relay an operational data, which are not confirmed yet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredTxs {
+    /// The tracking id of the operational data
+    pub tracking_id: String,
+    pub tx_hashes: Vec<TxHash>,
+    /// The time of submission, in seconds since the Unix epoch
+    pub submitted_at: u64,
+}
+
+impl StoredTxs {
+    pub fn new(tracking_id: String, tx_hashes: Vec<TxHash>) -> Self {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            tracking_id,
+            tx_hashes,
+            submitted_at,
+        }
+    }
+
+    /// The time elapsed since these transactions were submitted.
+    pub fn elapsed(&self) -> Duration {
+        let submitted_at = UNIX_EPOCH + Duration::from_secs(self.submitted_at);
+
+        SystemTime::now()
+            .duration_since(submitted_at)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Submitted {
+        path: PathEnd,
+        txs: StoredTxs,
+    },
+    Resolved {
+        path: PathEnd,
+        tx_hashes: Vec<TxHash>,
+    },
+    Cleared {
+        path: PathEnd,
+        height: Height,
+    },
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pending: BTreeMap<PathEnd, Vec<StoredTxs>>,
+    cleared: BTreeMap<PathEnd, Height>,
+}
+
+impl State {
+    fn apply(&mut self, record: Record) {
+        match record {
+            Record::Submitted { path, txs } => self.pending.entry(path).or_default().push(txs),
+            Record::Resolved { path, tx_hashes } => {
+                if let Some(pending) = self.pending.get_mut(&path) {
+                    pending.retain(|txs| txs.tx_hashes != tx_hashes);
+
+                    if pending.is_empty() {
+                        self.pending.remove(&path);
+                    }
+                }
+            }
+            Record::Cleared { path, height } => {
+                self.cleared.insert(path, height);
+            }
+        }
+    }
+
+    /// The number of records from which the current state can be rebuilt.
+    fn record_count(&self) -> usize {
+        self.pending.values().map(Vec::len).sum::<usize>() + self.cleared.len()
+    }
+
+    /// The records from which the current state can be rebuilt.
+    fn records(&self) -> impl Iterator<Item = Record> + '_ {
+        let pending = self.pending.iter().flat_map(|(path, txs)| {
+            txs.iter().map(|txs| Record::Submitted {
+                path: path.clone(),
+                txs: txs.clone(),
+            })
+        });
+
+        let cleared = self.cleared.iter().map(|(path, height)| Record::Cleared {
+            path: path.clone(),
+            height: *height,
+        });
+
+        pending.chain(cleared)
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    file: File,
+    state: State,
+    /// The number of records in the log
+    log_records: usize,
+    /// Whether the log may end with a partially written record, which could not be
+    /// truncated away, in which case the next record must start on a new line
+    torn: bool,
+}
+
+impl Inner {
+    fn needs_compaction(&self) -> bool {
+        self.log_records > COMPACTION_THRESHOLD && self.log_records > 2 * self.state.record_count()
+    }
+}
+
+/// A handle to the store, which can be shared by all the workers.
+#[derive(Clone, Debug)]
+pub struct Store {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Store {
+    /// Open the store configured with the given configuration.
+    pub fn from_config(config: &StoreConfig) -> Result<Self, StoreError> {
+        let dir = match &config.path {
+            Some(path) => path.clone(),
+            None => dirs_next::home_dir()
+                .ok_or_else(StoreError::home_location_unavailable)?
+                .join(DEFAULT_STORE_DIR),
+        };
+
+        Self::open(&dir)
+    }
+
+    /// Open the store in the given directory, creating it if needed.
+    ///
+    /// The log of the store is replayed to restore its state, and then
+    /// compacted so that it only holds the records relevant to that state.
+    pub fn open(dir: &Path) -> Result<Self, StoreError> {
+        fs::create_dir_all(dir).map_err(|e| StoreError::io(dir.to_path_buf(), e))?;
+
+        let path = dir.join(LOG_FILE);
+        let state = replay(&path)?;
+        let file = compact(&path, &state)?;
+
+        debug!(
+            path = %path.display(),
+            pending = state.pending.values().map(Vec::len).sum::<usize>(),
+            "opened store"
+        );
+
+        let log_records = state.record_count();
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                path,
+                file,
+                state,
+                log_records,
+                torn: false,
+            })),
+        })
+    }
+
+    /// Record transactions submitted to the chain at the given path end.
+    pub fn record_submitted(&self, path: &PathEnd, txs: StoredTxs) -> Result<(), StoreError> {
+        self.append(Record::Submitted {
+            path: path.clone(),
+            txs,
+        })
+    }
+
+    /// Record that the given transactions submitted to the chain at the given
+    /// path end have been confirmed, or that they will not be tracked any longer.
+    pub fn record_resolved(&self, path: &PathEnd, tx_hashes: &[TxHash]) -> Result<(), StoreError> {
+        self.append(Record::Resolved {
+            path: path.clone(),
+            tx_hashes: tx_hashes.to_vec(),
+        })
+    }
+
+    /// Record that the packets sent from the given path end were cleared at the given height.
+    pub fn record_cleared(&self, path: &PathEnd, height: Height) -> Result<(), StoreError> {
+        self.append(Record::Cleared {
+            path: path.clone(),
+            height,
+        })
+    }
+
+    /// The transactions submitted to the chain at the given path end which are not confirmed yet.
+    pub fn pending_txs(&self, path: &PathEnd) -> Vec<StoredTxs> {
+        let inner = self.inner.lock().unwrap();
+        inner.state.pending.get(path).cloned().unwrap_or_default()
+    }
+
+    /// The last height at which the packets sent from the given path end were cleared.
+    pub fn last_cleared_height(&self, path: &PathEnd) -> Option<Height> {
+        let inner = self.inner.lock().unwrap();
+        inner.state.cleared.get(path).copied()
+    }
+
+    /// Append the given record to the log, and make sure it is on disk before returning.
+    ///
+    /// The state of the store is only updated once the record is on disk. If writing
+    /// the record fails, the log is truncated back to its length before the write.
+    fn append(&self, record: Record) -> Result<(), StoreError> {
+        let mut inner = self.inner.lock().unwrap();
+        let Inner {
+            path, file, torn, ..
+        } = &mut *inner;
+
+        let len = file
+            .metadata()
+            .map_err(|e| StoreError::io(path.to_path_buf(), e))?
+            .len();
+
+        let written = write_record(file, path, &record, *torn).and_then(|()| {
+            file.sync_data()
+                .map_err(|e| StoreError::io(path.to_path_buf(), e))
+        });
+
+        if let Err(e) = written {
+            // Drop whatever part of the record was written, so that the next record
+            // is not appended to a partial line, which would make both unreadable.
+            // The log is opened in append mode, so the next write goes to its new end.
+            if let Err(e) = file.set_len(len).and_then(|()| file.sync_data()) {
+                warn!(
+                    path = %path.display(),
+                    "failed to truncate partially written store record: {}", e
+                );
+                *torn = true;
+            }
+
+            return Err(e);
+        }
+
+        inner.torn = false;
+        inner.state.apply(record);
+        inner.log_records += 1;
+
+        if inner.needs_compaction() {
+            debug!(
+                path = %inner.path.display(),
+                records = inner.log_records,
+                "compacting store"
+            );
+
+            inner.file = compact(&inner.path, &inner.state)?;
+            inner.log_records = inner.state.record_count();
+        }
+
+        Ok(())
+    }
+}
+
+/// Rewrite the log at the given path so that it only holds the records from which
+/// the given state can be rebuilt, and open it for appending further records.
+///
+/// The compacted log is written to a temporary file which then replaces the log,
+/// so that a crash while compacting leaves the previous log in place.
+fn compact(path: &Path, state: &State) -> Result<File, StoreError> {
+    let tmp_path = path.with_extension("log.tmp");
+    let mut tmp = File::create(&tmp_path).map_err(|e| StoreError::io(tmp_path.clone(), e))?;
+
+    for record in state.records() {
+        write_record(&mut tmp, &tmp_path, &record, false)?;
+    }
+
+    tmp.sync_all()
+        .map_err(|e| StoreError::io(tmp_path.clone(), e))?;
+    fs::rename(&tmp_path, path).map_err(|e| StoreError::io(path.to_path_buf(), e))?;
+
+    if let Some(dir) = path.parent() {
+        sync_dir(dir)?;
+    }
+
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .map_err(|e| StoreError::io(path.to_path_buf(), e))
+}
+
+/// Write the given record to the log, on a line of its own.
+///
+/// If `torn` is true, the log may end with a partial line, which the record is then
+/// separated from by a new line, so that only the partial line is skipped on replay.
+fn write_record(
+    file: &mut File,
+    path: &Path,
+    record: &Record,
+    torn: bool,
+) -> Result<(), StoreError> {
+    let mut line = if torn { vec![b'\n'] } else { Vec::new() };
+    serde_json::to_writer(&mut line, record).map_err(StoreError::encode)?;
+    line.push(b'\n');
+
+    file.write_all(&line)
+        .map_err(|e| StoreError::io(path.to_path_buf(), e))
+}
+
+/// Make sure that the entries of the given directory, such as a file renamed into it, are on disk.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<(), StoreError> {
+    File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| StoreError::io(dir.to_path_buf(), e))
+}
+
+/// Directories cannot be opened as files, and thus synced, on other platforms.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<(), StoreError> {
+    Ok(())
+}
+
+/// Rebuild the state of the store from its log, if any.
+///
+/// Records which cannot be decoded, eg. because the relayer crashed while
+/// writing them, are skipped.
+fn replay(path: &Path) -> Result<State, StoreError> {
+    let mut state = State::default();
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(state),
+        Err(e) => return Err(StoreError::io(path.to_path_buf(), e)),
+    };
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| StoreError::io(path.to_path_buf(), e))?;
+
+        match serde_json::from_str(&line) {
+            Ok(record) => state.apply(record),
+            Err(e) => warn!(path = %path.display(), "skipping invalid store record: {}", e),
+        }
+    }
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    fn path_end() -> PathEnd {
+        PathEnd {
+            chain_id: ChainId::from_string("ibc-0"),
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            counterparty_chain_id: ChainId::from_string("ibc-1"),
+        }
+    }
+
+    fn tx_hash(byte: char) -> TxHash {
+        TxHash::from_str(&byte.to_string().repeat(64)).unwrap()
+    }
+
+    #[test]
+    fn store_survives_reopening() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = tmp_dir.path();
+
+        let path = path_end();
+        let height = Height::new(0, 42).unwrap();
+
+        {
+            let store = Store::open(dir).unwrap();
+            assert!(store.pending_txs(&path).is_empty());
+            assert_eq!(store.last_cleared_height(&path), None);
+
+            let (a, b) = (vec![tx_hash('A')], vec![tx_hash('B'), tx_hash('C')]);
+            store
+                .record_submitted(&path, StoredTxs::new("a".to_string(), a.clone()))
+                .unwrap();
+            store
+                .record_submitted(&path, StoredTxs::new("b".to_string(), b))
+                .unwrap();
+            store.record_resolved(&path, &a).unwrap();
+            store.record_cleared(&path, height).unwrap();
+        }
+
+        // Simulate a crash while writing a record
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join(LOG_FILE))
+            .unwrap();
+        file.write_all(b"{\"type\":\"submi").unwrap();
+
+        let store = Store::open(dir).unwrap();
+        let pending = store.pending_txs(&path);
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tracking_id, "b");
+        assert_eq!(pending[0].tx_hashes.len(), 2);
+        assert_eq!(store.last_cleared_height(&path), Some(height));
+
+        // The log has been compacted on reopening
+        let log = fs::read_to_string(dir.join(LOG_FILE)).unwrap();
+        assert_eq!(log.lines().count(), 2);
+    }
+
+    #[test]
+    fn records_after_a_partial_write_are_kept() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = tmp_dir.path();
+
+        let path = path_end();
+        let height = Height::new(0, 42).unwrap();
+
+        let store = Store::open(dir).unwrap();
+        store
+            .record_submitted(&path, StoredTxs::new("a".to_string(), vec![tx_hash('A')]))
+            .unwrap();
+
+        // Simulate a write which failed halfway, and whose partial record could not be truncated
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join(LOG_FILE))
+            .unwrap();
+        file.write_all(b"{\"type\":\"resol").unwrap();
+        store.inner.lock().unwrap().torn = true;
+
+        store.record_cleared(&path, height).unwrap();
+        assert!(!store.inner.lock().unwrap().torn);
+
+        drop(store);
+
+        let store = Store::open(dir).unwrap();
+        assert_eq!(store.pending_txs(&path).len(), 1);
+        assert_eq!(store.last_cleared_height(&path), Some(height));
+    }
+
+    #[test]
+    fn store_is_compacted_when_growing() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = tmp_dir.path();
+
+        let path = path_end();
+        let store = Store::open(dir).unwrap();
+
+        let log_lines = || {
+            fs::read_to_string(dir.join(LOG_FILE))
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        let txs = vec![tx_hash('A')];
+        store
+            .record_submitted(&path, StoredTxs::new("a".to_string(), txs.clone()))
+            .unwrap();
+
+        for i in 1..COMPACTION_THRESHOLD as u64 {
+            store
+                .record_cleared(&path, Height::new(0, i).unwrap())
+                .unwrap();
+        }
+
+        // Every record is on disk, and the log is not compacted yet
+        assert_eq!(log_lines(), COMPACTION_THRESHOLD);
+
+        store
+            .record_cleared(&path, Height::new(0, 1000).unwrap())
+            .unwrap();
+
+        // The log only holds the pending transactions and the last cleared height
+        assert_eq!(log_lines(), 2);
+
+        // Further records are appended to the compacted log
+        store.record_resolved(&path, &txs).unwrap();
+        assert_eq!(log_lines(), 3);
+
+        drop(store);
+
+        let store = Store::open(dir).unwrap();
+        assert!(store.pending_txs(&path).is_empty());
+        assert_eq!(
+            store.last_cleared_height(&path),
+            Some(Height::new(0, 1000).unwrap())
+        );
+    }
+}
@@ -10,28 +10,39 @@ use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
 use ibc_relayer_types::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::{
+        ics02_client::client_state::ClientState,
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    },
     events::IbcEvent,
     Height,
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
-    config::Config,
+    chain::{
+        endpoint::HealthCheck,
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryClientStateRequest, QueryHeight},
+        tracking::TrackingId,
+    },
+    config::{ChainConfig, Config},
     event::{
         monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
+        stream::{self, StreamEvent},
         IbcEventWithHeight,
     },
+    foreign_client::ForeignClient,
     object::Object,
-    registry::{Registry, SharedRegistry},
-    rest,
+    registry::{shutdown_runtime, Registry, SharedRegistry},
+    rest::{self, request::WorkerStatus, RestApiError},
+    store::Store,
     supervisor::scan::ScanMode,
     telemetry,
     util::{
         lock::LockExt,
         task::{spawn_background_task, Next, TaskError, TaskHandle},
     },
-    worker::WorkerMap,
+    worker::{WorkerHandle, WorkerMap},
 };
 
 pub mod client_state_filter;
@@ -47,7 +58,7 @@ pub mod scan;
 pub mod spawn;
 
 pub mod cmd;
-use cmd::SupervisorCmd;
+use cmd::{ConfigUpdate, SequenceExclusion, SupervisorCmd};
 
 use self::{scan::ChainScanner, spawn::SpawnContext};
 
@@ -131,6 +142,44 @@ impl SupervisorHandle {
 
         Ok(state)
     }
+
+    /// Ask the supervisor to apply the given [`ConfigUpdate`],
+    /// and wait for the outcome.
+    pub fn update_config(&self, update: ConfigUpdate) -> Result<(), Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::UpdateConfig(update, tx))
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
+
+    /// Ask the supervisor to stop relaying the given packet sequences,
+    /// and wait for the outcome.
+    pub fn exclude_sequences(&self, exclusion: SequenceExclusion) -> Result<(), Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ExcludeSequences(exclusion, tx))
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
+}
+
+/// The state shared between the tasks of the supervisor.
+///
+/// The configuration, and thus the set of chains the supervisor relays for,
+/// can be changed at runtime through a [`SupervisorCmd::UpdateConfig`] command.
+#[derive(Clone)]
+struct SupervisorContext<Chain: ChainHandle> {
+    config: Arc<RwLock<Config>>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    /// The tasks processing the event batches of each chain
+    batch_tasks: Arc<RwLock<HashMap<ChainId, TaskHandle>>>,
 }
 
 pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
@@ -144,7 +193,14 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         health_check(&config, &mut registry.write());
     }
 
-    let workers = Arc::new(RwLock::new(WorkerMap::new()));
+    let workers = if config.store.enabled {
+        let store = Store::from_config(&config.store).map_err(Error::store)?;
+        WorkerMap::with_store(store)
+    } else {
+        WorkerMap::new()
+    };
+
+    let workers = Arc::new(RwLock::new(workers));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
     let scan = chain_scanner(
@@ -166,70 +222,68 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
-    let batch_tasks = spawn_batch_workers(
-        &config,
-        registry.clone(),
+    let ctx = SupervisorContext {
+        config: Arc::new(RwLock::new(config)),
+        registry,
         client_state_filter,
-        workers.clone(),
-        subscriptions,
-    );
+        workers,
+        batch_tasks: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    for (chain, subscription) in subscriptions {
+        let batch_task = spawn_batch_worker(ctx.clone(), chain.clone(), subscription);
+        ctx.batch_tasks
+            .acquire_write()
+            .insert(chain.id(), batch_task);
+    }
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let cmd_task = spawn_cmd_worker(ctx.clone(), cmd_rx);
 
     let mut tasks = vec![cmd_task];
-    tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
+        let rest_task = spawn_rest_worker(ctx, rest_rx);
         tasks.push(rest_task);
     }
 
     Ok(tasks)
 }
 
-fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
-    registry: SharedRegistry<Chain>,
-    client_state_filter: Arc<RwLock<FilterPolicy>>,
-    workers: Arc<RwLock<WorkerMap>>,
-    subscriptions: Vec<(Chain, Subscription)>,
-) -> Vec<TaskHandle> {
-    let mut handles = Vec::with_capacity(subscriptions.len());
-
-    for (chain, subscription) in subscriptions {
-        let config = config.clone();
-        let registry = registry.clone();
-        let client_state_filter = client_state_filter.clone();
-        let workers = workers.clone();
-
-        let handle = spawn_background_task(
-            error_span!("worker.batch", chain = %chain.id()),
-            Some(Duration::from_millis(5)),
-            move || -> Result<Next, TaskError<Infallible>> {
-                if let Ok(batch) = subscription.try_recv() {
-                    handle_batch(
-                        &config,
-                        &mut registry.write(),
-                        &mut client_state_filter.acquire_write(),
-                        &mut workers.acquire_write(),
-                        chain.clone(),
-                        batch,
-                    );
+fn spawn_batch_worker<Chain: ChainHandle>(
+    ctx: SupervisorContext<Chain>,
+    chain: Chain,
+    subscription: Subscription,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.batch", chain = %chain.id()),
+        Some(Duration::from_millis(5)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if let Ok(batch) = subscription.try_recv() {
+                if let Ok(batch) = batch.as_ref() {
+                    stream::publish_with(|| StreamEvent::ChainEvents {
+                        chain_id: batch.chain_id.clone(),
+                        height: batch.height,
+                        events: batch.events.clone(),
+                    });
                 }
 
-                Ok(Next::Continue)
-            },
-        );
-
-        handles.push(handle);
-    }
+                handle_batch(
+                    &ctx.config.acquire_read(),
+                    &mut ctx.registry.write(),
+                    &mut ctx.client_state_filter.acquire_write(),
+                    &mut ctx.workers.acquire_write(),
+                    chain.clone(),
+                    batch,
+                );
+            }
 
-    handles
+            Ok(Next::Continue)
+        },
+    )
 }
 
-pub fn spawn_cmd_worker<Chain: ChainHandle>(
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+fn spawn_cmd_worker<Chain: ChainHandle>(
+    ctx: SupervisorContext<Chain>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -238,8 +292,16 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
         move || -> Result<Next, TaskError<Infallible>> {
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
+                    SupervisorCmd::UpdateConfig(update, reply_to) => {
+                        let result = update_config(&ctx, update);
+                        let _ = reply_to.try_send(result);
+                    }
                     SupervisorCmd::DumpState(reply_to) => {
-                        dump_state(&registry.read(), &workers.acquire_read(), reply_to);
+                        dump_state(&ctx.registry.read(), &ctx.workers.acquire_read(), reply_to);
+                    }
+                    SupervisorCmd::ExcludeSequences(exclusion, reply_to) => {
+                        let result = exclude_sequences(&ctx, exclusion);
+                        let _ = reply_to.try_send(result);
                     }
                 }
             }
@@ -249,17 +311,15 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
     )
 }
 
-pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+fn spawn_rest_worker<Chain: ChainHandle>(
+    ctx: SupervisorContext<Chain>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&ctx, &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -482,6 +542,28 @@ pub fn collect_events(
                     || Object::client_from_chan_open_events(&attributes, src_chain).ok(),
                 );
             }
+            IbcEvent::UpgradeInitChannel(..)
+            | IbcEvent::UpgradeTryChannel(..)
+            | IbcEvent::UpgradeAckChannel(..)
+            | IbcEvent::UpgradeConfirmChannel(..)
+            | IbcEvent::UpgradeOpenChannel(..)
+            | IbcEvent::UpgradeTimeoutChannel(..)
+            | IbcEvent::UpgradeCancelChannel(..) => {
+                // If handshake message relaying is enabled create worker to relay the upgrade handshake
+                collect_event(
+                    &mut collected,
+                    event_with_height.clone(),
+                    mode.channels.enabled,
+                    || {
+                        event_with_height
+                            .event
+                            .channel_upgrade_attributes()
+                            .and_then(|attr| {
+                                Object::channel_from_chan_upgrade_events(attr, src_chain).ok()
+                            })
+                    },
+                );
+            }
             IbcEvent::SendPacket(ref packet) => {
                 collect_event(
                     &mut collected,
@@ -635,30 +717,486 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 }
 
 fn handle_rest_requests<Chain: ChainHandle>(
-    config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    ctx: &SupervisorContext<Chain>,
     rest_rx: &rest::Receiver,
 ) {
-    if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+    // Release the lock on the configuration before handling the command,
+    // as the latter might need to update the configuration.
+    let cmd = rest::process_incoming_requests(&ctx.config.acquire_read(), rest_rx);
+
+    if let Some(cmd) = cmd {
+        handle_rest_cmd(ctx, cmd);
     }
 }
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
-fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
-    m: rest::Command,
-) {
+fn handle_rest_cmd<Chain: ChainHandle>(ctx: &SupervisorContext<Chain>, m: rest::Command) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&ctx.registry.read(), &ctx.workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::Workers(reply) => {
+            let workers = worker_statuses(&ctx.workers.acquire_read());
+
+            reply
+                .send(Ok(workers))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::PathBacklog {
+            chain_id,
+            port_id,
+            channel_id,
+            reply_to,
+        } => {
+            let result = with_packet_worker(ctx, &chain_id, &port_id, &channel_id, |_| ())
+                .and_then(|()| rest::path_backlog(&chain_id, &port_id, &channel_id));
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::UpdateConfig(update, reply) => {
+            let result =
+                update_config(ctx, update).map_err(|e| RestApiError::ConfigUpdate(e.to_string()));
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ClearPackets {
+            chain_id,
+            port_id,
+            channel_id,
+            reply_to,
+        } => {
+            let result = with_packet_worker(ctx, &chain_id, &port_id, &channel_id, |worker| {
+                worker.clear_pending_packets()
+            });
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::RefreshClient {
+            chain_id,
+            client_id,
+            force,
+            reply_to,
+        } => {
+            let result = schedule_client_refresh(ctx, chain_id, client_id, force);
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::PausePacketWorker {
+            chain_id,
+            port_id,
+            channel_id,
+            paused,
+            reply_to,
+        } => {
+            let result = with_packet_worker(ctx, &chain_id, &port_id, &channel_id, |worker| {
+                if paused {
+                    worker.pause()
+                } else {
+                    worker.resume()
+                }
+            });
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+    }
+}
+
+/// Returns the status of all the workers, along with
+/// the backlog of their path for the packet workers.
+fn worker_statuses(workers: &WorkerMap) -> Vec<WorkerStatus> {
+    workers
+        .handles()
+        .map(|worker| {
+            let backlog = match worker.object() {
+                Object::Packet(path) => {
+                    rest::path_backlog(&path.src_chain_id, &path.src_port_id, &path.src_channel_id)
+                        .ok()
+                }
+                _ => None,
+            };
+
+            WorkerStatus {
+                id: worker.id(),
+                object: worker.object().clone(),
+                data: worker.data().cloned(),
+                stopped: worker.is_stopped(),
+                backlog,
+            }
+        })
+        .sorted_by_key(|status| status.id)
+        .collect()
+}
+
+/// Apply the given function to the handle of the worker relaying
+/// the packets sent from the given chain on the given port and channel.
+fn with_packet_worker<Chain: ChainHandle, T>(
+    ctx: &SupervisorContext<Chain>,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    f: impl FnOnce(&WorkerHandle) -> T,
+) -> Result<T, RestApiError> {
+    let workers = ctx.workers.acquire_read();
+
+    let worker = workers.handles().find(|worker| match worker.object() {
+        Object::Packet(path) => {
+            &path.src_chain_id == chain_id
+                && &path.src_port_id == port_id
+                && &path.src_channel_id == channel_id
+        }
+        _ => false,
+    });
+
+    match worker {
+        Some(worker) => Ok(f(worker)),
+        None => Err(RestApiError::PacketWorkerNotFound(
+            chain_id.clone(),
+            port_id.clone(),
+            channel_id.clone(),
+        )),
+    }
+}
+
+/// Refresh the given client in the background, as doing so submits a transaction
+/// and waits for it to be committed, which must block neither the supervisor
+/// nor the REST server. The outcome of the refresh is only logged.
+fn schedule_client_refresh<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    chain_id: ChainId,
+    client_id: ClientId,
+    force: bool,
+) -> Result<(), RestApiError> {
+    if !ctx.config.acquire_read().has_chain(&chain_id) {
+        return Err(RestApiError::ChainConfigNotFound(chain_id));
+    }
+
+    let ctx = ctx.clone();
+
+    std::thread::spawn(
+        move || match refresh_client(&ctx, &chain_id, &client_id, force) {
+            Ok(events) => info!(
+                chain = %chain_id,
+                client = %client_id,
+                "client refresh emitted {} events",
+                events.len()
+            ),
+            Err(e) => error!(
+                chain = %chain_id,
+                client = %client_id,
+                "failed to refresh client: {}",
+                e
+            ),
+        },
+    );
+
+    Ok(())
+}
+
+/// Refresh the given client hosted on the given chain, or update it to the
+/// latest height of its target chain if `force` is set, and return the
+/// events emitted by the update, if any.
+#[instrument(
+    name = "supervisor.refresh_client",
+    level = "error",
+    skip_all,
+    fields(chain = %chain_id, client = %client_id)
+)]
+fn refresh_client<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+    force: bool,
+) -> Result<Vec<IbcEvent>, RestApiError> {
+    let error = |e: String| RestApiError::ClientRefresh(e);
+
+    let (host_chain, target_chain) = {
+        let mut registry = ctx.registry.write();
+
+        let host_chain = registry
+            .get_or_spawn(chain_id)
+            .map_err(|e| error(e.to_string()))?;
+
+        let (client_state, _) = host_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| error(e.to_string()))?;
+
+        let target_chain = registry
+            .get_or_spawn(&client_state.chain_id())
+            .map_err(|e| error(e.to_string()))?;
+
+        (host_chain, target_chain)
+    };
+
+    let mut client = ForeignClient::restore(client_id.clone(), host_chain, target_chain);
+
+    info!(force, "refreshing client");
+
+    let events = if force {
+        client.build_latest_update_client_and_send()
+    } else {
+        client.refresh().map(Option::unwrap_or_default)
+    };
+
+    events.map_err(|e| error(e.to_string()))
+}
+
+/// Apply the given [`ConfigUpdate`] to the running supervisor.
+#[instrument(
+    name = "supervisor.update_config",
+    level = "error",
+    skip_all,
+    fields(chain = %update.chain_id())
+)]
+fn update_config<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    update: ConfigUpdate,
+) -> Result<(), Error> {
+    match update {
+        ConfigUpdate::Add(chain_config) => add_chain(ctx, chain_config),
+        ConfigUpdate::Remove(chain_id) => remove_chain(ctx, &chain_id),
+        ConfigUpdate::Update(chain_config) => update_chain(ctx, chain_config),
+    }
+}
+
+/// Add the given chain to the configuration, spawn its runtime,
+/// spawn the workers for the objects it is involved in,
+/// and start processing the events it emits.
+fn add_chain<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    chain_config: ChainConfig,
+) -> Result<(), Error> {
+    let chain_id = chain_config.id.clone();
+
+    let current = ctx.config.acquire_read().clone();
+    if current.has_chain(&chain_id) {
+        return Err(Error::chain_already_configured(chain_id));
+    }
+
+    info!("adding chain");
+
+    let mut config = current.clone();
+    config.chains.push(chain_config.clone());
+
+    // Subscribe to the events of the chain before spawning any worker for it,
+    // so that the registry is the only state to roll back if anything fails.
+    let (chain, subscription) = {
+        let mut registry = ctx.registry.write();
+        registry.set_config(config.clone());
+
+        let spawned = registry
+            .get_or_spawn(&chain_id)
+            .map_err(Error::spawn)
+            .and_then(|chain| {
+                let subscription = chain.subscribe().map_err(Error::relayer)?;
+                Ok((chain, subscription))
+            });
+
+        match spawned {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                registry.shutdown(&chain_id);
+                registry.set_config(current);
+                return Err(e);
+            }
+        }
+    };
+
+    *ctx.config.acquire_write() = config.clone();
+
+    start_chain(ctx, &config, &chain_config, chain, subscription);
+
+    Ok(())
+}
+
+/// Scan the given chain, spawn the workers for the objects it is involved in,
+/// and start processing the events it emits through the given subscription.
+fn start_chain<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    config: &Config,
+    chain_config: &ChainConfig,
+    chain: Chain,
+    subscription: Subscription,
+) {
+    let scan = chain_scanner(
+        config,
+        &mut ctx.registry.write(),
+        &mut ctx.client_state_filter.acquire_write(),
+        ScanMode::Auto,
+    )
+    .scan_chain(chain_config);
+
+    match scan {
+        Ok(scan) => {
+            spawn_context(
+                config,
+                &mut ctx.registry.write(),
+                &mut ctx.workers.acquire_write(),
+            )
+            .spawn_workers_for_chain(scan);
+        }
+        Err(e) => error!(
+            "failed to scan chain, workers will be spawned on demand: {}",
+            e
+        ),
+    }
+
+    let batch_task = spawn_batch_worker(ctx.clone(), chain, subscription);
+    ctx.batch_tasks
+        .acquire_write()
+        .insert(chain_config.id.clone(), batch_task);
+}
+
+/// Stop processing the events emitted by the given chain,
+/// and shut down the workers for all objects it is involved in.
+fn stop_chain<Chain: ChainHandle>(ctx: &SupervisorContext<Chain>, chain_id: &ChainId) {
+    // Dropping the batch task waits for it to terminate, so make sure
+    // not to hold any lock it might be waiting on while doing so.
+    let batch_task = ctx.batch_tasks.acquire_write().remove(chain_id);
+    drop(batch_task);
+
+    let mut workers = ctx.workers.acquire_write();
+    for object in workers.objects_for_chain(chain_id) {
+        debug!("shutting down worker {}", object.short_name());
+        workers.shutdown_worker(&object);
+    }
+}
+
+/// Stop processing the events emitted by the given chain, shut down
+/// the workers for all objects it is involved in as well as its runtime,
+/// and remove it from the configuration.
+fn remove_chain<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    chain_id: &ChainId,
+) -> Result<(), Error> {
+    let mut config = ctx.config.acquire_read().clone();
+    if !config.has_chain(chain_id) {
+        return Err(Error::chain_not_configured(chain_id.clone()));
+    }
+
+    info!("removing chain");
+
+    config.chains.retain(|c| &c.id != chain_id);
+
+    stop_chain(ctx, chain_id);
+
+    *ctx.config.acquire_write() = config.clone();
+
+    let mut registry = ctx.registry.write();
+    registry.set_config(config);
+    registry.shutdown(chain_id);
+
+    Ok(())
+}
+
+/// Replace the configuration of the given chain.
+///
+/// The runtime of the chain is first spawned with its new configuration and subscribed
+/// to its events, and only then replaces the current runtime, along with the workers
+/// for the objects the chain is involved in. If the new runtime cannot be spawned,
+/// the chain keeps running with its current configuration.
+fn update_chain<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    chain_config: ChainConfig,
+) -> Result<(), Error> {
+    let chain_id = chain_config.id.clone();
+
+    let mut config = ctx.config.acquire_read().clone();
+    match config.find_chain_mut(&chain_id) {
+        Some(current) => *current = chain_config.clone(),
+        None => return Err(Error::chain_not_configured(chain_id)),
+    }
+
+    info!("updating chain");
+
+    let chain = ctx
+        .registry
+        .read()
+        .spawn_unregistered(&config, &chain_id)
+        .map_err(Error::spawn)?;
+
+    let subscription = match chain.subscribe() {
+        Ok(subscription) => subscription,
+        Err(e) => {
+            shutdown_runtime(&chain_id, chain);
+            return Err(Error::relayer(e));
+        }
+    };
+
+    stop_chain(ctx, &chain_id);
+
+    *ctx.config.acquire_write() = config.clone();
+
+    {
+        let mut registry = ctx.registry.write();
+        registry.set_config(config.clone());
+        registry.replace(&chain_id, chain.clone());
+    }
+
+    start_chain(ctx, &config, &chain_config, chain, subscription);
+
+    Ok(())
+}
+
+/// Stop relaying the given packet sequences, both in the packet workers
+/// currently running and in the ones spawned later on.
+fn exclude_sequences<Chain: ChainHandle>(
+    ctx: &SupervisorContext<Chain>,
+    exclusion: SequenceExclusion,
+) -> Result<(), Error> {
+    let SequenceExclusion {
+        chain_id,
+        port_id,
+        channel_id,
+        sequences,
+    } = exclusion;
+
+    let config = {
+        let mut config = ctx.config.acquire_write();
+
+        let chain_config = config
+            .find_chain_mut(&chain_id)
+            .ok_or_else(|| Error::chain_not_configured(chain_id.clone()))?;
+
+        chain_config
+            .excluded_sequences
+            .exclude(&port_id, &channel_id, sequences.iter().copied());
+
+        config.clone()
+    };
+
+    ctx.registry.write().set_config(config);
+
+    for worker in ctx.workers.acquire_read().workers_for_chain(&chain_id) {
+        if let Object::Packet(_) = worker.object() {
+            worker.exclude_sequences(
+                chain_id.clone(),
+                port_id.clone(),
+                channel_id.clone(),
+                sequences.clone(),
+            );
+        }
     }
+
+    Ok(())
 }
 
 #[instrument(
@@ -839,6 +1377,12 @@ fn handle_batch<Chain: ChainHandle>(
             let _ = clear_pending_packets(workers, &chain_id)
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
+        Err(EventError(EventErrorDetail::BlockResultsUnavailable(_), _)) => {
+            warn!("events of a block were skipped, clearing pending packets");
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
         Err(e) => {
             error!("error when receiving event batch: {}", e)
         }
@@ -1,8 +1,115 @@
 use crossbeam_channel::Sender;
 
-use super::dump_state::SupervisorState;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::config::ChainConfig;
+
+use super::{dump_state::SupervisorState, Error};
+
+/// A change to the set of chains a running supervisor is relaying for.
+#[derive(Clone, Debug)]
+pub enum ConfigUpdate {
+    /// Start relaying for a new chain.
+    Add(ChainConfig),
+
+    /// Stop relaying for the chain with the given identifier.
+    Remove(ChainId),
+
+    /// Replace the configuration of an existing chain.
+    Update(ChainConfig),
+}
+
+impl ConfigUpdate {
+    /// The identifier of the chain affected by this update.
+    pub fn chain_id(&self) -> &ChainId {
+        match self {
+            Self::Add(config) | Self::Update(config) => &config.id,
+            Self::Remove(chain_id) => chain_id,
+        }
+    }
+
+    /// Compute the updates needed to go from the `current` set of
+    /// chain configurations to the `new` one.
+    ///
+    /// Chains which are present in both sets are only updated
+    /// if their configuration actually differs.
+    pub fn diff(current: &[ChainConfig], new: &[ChainConfig]) -> Vec<Self> {
+        let mut updates: Vec<Self> = current
+            .iter()
+            .filter(|c| !new.iter().any(|n| n.id == c.id))
+            .map(|c| Self::Remove(c.id.clone()))
+            .collect();
+
+        for chain_config in new {
+            match current.iter().find(|c| c.id == chain_config.id) {
+                None => updates.push(Self::Add(chain_config.clone())),
+                Some(c) if !same_chain_config(c, chain_config) => {
+                    updates.push(Self::Update(chain_config.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        updates
+    }
+}
+
+/// `ChainConfig` does not implement `PartialEq`, so we compare
+/// the serialized representations of both configurations instead.
+fn same_chain_config(a: &ChainConfig, b: &ChainConfig) -> bool {
+    match (toml::to_string(a), toml::to_string(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Packet sequences to stop relaying, for the packets sent
+/// from the given chain on the given port and channel.
+#[derive(Clone, Debug)]
+pub struct SequenceExclusion {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequences: Vec<Sequence>,
+}
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
+    UpdateConfig(ConfigUpdate, Sender<Result<(), Error>>),
     DumpState(Sender<SupervisorState>),
+    ExcludeSequences(SequenceExclusion, Sender<Result<(), Error>>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigUpdate;
+    use crate::config::{load, types::MaxMsgNum};
+
+    #[test]
+    fn diff_chain_configs() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let current = load(path).expect("could not parse config").chains;
+        let (chain_a, chain_b) = (current[0].clone(), current[1].clone());
+
+        assert!(ConfigUpdate::diff(&current, &current).is_empty());
+
+        let mut chain_c = chain_b.clone();
+        chain_c.id = "chain_C".parse().unwrap();
+
+        let mut updated_a = chain_a.clone();
+        updated_a.max_msg_num = MaxMsgNum::new(5).unwrap();
+
+        let new = vec![updated_a, chain_c];
+        let updates = ConfigUpdate::diff(&current, &new);
+
+        assert_eq!(updates.len(), 3);
+        assert!(matches!(&updates[0], ConfigUpdate::Remove(id) if *id == chain_b.id));
+        assert!(matches!(&updates[1], ConfigUpdate::Update(c) if c.id == chain_a.id));
+        assert!(matches!(&updates[2], ConfigUpdate::Add(c) if c.id.as_str() == "chain_C"));
+    }
 }
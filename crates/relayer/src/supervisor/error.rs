@@ -5,6 +5,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, Connec
 
 use crate::error::Error as RelayerError;
 use crate::spawn::SpawnError;
+use crate::store::StoreError;
 use crate::supervisor::scan::Error as ScanError;
 
 define_error! {
@@ -62,6 +63,14 @@ define_error! {
         NoChainsAvailable
             |_| { "supervisor was not able to connect to any chains" },
 
+        ChainAlreadyConfigured
+            { chain_id: ChainId }
+            |e| { format_args!("chain {} is already part of the supervisor configuration", e.chain_id) },
+
+        ChainNotConfigured
+            { chain_id: ChainId }
+            |e| { format_args!("chain {} is not part of the supervisor configuration", e.chain_id) },
+
         Spawn
             [ SpawnError ]
             |_| { "supervisor was not able to spawn chain runtime" },
@@ -70,6 +79,10 @@ define_error! {
             [ ScanError ]
             |_| { "supervisor encountered an error when scanning chains" },
 
+        Store
+            [ StoreError ]
+            |_| { "supervisor was not able to open the store" },
+
         HandleSend
             |_| { "failed to send a command to the supervisor through a channel" },
 
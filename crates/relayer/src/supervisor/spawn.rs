@@ -2,11 +2,15 @@ use tracing::{error, info};
 
 use ibc_relayer_types::core::{
     ics02_client::client_state::ClientState, ics03_connection::connection::IdentifiedConnectionEnd,
-    ics04_channel::channel::State as ChannelState,
+    ics04_channel::channel::State as ChannelState, ics04_channel::packet::Sequence,
 };
 
 use crate::{
-    chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
+    chain::{
+        counterparty::connection_state_on_destination,
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryHeight, QueryUpgradeRequest},
+    },
     client_state::IdentifiedAnyClientState,
     config::Config,
     object::{Channel, Client, Connection, Object, Packet, Wallet},
@@ -244,9 +248,12 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             chan_state_dst
         );
 
-        if (mode.clients.enabled || mode.packets.enabled)
-            && chan_state_src.is_open()
-            && chan_state_dst.is_open()
+        // Packets keep flowing on a channel while it is being upgraded
+        let is_usable = |state: ChannelState| state.is_open() || state.is_upgrading();
+
+        if (mode.clients.enabled || mode.packets.enabled || mode.channels.enabled)
+            && is_usable(chan_state_src)
+            && is_usable(chan_state_dst)
         {
             if mode.clients.enabled {
                 // Spawn the client worker
@@ -302,6 +309,49 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 }
             }
 
+            if mode.channels.enabled {
+                // Initializing an upgrade bumps the upgrade sequence of the channel end,
+                // so the channels which were never upgraded have no upgrade in progress
+                let was_upgraded = channel_scan.channel.channel_end.upgrade_sequence
+                    != Sequence::default()
+                    || channel_scan.counterparty.as_ref().map_or(false, |c| {
+                        c.channel_end.upgrade_sequence != Sequence::default()
+                    });
+
+                let has_upgrade = || {
+                    was_upgraded
+                        && chain
+                            .query_upgrade(
+                                QueryUpgradeRequest {
+                                    port_id: channel_scan.channel.port_id.clone(),
+                                    channel_id: channel_scan.channel.channel_id.clone(),
+                                    height: QueryHeight::Latest,
+                                },
+                                IncludeProof::No,
+                            )
+                            .is_ok()
+                };
+
+                // If there is an upgrade in progress, spawn the worker relaying its handshake
+                if chan_state_src.is_upgrading() || chan_state_dst.is_upgrading() || has_upgrade() {
+                    let channel_object = Object::Channel(Channel {
+                        dst_chain_id: counterparty_chain.id(),
+                        src_chain_id: chain.id(),
+                        src_channel_id: channel_scan.channel.channel_id.clone(),
+                        src_port_id: channel_scan.channel.port_id.clone(),
+                    });
+
+                    self.workers
+                        .spawn(
+                            chain.clone(),
+                            counterparty_chain.clone(),
+                            &channel_object,
+                            self.config,
+                        )
+                        .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
+                }
+            }
+
             Ok(mode.clients.enabled)
         } else if mode.channels.enabled
             && !chan_state_dst.is_open()
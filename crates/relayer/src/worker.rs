@@ -1,16 +1,18 @@
 use alloc::sync::Arc;
 use core::fmt::{Display, Error as FmtError, Formatter};
+use core::sync::atomic::AtomicBool;
 use ibc_relayer_types::core::ics04_channel::channel::Order;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 use tracing::error;
 
 use crate::foreign_client::ForeignClient;
-use crate::link::{Link, LinkParameters, Resubmit};
+use crate::link::{Link, LinkParameters, RelayPath, Resubmit};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store::Store,
 };
 
 pub mod retry_strategy;
@@ -59,6 +61,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     id: WorkerId,
     object: Object,
     config: &Config,
+    store: Option<Store>,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
@@ -123,7 +126,15 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             );
 
             match link_res {
-                Ok(link) => {
+                Ok(mut link) => {
+                    exclude_configured_sequences(&mut link.a_to_b, config);
+                    link.a_to_b
+                        .set_record_packet_latency(config.telemetry.enabled);
+
+                    if let Some(store) = store {
+                        link.a_to_b.set_store(store);
+                    }
+
                     let channel_ordering = link.a_to_b.channel().ordering;
                     let should_clear_on_start =
                         packets_config.clear_on_start || channel_ordering == Order::Ordered;
@@ -131,6 +142,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
                     let resubmit = Resubmit::from_clear_interval(packets_config.clear_interval);
+                    let paused = Arc::new(AtomicBool::new(false));
 
                     let packet_task = packet::spawn_packet_cmd_worker(
                         cmd_rx,
@@ -138,10 +150,12 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                         should_clear_on_start,
                         packets_config.clear_interval,
                         path.clone(),
+                        paused.clone(),
                     );
                     task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
+                    let link_task =
+                        packet::spawn_packet_worker(path.clone(), link, resubmit, paused);
                     task_handles.push(link_task);
 
                     (Some(cmd_tx), None)
@@ -178,3 +192,38 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
     WorkerHandle::new(id, object, data, cmd_tx, task_handles)
 }
+
+/// Exclude from relaying on the given path the packet sequences listed in the
+/// configuration of its chains. The chain runtimes only know about the sequences
+/// excluded at startup, while the given configuration also includes the ones
+/// excluded at runtime through the supervisor.
+fn exclude_configured_sequences<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relay_path: &mut RelayPath<ChainA, ChainB>,
+    config: &Config,
+) {
+    let ends = [
+        (
+            relay_path.src_chain().id(),
+            relay_path.src_port_id().clone(),
+            relay_path.src_channel_id().clone(),
+        ),
+        (
+            relay_path.dst_chain().id(),
+            relay_path.dst_port_id().clone(),
+            relay_path.dst_channel_id().clone(),
+        ),
+    ];
+
+    for (chain_id, port_id, channel_id) in ends {
+        let sequences: Vec<_> = config
+            .find_chain(&chain_id)
+            .map(|c| c.excluded_sequences.for_channel(&port_id, &channel_id))
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        if !sequences.is_empty() {
+            relay_path.exclude_sequences(&chain_id, &port_id, &channel_id, &sequences);
+        }
+    }
+}
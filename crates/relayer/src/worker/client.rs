@@ -141,6 +141,8 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                     WorkerCmd::NewBlock { .. } => {}
                     WorkerCmd::ClearPendingPackets => {}
+                    WorkerCmd::ExcludeSequences { .. } => {}
+                    WorkerCmd::Pause | WorkerCmd::Resume => {}
                 }
             }
 
@@ -1,6 +1,11 @@
 use core::fmt::{Display, Error as FmtError, Formatter};
 
-use ibc_relayer_types::{core::ics02_client::events::NewBlock, Height};
+use itertools::Itertools;
+
+use ibc_relayer_types::core::ics02_client::events::NewBlock;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::Height;
 
 use crate::event::monitor::EventBatch;
 
@@ -15,6 +20,21 @@ pub enum WorkerCmd {
 
     /// Trigger a pending packets clear
     ClearPendingPackets,
+
+    /// Stop relaying the packets with the given sequences,
+    /// sent from the given chain on the given port and channel
+    ExcludeSequences {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequences: Vec<Sequence>,
+    },
+
+    /// Stop relaying packets until a `Resume` command is received
+    Pause,
+
+    /// Resume relaying packets, and clear the ones sent while paused
+    Resume,
 }
 
 impl Display for WorkerCmd {
@@ -31,6 +51,21 @@ impl Display for WorkerCmd {
                 write!(f, "NewBlock({}, {})", height, new_block)
             }
             WorkerCmd::ClearPendingPackets => write!(f, "CleaPendingPackets"),
+            WorkerCmd::ExcludeSequences {
+                chain_id,
+                port_id,
+                channel_id,
+                sequences,
+            } => write!(
+                f,
+                "ExcludeSequences({}, {}/{}, {})",
+                chain_id,
+                port_id,
+                channel_id,
+                sequences.iter().join(", ")
+            ),
+            WorkerCmd::Pause => write!(f, "Pause"),
+            WorkerCmd::Resume => write!(f, "Resume"),
         }
     }
 }
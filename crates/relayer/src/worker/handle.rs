@@ -7,7 +7,11 @@ use serde::Serialize;
 use tracing::{debug, trace};
 
 use ibc_relayer_types::{
-    core::{ics02_client::events::NewBlock, ics24_host::identifier::ChainId},
+    core::{
+        ics02_client::events::NewBlock,
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChainId, ChannelId, PortId},
+    },
     Height,
 };
 
@@ -91,6 +95,33 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::ClearPendingPackets);
     }
 
+    /// Instruct the worker to stop relaying the packets with the given
+    /// sequences, sent from the given chain on the given port and channel.
+    pub fn exclude_sequences(
+        &self,
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequences: Vec<Sequence>,
+    ) {
+        self.try_send_command(WorkerCmd::ExcludeSequences {
+            chain_id,
+            port_id,
+            channel_id,
+            sequences,
+        });
+    }
+
+    /// Instruct the worker to stop relaying packets.
+    pub fn pause(&self) {
+        self.try_send_command(WorkerCmd::Pause);
+    }
+
+    /// Instruct the worker to resume relaying packets.
+    pub fn resume(&self) {
+        self.try_send_command(WorkerCmd::Resume);
+    }
+
     /// Shutdown all worker tasks without waiting for them to terminate.
     pub fn shutdown(&self) {
         for task in self.task_handles.iter() {
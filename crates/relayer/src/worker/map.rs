@@ -10,6 +10,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store::Store,
     telemetry,
 };
 
@@ -20,6 +21,7 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    store: Option<Store>,
 }
 
 impl Default for WorkerMap {
@@ -27,6 +29,7 @@ impl Default for WorkerMap {
         Self {
             workers: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
+            store: None,
         }
     }
 }
@@ -39,6 +42,16 @@ impl WorkerMap {
         Self::default()
     }
 
+    /// Create a new worker map, whose packet workers record their
+    /// pending transactions and clearing heights in the given store.
+    pub fn with_store(store: Store) -> Self {
+        Self {
+            workers: HashMap::new(),
+            latest_worker_id: WorkerId::new(0),
+            store: Some(store),
+        }
+    }
+
     /// Returns `true` if there is a spawned [`WorkerHandle`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
@@ -166,6 +179,7 @@ impl WorkerMap {
             self.next_worker_id(),
             object.clone(),
             config,
+            self.store.clone(),
         )
     }
 
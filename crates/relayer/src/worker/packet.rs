@@ -1,8 +1,9 @@
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::time::Duration;
 use std::sync::{Arc, Mutex};
 
 use crossbeam_channel::Receiver;
-use tracing::{error, error_span, trace};
+use tracing::{debug, error, error_span, info, trace};
 
 use ibc_relayer_types::Height;
 
@@ -30,11 +31,14 @@ fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
 
 /// Spawns a packet worker task in the background that handles the work of
 /// processing pending txs between `ChainA` and `ChainB`.
+///
+/// Nothing is processed while the `paused` flag is set.
 pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     path: Packet,
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     resubmit: Resubmit,
+    paused: Arc<AtomicBool>,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -48,6 +52,10 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
+        if paused.load(Ordering::SeqCst) {
+            return Ok(Next::Continue);
+        }
+
         handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
         Ok(Next::Continue)
     })
@@ -60,6 +68,7 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut should_clear_on_start: bool,
     clear_interval: u64,
     path: Packet,
+    paused: Arc<AtomicBool>,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -84,6 +93,7 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                 &mut should_clear_on_start,
                 clear_interval,
                 &path,
+                &paused,
                 cmd,
             )?;
         }
@@ -102,6 +112,17 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 ///
 /// Given a `ClearPendingPackets` command, clears pending packets.
 ///
+/// Unless explicitly requested with a `ClearPendingPackets` command, packet clearing
+/// is postponed while the transactions submitted before the relayer restarted are
+/// not confirmed, and clearing on start is skipped if it was done recently.
+///
+/// Given an `ExcludeSequences` command, stops relaying the packets
+/// with the given sequences.
+///
+/// Given a `Pause` command, stops relaying packets: the events and blocks received
+/// until a `Resume` command are dropped, and the packets sent in the meantime are
+/// relayed by clearing the pending packets on resume.
+///
 /// Regardless of the incoming command, this method also refreshes and
 /// and executes any scheduled operational data that is ready.
 fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
@@ -109,10 +130,23 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     should_clear_on_start: &mut bool,
     clear_interval: u64,
     path: &Packet,
+    paused: &AtomicBool,
     cmd: WorkerCmd,
 ) -> Result<(), TaskError<RunError>> {
+    if paused.load(Ordering::SeqCst)
+        && matches!(
+            cmd,
+            WorkerCmd::IbcEvents { .. }
+                | WorkerCmd::NewBlock { .. }
+                | WorkerCmd::ClearPendingPackets
+        )
+    {
+        trace!("packet relaying is paused, dropping command: {}", cmd);
+        return Ok(());
+    }
+
     // Handle packet clearing which is triggered from a command
-    let (do_clear, maybe_height) = match &cmd {
+    let (mut do_clear, maybe_height) = match &cmd {
         WorkerCmd::IbcEvents { batch } => {
             if *should_clear_on_start {
                 (true, Some(batch.height))
@@ -132,8 +166,51 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
 
         WorkerCmd::ClearPendingPackets => (true, None),
+
+        WorkerCmd::ExcludeSequences {
+            chain_id,
+            port_id,
+            channel_id,
+            sequences,
+        } => {
+            link.a_to_b
+                .exclude_sequences(chain_id, port_id, channel_id, sequences);
+
+            (false, None)
+        }
+
+        WorkerCmd::Pause => {
+            info!("pausing packet relaying");
+            paused.store(true, Ordering::SeqCst);
+
+            (false, None)
+        }
+
+        WorkerCmd::Resume => {
+            if paused.swap(false, Ordering::SeqCst) {
+                info!("resuming packet relaying, clearing the packets sent while paused");
+                (true, None)
+            } else {
+                (false, None)
+            }
+        }
     };
 
+    if do_clear && !matches!(cmd, WorkerCmd::ClearPendingPackets) {
+        if link.a_to_b.has_recovered_txs() {
+            // Clearing now would resubmit the packets relayed by these transactions
+            debug!("postponing packet clearing until the transactions submitted before restart are confirmed");
+            do_clear = false;
+        } else if *should_clear_on_start && cleared_recently(link, clear_interval, maybe_height) {
+            debug!(
+                "skipping packet clearing on start, packets were cleared less than {} blocks ago",
+                clear_interval
+            );
+            *should_clear_on_start = false;
+            do_clear = false;
+        }
+    }
+
     if do_clear {
         // Reset the `clear_on_start` flag and attempt packet clearing once now.
         // More clearing will be done at clear interval.
@@ -161,6 +238,24 @@ fn should_clear_packets(clear_interval: u64, height: Height) -> bool {
     clear_interval != 0 && height.revision_height() % clear_interval == 0
 }
 
+/// Whether or not the packets were cleared, before the relayer restarted, less than
+/// `clear_interval` blocks before the given height, as recorded in the store.
+/// In that case, the packets left to clear will be cleared at the next interval.
+fn cleared_recently<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+    clear_interval: u64,
+    height: Option<Height>,
+) -> bool {
+    match (link.a_to_b.last_cleared_height(), height) {
+        (Some(cleared), Some(height)) => {
+            clear_interval != 0
+                && cleared.revision_number() == height.revision_number()
+                && height.revision_height() < cleared.revision_height() + clear_interval
+        }
+        _ => false,
+    }
+}
+
 fn handle_update_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     clear_interval: u64,
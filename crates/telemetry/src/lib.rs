@@ -6,23 +6,33 @@ pub mod server;
 pub mod state;
 
 use alloc::sync::Arc;
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 use std::{
     error::Error,
     net::{SocketAddr, ToSocketAddrs},
     thread::JoinHandle,
 };
 
-pub use crate::state::TelemetryState;
+pub use crate::state::{LatencyBuckets, TelemetryState};
 
 pub fn new_state() -> Arc<TelemetryState> {
     Arc::new(TelemetryState::default())
 }
 
-static GLOBAL_STATE: Lazy<Arc<TelemetryState>> = Lazy::new(new_state);
+static GLOBAL_STATE: OnceCell<Arc<TelemetryState>> = OnceCell::new();
 
 pub fn global() -> &'static Arc<TelemetryState> {
-    &GLOBAL_STATE
+    GLOBAL_STATE.get_or_init(new_state)
+}
+
+/// Initialize the global telemetry state with the given buckets for the latency histograms.
+///
+/// Returns `false` if the global state was already initialized, in which case
+/// the given buckets are ignored.
+pub fn init_global(latency_buckets: LatencyBuckets) -> bool {
+    GLOBAL_STATE
+        .set(Arc::new(TelemetryState::new(latency_buckets)))
+        .is_ok()
 }
 
 pub fn spawn<A>(
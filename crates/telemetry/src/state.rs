@@ -4,13 +4,16 @@ use std::time::{Duration, Instant};
 use dashmap::DashMap;
 use opentelemetry::{
     global,
-    metrics::{Counter, ObservableGauge, UpDownCounter},
+    metrics::{Counter, Histogram, ObservableGauge, UpDownCounter},
     Context, KeyValue,
 };
 use opentelemetry_prometheus::PrometheusExporter;
 use prometheus::proto::MetricFamily;
 
-use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, PortChannelId, PortId,
+};
+use ibc_relayer_types::Height;
 
 use tendermint::Time;
 
@@ -79,6 +82,54 @@ impl Display for WorkerType {
     }
 }
 
+/// The outcome of the last attempts at relaying packets on a path.
+#[derive(Clone, Debug, Default)]
+struct PathActivity {
+    last_relayed_height: Option<Height>,
+    last_error: Option<String>,
+}
+
+/// A snapshot of the packets pending on a path, and of the relaying activity on it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathStatus {
+    /// The sequence numbers of the pending packets, along with the local timestamp
+    /// at which their SendPacket event was observed, in seconds since the unix epoch.
+    /// Sorted by increasing sequence number.
+    pub pending: Vec<(u64, u64)>,
+
+    /// The height of the last event relayed from this end of the path.
+    pub last_relayed_height: Option<Height>,
+
+    /// The last error which occurred while relaying from this end of the path.
+    pub last_error: Option<String>,
+}
+
+/// The upper bounds of the buckets of the latency histograms, in milliseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyBuckets {
+    /// Buckets of the `tx_latency_submitted` histogram
+    pub submitted: Vec<f64>,
+
+    /// Buckets of the `tx_latency_confirmed` histogram
+    pub confirmed: Vec<f64>,
+
+    /// Buckets of the `packet_latency` histogram
+    pub packet: Vec<f64>,
+}
+
+/// # IMPORTANT: Remember to update the Hermes guide & the default config.toml whenever these values change.
+impl Default for LatencyBuckets {
+    fn default() -> Self {
+        Self {
+            submitted: vec![200.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0],
+            confirmed: vec![1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0],
+            packet: vec![
+                5000.0, 10000.0, 20000.0, 30000.0, 60000.0, 120000.0, 300000.0, 600000.0,
+            ],
+        }
+    }
+}
+
 pub struct TelemetryState {
     exporter: PrometheusExporter,
 
@@ -115,18 +166,46 @@ pub struct TelemetryState {
     /// Number of messages submitted to a specific chain
     total_messages_submitted: Counter<u64>,
 
+    /// The latest height reported by each RPC and gRPC endpoint of a chain
+    /// at the last health check, or 0 if the endpoint is unhealthy
+    endpoint_height: ObservableGauge<u64>,
+
+    /// Number of times Hermes switched to another RPC, gRPC or WebSocket endpoint of a chain
+    endpoint_failovers: Counter<u64>,
+
     /// The balance of each wallet Hermes uses per chain
     wallet_balance: ObservableGauge<f64>,
 
+    /// The gas price used for the last transaction submitted to each chain,
+    /// when the gas price is queried from the chain's fee market module
+    dynamic_gas_price: ObservableGauge<f64>,
+
+    /// The fees paid by the transactions submitted to each chain,
+    /// per denom, message type and channel
+    tx_fees_paid: Counter<u64>,
+
+    /// The gas limit of the transactions committed by each chain,
+    /// per message type and channel
+    tx_gas_wanted: Counter<u64>,
+
+    /// The gas used by the transactions committed by each chain,
+    /// per message type and channel
+    tx_gas_used: Counter<u64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
-    tx_latency_submitted: ObservableGauge<u64>,
+    tx_latency_submitted: Histogram<u64>,
 
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were confirmed. Milliseconds.
-    tx_latency_confirmed: ObservableGauge<u64>,
+    tx_latency_confirmed: Histogram<u64>,
+
+    /// The end-to-end latency for relaying packets, i.e. the difference between the time
+    /// of the block including the SendPacket event and the moment when the transaction
+    /// including the corresponding RecvPacket message was confirmed. Milliseconds.
+    packet_latency: Histogram<u64>,
 
     /// Records the time at which we started processing an event batch.
     /// Used for computing the `tx_latency` metric.
@@ -147,6 +226,9 @@ pub struct TelemetryState {
     /// Number of WriteAcknowledgement events received during the initial and periodic clearing
     cleared_acknowledgment_events: Counter<u64>,
 
+    /// Number of packets not received because they did not pay enough ICS-29 fees
+    skipped_unincentivized: Counter<u64>,
+
     /// Records the sequence number of the oldest pending packet. This corresponds to
     /// the sequence number of the oldest SendPacket event for which no
     /// WriteAcknowledgement or Timeout events have been received. The value is 0 if all the
@@ -168,6 +250,11 @@ pub struct TelemetryState {
     /// that the relayer observed, and for which there was no associated Acknowledgement or
     /// Timeout event.
     backlogs: DashMap<PathIdentifier, DashMap<u64, u64>>,
+
+    /// Stores the outcome of the last attempts at relaying packets, for all the paths
+    /// the relayer is active on. Along with the backlogs, it is exposed per path
+    /// through [`TelemetryState::path_status`] rather than as metrics.
+    path_activities: DashMap<PathIdentifier, PathActivity>,
 }
 
 impl TelemetryState {
@@ -437,6 +524,34 @@ impl TelemetryState {
         self.ws_events.add(&cx, count, labels);
     }
 
+    /// The latest height reported by the given endpoint of the given chain,
+    /// or 0 if the endpoint failed its last health check.
+    pub fn endpoint_height(&self, chain_id: &ChainId, kind: &str, address: &str, height: u64) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("kind", kind.to_string()),
+            KeyValue::new("address", address.to_string()),
+        ];
+
+        self.endpoint_height.observe(&cx, height, labels);
+    }
+
+    /// Hermes switched from one endpoint of the given chain to another
+    pub fn endpoint_failover(&self, chain_id: &ChainId, kind: &str, from: &str, to: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("kind", kind.to_string()),
+            KeyValue::new("from", from.to_string()),
+            KeyValue::new("to", to.to_string()),
+        ];
+
+        self.endpoint_failovers.add(&cx, 1, labels);
+    }
+
     /// How many messages Hermes submitted to the chain
     pub fn total_messages_submitted(&self, chain_id: &ChainId, count: u64) {
         let cx = Context::current();
@@ -460,6 +575,79 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// The gas price used for the last transaction submitted to the given chain,
+    /// after querying the fee market module and applying the multiplier and cap.
+    pub fn dynamic_gas_price(&self, chain_id: &ChainId, denom: &str, price: f64) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.dynamic_gas_price.observe(&cx, price, labels);
+    }
+
+    /// The fees paid by a transaction submitted to the given chain, in the given denom.
+    ///
+    /// The channel labels are empty for transactions which do not relay packets.
+    pub fn tx_fees_paid(
+        &self,
+        chain_id: &ChainId,
+        msg_type: &str,
+        path: Option<&PortChannelId>,
+        denom: &str,
+        amount: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("msg_type", msg_type.to_string()),
+            KeyValue::new(
+                "port",
+                path.map(|p| p.port_id.to_string()).unwrap_or_default(),
+            ),
+            KeyValue::new(
+                "channel",
+                path.map(|p| p.channel_id.to_string()).unwrap_or_default(),
+            ),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.tx_fees_paid.add(&cx, amount, labels);
+    }
+
+    /// The gas limit and the gas used by a transaction committed by the given chain.
+    ///
+    /// The channel labels are empty for transactions which do not relay packets.
+    pub fn tx_gas(
+        &self,
+        chain_id: &ChainId,
+        msg_type: &str,
+        path: Option<&PortChannelId>,
+        gas_wanted: u64,
+        gas_used: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("msg_type", msg_type.to_string()),
+            KeyValue::new(
+                "port",
+                path.map(|p| p.port_id.to_string()).unwrap_or_default(),
+            ),
+            KeyValue::new(
+                "channel",
+                path.map(|p| p.channel_id.to_string()).unwrap_or_default(),
+            ),
+        ];
+
+        self.tx_gas_wanted.add(&cx, gas_wanted, labels);
+        self.tx_gas_used.add(&cx, gas_used, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -490,7 +678,7 @@ impl TelemetryState {
             ];
 
             for _ in 0..tx_count {
-                self.tx_latency_submitted.observe(&cx, latency, labels);
+                self.tx_latency_submitted.record(&cx, latency, labels);
             }
         }
     }
@@ -520,11 +708,35 @@ impl TelemetryState {
             ];
 
             for _ in 0..tx_count {
-                self.tx_latency_confirmed.observe(&cx, latency, labels);
+                self.tx_latency_confirmed.record(&cx, latency, labels);
             }
         }
     }
 
+    /// Records the end-to-end latency of relaying a packet sent from the given chain
+    /// on the given channel, from the block including its SendPacket event
+    /// until the confirmation of its RecvPacket message.
+    pub fn packet_latency(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        latency: Duration,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.packet_latency
+            .record(&cx, latency.as_millis() as u64, labels);
+    }
+
     pub fn send_packet_events(
         &self,
         _seq_nr: u64,
@@ -628,6 +840,27 @@ impl TelemetryState {
         self.cleared_acknowledgment_events.add(&cx, 1, labels);
     }
 
+    /// Records a packet sent from `chain_id` which was not relayed
+    /// because it did not pay enough fees for the configured fee filter.
+    pub fn skipped_unincentivized(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.skipped_unincentivized.add(&cx, 1, labels);
+    }
+
     /// Inserts in the backlog a new event for the given sequence number.
     /// This happens when the relayer observed a new SendPacket event.
     pub fn backlog_insert(
@@ -760,6 +993,82 @@ impl TelemetryState {
             }
         }
     }
+
+    /// Records that events up to the given height were relayed from the given end of a path,
+    /// clearing the error which previously occurred while relaying from it, if any.
+    pub fn packets_relayed(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        height: Height,
+    ) {
+        let path_uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        let mut activity = self.path_activities.entry(path_uid).or_default();
+
+        if activity.last_relayed_height.map_or(true, |h| h < height) {
+            activity.last_relayed_height = Some(height);
+        }
+
+        activity.last_error = None;
+    }
+
+    /// Records the error which occurred while relaying from the given end of a path.
+    pub fn packet_relaying_failed(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        error: String,
+    ) {
+        let path_uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        self.path_activities.entry(path_uid).or_default().last_error = Some(error);
+    }
+
+    /// Returns the packets pending on the given end of a path,
+    /// along with the outcome of the last attempts at relaying them.
+    pub fn path_status(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> PathStatus {
+        let path_uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        let mut pending: Vec<(u64, u64)> = self
+            .backlogs
+            .get(&path_uid)
+            .map(|backlog| backlog.iter().map(|e| (*e.key(), *e.value())).collect())
+            .unwrap_or_default();
+
+        pending.sort_unstable();
+
+        let activity = self
+            .path_activities
+            .get(&path_uid)
+            .map(|activity| activity.clone())
+            .unwrap_or_default();
+
+        PathStatus {
+            pending,
+            last_relayed_height: activity.last_relayed_height,
+            last_error: activity.last_error,
+        }
+    }
 }
 
 use std::sync::Arc;
@@ -771,24 +1080,25 @@ use opentelemetry::sdk::metrics::aggregators::{histogram, last_value, sum};
 use opentelemetry::sdk::metrics::sdk_api::Descriptor;
 
 #[derive(Debug)]
-struct CustomAggregatorSelector;
+struct CustomAggregatorSelector {
+    latency_buckets: LatencyBuckets,
+}
 
 impl AggregatorSelector for CustomAggregatorSelector {
     fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
         match descriptor.name() {
             "wallet_balance" => Some(Arc::new(last_value())),
+            "dynamic_gas_price" => Some(Arc::new(last_value())),
+            "endpoint_height" => Some(Arc::new(last_value())),
             "backlog_oldest_sequence" => Some(Arc::new(last_value())),
             "backlog_oldest_timestamp" => Some(Arc::new(last_value())),
             "backlog_size" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.11.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
-            "tx_latency_submitted" => Some(Arc::new(histogram(&[
-                200.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0,
-            ]))),
-            "tx_latency_confirmed" => Some(Arc::new(histogram(&[
-                1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
-            ]))),
+            "tx_latency_submitted" => Some(Arc::new(histogram(&self.latency_buckets.submitted))),
+            "tx_latency_confirmed" => Some(Arc::new(histogram(&self.latency_buckets.confirmed))),
+            "packet_latency" => Some(Arc::new(histogram(&self.latency_buckets.packet))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -796,12 +1106,19 @@ impl AggregatorSelector for CustomAggregatorSelector {
 
 impl Default for TelemetryState {
     fn default() -> Self {
+        Self::new(LatencyBuckets::default())
+    }
+}
+
+impl TelemetryState {
+    /// Create the telemetry state, with the given buckets for the latency histograms.
+    pub fn new(latency_buckets: LatencyBuckets) -> Self {
         use opentelemetry::sdk::export::metrics::aggregation;
         use opentelemetry::sdk::metrics::{controllers, processors};
 
         let controller = controllers::basic(
             processors::factory(
-                CustomAggregatorSelector,
+                CustomAggregatorSelector { latency_buckets },
                 aggregation::cumulative_temporality_selector(),
             )
             .with_memory(true),
@@ -872,11 +1189,41 @@ impl Default for TelemetryState {
                 .with_description("Number of messages submitted to a specific chain")
                 .init(),
 
+            endpoint_height: meter
+                .u64_observable_gauge("endpoint_height")
+                .with_description("The latest height reported by each RPC and gRPC endpoint of a chain at the last health check, or 0 if the endpoint is unhealthy")
+                .init(),
+
+            endpoint_failovers: meter
+                .u64_counter("endpoint_failovers")
+                .with_description("Number of times Hermes switched to another RPC, gRPC or WebSocket endpoint of a chain")
+                .init(),
+
             wallet_balance: meter
                 .f64_observable_gauge("wallet_balance")
                 .with_description("The balance of each wallet Hermes uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
                 .init(),
 
+            dynamic_gas_price: meter
+                .f64_observable_gauge("dynamic_gas_price")
+                .with_description("The gas price used for the last transaction submitted to each chain, as queried from the chain's fee market module")
+                .init(),
+
+            tx_fees_paid: meter
+                .u64_counter("tx_fees_paid")
+                .with_description("The fees paid by the transactions submitted to each chain, per denom, message type and channel")
+                .init(),
+
+            tx_gas_wanted: meter
+                .u64_counter("tx_gas_wanted")
+                .with_description("The gas limit of the transactions committed by each chain, per message type and channel")
+                .init(),
+
+            tx_gas_used: meter
+                .u64_counter("tx_gas_used")
+                .with_description("The gas used by the transactions committed by each chain, per message type and channel")
+                .init(),
+
             send_packet_events: meter
                 .u64_counter("send_packet_events")
                 .with_description("Number of SendPacket events received")
@@ -902,8 +1249,13 @@ impl Default for TelemetryState {
                 .with_description("Number of WriteAcknowledgement events received during the initial and periodic clearing")
                 .init(),
 
+            skipped_unincentivized: meter
+                .u64_counter("skipped_unincentivized")
+                .with_description("Number of packets not received because they did not pay enough ICS-29 fees")
+                .init(),
+
             tx_latency_submitted: meter
-                .u64_observable_gauge("tx_latency_submitted")
+                .u64_histogram("tx_latency_submitted")
                 .with_unit(Unit::new("milliseconds"))
                 .with_description("The latency for all transactions submitted to a specific chain, \
                     i.e. the difference between the moment when Hermes received a batch of events \
@@ -911,13 +1263,21 @@ impl Default for TelemetryState {
                 .init(),
 
             tx_latency_confirmed: meter
-                .u64_observable_gauge("tx_latency_confirmed")
+                .u64_histogram("tx_latency_confirmed")
                 .with_unit(Unit::new("milliseconds"))
                 .with_description("The latency for all transactions submitted & confirmed to a specific chain, \
                     i.e. the difference between the moment when Hermes received a batch of events \
                     until the corresponding transaction(s) were confirmed. Milliseconds.")
                 .init(),
 
+            packet_latency: meter
+                .u64_histogram("packet_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The end-to-end latency for relaying packets, \
+                    i.e. the difference between the time of the block including the SendPacket event \
+                    and the moment when the corresponding RecvPacket message was confirmed. Milliseconds.")
+                .init(),
+
             in_flight_events: moka::sync::Cache::builder()
                 .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
                 .time_to_idle(Duration::from_secs(30 * 60)) // Remove entries if they have been idle for 30 minutes
@@ -925,6 +1285,8 @@ impl Default for TelemetryState {
 
             backlogs: DashMap::new(),
 
+            path_activities: DashMap::new(),
+
             backlog_oldest_sequence: meter
                 .u64_observable_gauge("backlog_oldest_sequence")
                 .with_description("Sequence number of the oldest SendPacket event in the backlog")
@@ -943,3 +1305,64 @@ impl Default for TelemetryState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_status() {
+        let state = TelemetryState::default();
+
+        let chain_id = ChainId::from_string("ibc-0");
+        let counterparty_chain_id = ChainId::from_string("ibc-1");
+        let channel_id = ChannelId::new(0);
+        let port_id = PortId::transfer();
+
+        let status = || state.path_status(&chain_id, &channel_id, &port_id);
+
+        assert_eq!(status(), PathStatus::default());
+
+        for seq in [7, 3, 5] {
+            state.backlog_insert(
+                seq,
+                &chain_id,
+                &channel_id,
+                &port_id,
+                &counterparty_chain_id,
+            );
+        }
+        state.backlog_remove(5, &chain_id, &channel_id, &port_id, &counterparty_chain_id);
+
+        let sequences: Vec<u64> = status().pending.iter().map(|(seq, _)| *seq).collect();
+        assert_eq!(sequences, vec![3, 7]);
+
+        // The status of the other ends of paths is not affected
+        assert_eq!(
+            state.path_status(&chain_id, &ChannelId::new(1), &port_id),
+            PathStatus::default()
+        );
+
+        state.packet_relaying_failed(&chain_id, &channel_id, &port_id, "failed".to_string());
+        assert_eq!(status().last_error, Some("failed".to_string()));
+
+        // Relaying successfully clears the last error
+        state.packets_relayed(
+            &chain_id,
+            &channel_id,
+            &port_id,
+            Height::new(0, 12).unwrap(),
+        );
+        assert_eq!(status().last_error, None);
+        assert_eq!(status().last_relayed_height, Height::new(0, 12).ok());
+
+        // The last relayed height only increases
+        state.packets_relayed(
+            &chain_id,
+            &channel_id,
+            &port_id,
+            Height::new(0, 10).unwrap(),
+        );
+        assert_eq!(status().last_relayed_height, Height::new(0, 12).ok());
+    }
+}
@@ -0,0 +1,308 @@
+//! A local remote signer, holding secp256k1 keys derived from mnemonics
+//! and signing on behalf of Hermes over the protocol described in
+//! [`ibc_relayer::keyring::remote_signer`].
+//!
+//! Meant for testing the remote signer support of Hermes offline only,
+//! as it keeps the keys in memory and does not authenticate its clients.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::Parser;
+use hdpath::StandardHDPath;
+use rouille::{router, try_or_400, Request, Response};
+use tracing::{error, info};
+use tracing_subscriber::{filter::LevelFilter, EnvFilter};
+
+use ibc_relayer::config::AddressType;
+use ibc_relayer::keyring::remote_signer::{
+    ErrorResponse, PubKeyRequest, PubKeyResponse, SignRequest, SignResponse,
+};
+use ibc_relayer::keyring::{Secp256k1KeyPair, SignHash, SigningKeyPair};
+
+const ETHERMINT_PK_TYPE: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// The signer only deals with public keys, the account prefix is irrelevant
+const ACCOUNT_PREFIX: &str = "signer";
+
+#[derive(Debug, Parser)]
+struct Opts {
+    /// The address to listen on
+    #[clap(long, default_value = "127.0.0.1:3500")]
+    listen: String,
+
+    /// A key to sign with, as `<NAME>=<MNEMONIC_FILE>`. Can be given multiple times.
+    #[clap(long = "key", required = true)]
+    keys: Vec<KeyArg>,
+
+    /// Derivation path of the keys
+    #[clap(long, default_value = "m/44'/118'/0'/0/0")]
+    hd_path: String,
+}
+
+#[derive(Debug)]
+struct KeyArg {
+    name: String,
+    mnemonic_file: PathBuf,
+}
+
+impl FromStr for KeyArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, path) = s
+            .split_once('=')
+            .ok_or_else(|| format!("invalid key '{}', expected <NAME>=<MNEMONIC_FILE>", s))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            mnemonic_file: PathBuf::from(path),
+        })
+    }
+}
+
+/// The same private key, hashing messages either with SHA-256 or Keccak-256 before signing
+struct Key {
+    sha256: Secp256k1KeyPair,
+    keccak256: Secp256k1KeyPair,
+}
+
+impl Key {
+    fn from_mnemonic(mnemonic: &str, hd_path: &StandardHDPath) -> Result<Self, String> {
+        let ethermint = AddressType::Ethermint {
+            pk_type: ETHERMINT_PK_TYPE.to_string(),
+        };
+
+        Ok(Self {
+            sha256: Secp256k1KeyPair::from_mnemonic(
+                mnemonic,
+                hd_path,
+                &AddressType::Cosmos,
+                ACCOUNT_PREFIX,
+            )
+            .map_err(|e| e.to_string())?,
+            keccak256: Secp256k1KeyPair::from_mnemonic(
+                mnemonic,
+                hd_path,
+                &ethermint,
+                ACCOUNT_PREFIX,
+            )
+            .map_err(|e| e.to_string())?,
+        })
+    }
+
+    fn pub_key(&self) -> String {
+        hex::encode(self.sha256.public_key.serialize())
+    }
+
+    fn sign(&self, message: &[u8], hash: SignHash) -> Result<Vec<u8>, String> {
+        match hash {
+            SignHash::Sha256 => self.sha256.sign(message),
+            SignHash::Keccak256 => self.keccak256.sign(message),
+        }
+        .map_err(|e| e.to_string())
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .init();
+
+    let opts = Opts::parse();
+
+    let keys = match load_keys(&opts) {
+        Ok(keys) => keys,
+        Err(e) => {
+            error!("{e}");
+            std::process::exit(1);
+        }
+    };
+
+    info!("mock signer listening on {}", opts.listen);
+
+    rouille::start_server(&opts.listen, move |request| handle(&keys, request));
+}
+
+fn load_keys(opts: &Opts) -> Result<HashMap<String, Key>, String> {
+    let hd_path = StandardHDPath::from_str(&opts.hd_path)
+        .map_err(|_| format!("invalid derivation path: {}", opts.hd_path))?;
+
+    opts.keys
+        .iter()
+        .map(|key| {
+            let mnemonic = fs::read_to_string(&key.mnemonic_file)
+                .map_err(|e| format!("failed to read {}: {}", key.mnemonic_file.display(), e))?;
+
+            let loaded = Key::from_mnemonic(mnemonic.trim(), &hd_path)?;
+            info!("loaded key '{}' ({})", key.name, loaded.pub_key());
+
+            Ok((key.name.clone(), loaded))
+        })
+        .collect()
+}
+
+fn handle(keys: &HashMap<String, Key>, request: &Request) -> Response {
+    router!(request,
+        (POST) (/pubkey) => {
+            let request: PubKeyRequest = try_or_400!(rouille::input::json_input(request));
+
+            match keys.get(&request.key_name) {
+                Some(key) => Response::json(&PubKeyResponse { pub_key: key.pub_key() }),
+                None => error_response(404, format!("unknown key '{}'", request.key_name)),
+            }
+        },
+
+        (POST) (/sign) => {
+            let request: SignRequest = try_or_400!(rouille::input::json_input(request));
+
+            let key = match keys.get(&request.key_name) {
+                Some(key) => key,
+                None => return error_response(404, format!("unknown key '{}'", request.key_name)),
+            };
+
+            let message = match hex::decode(&request.sign_bytes) {
+                Ok(message) => message,
+                Err(e) => return error_response(400, format!("invalid sign bytes: {}", e)),
+            };
+
+            match key.sign(&message, request.hash) {
+                Ok(signature) => {
+                    info!("signed {} bytes with key '{}'", message.len(), request.key_name);
+
+                    Response::json(&SignResponse {
+                        signature: hex::encode(signature),
+                        pub_key: key.pub_key(),
+                    })
+                }
+                Err(e) => error_response(500, e),
+            }
+        },
+
+        _ => Response::empty_404(),
+    )
+}
+
+fn error_response(status_code: u16, error: String) -> Response {
+    Response::json(&ErrorResponse { error }).with_status_code(status_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use hdpath::StandardHDPath;
+    use secp256k1::{ecdsa::Signature, Message, Secp256k1};
+    use sha2::{Digest, Sha256};
+
+    use ibc_relayer::config::AddressType;
+    use ibc_relayer::keyring::{RemoteSigner, Secp256k1KeyPair, SignHash, SigningKeyPair};
+
+    use super::{handle, Key, ETHERMINT_PK_TYPE};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn sign_remotely() {
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+
+        let keys = HashMap::from([(
+            "relayer".to_string(),
+            Key::from_mnemonic(MNEMONIC, &hd_path).unwrap(),
+        )]);
+
+        let server =
+            rouille::Server::new("127.0.0.1:0", move |request| handle(&keys, request)).unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let (handle, stop) = server.stoppable();
+
+        let local =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos")
+                .unwrap();
+
+        let remote = Secp256k1KeyPair::from_remote_signer(
+            RemoteSigner::new(url.clone(), "relayer".to_string()),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+
+        assert_eq!(remote.account(), local.account());
+
+        // ECDSA signatures are deterministic (RFC 6979)
+        let message = b"sign doc bytes";
+        let signature = remote.sign(message).unwrap();
+        assert_eq!(signature, local.sign(message).unwrap());
+
+        let hashed_message = Message::from_slice(&Sha256::digest(message)).unwrap();
+        let signature = Signature::from_compact(&signature).unwrap();
+        assert!(Secp256k1::verification_only()
+            .verify_ecdsa(&hashed_message, &signature, &remote.public_key)
+            .is_ok());
+
+        // Ethermint keys hash the message with Keccak-256 instead
+        let ethermint = AddressType::Ethermint {
+            pk_type: ETHERMINT_PK_TYPE.to_string(),
+        };
+        let local_ethermint =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &ethermint, "evmos").unwrap();
+        let remote_ethermint = Secp256k1KeyPair::from_remote_signer(
+            RemoteSigner::new(url.clone(), "relayer".to_string()),
+            &ethermint,
+            "evmos",
+        )
+        .unwrap();
+        assert_eq!(remote_ethermint.account(), local_ethermint.account());
+        assert_eq!(
+            remote_ethermint.sign(message).unwrap(),
+            local_ethermint.sign(message).unwrap()
+        );
+        assert_ne!(
+            remote_ethermint.sign(message).unwrap(),
+            remote.sign(message).unwrap()
+        );
+
+        // Signatures from another key than the expected one are rejected
+        let other = Secp256k1KeyPair::from_mnemonic(
+            MNEMONIC,
+            &StandardHDPath::from_str("m/44'/118'/0'/0/1").unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+        let hashed_message = Sha256::digest(message);
+        assert!(RemoteSigner::new(url.clone(), "relayer".to_string())
+            .sign(
+                message,
+                &hashed_message,
+                SignHash::Sha256,
+                &other.public_key
+            )
+            .is_err());
+
+        // Remote keys are stored without any private key, and can be restored
+        let serialized = serde_json::to_string(&remote).unwrap();
+        assert!(!serialized.contains("private_key"));
+        let restored: Secp256k1KeyPair = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(restored.remote_signer(), remote.remote_signer());
+
+        // Local keys are still serialized in the same format
+        let serialized = serde_json::to_string(&local).unwrap();
+        assert!(serialized.contains("private_key"));
+        let restored: Secp256k1KeyPair = serde_json::from_str(&serialized).unwrap();
+        assert!(restored.remote_signer().is_none());
+
+        let unknown = RemoteSigner::new(url, "unknown".to_string());
+        assert!(unknown.public_key().is_err());
+
+        stop.send(()).unwrap();
+        handle.join().unwrap();
+    }
+}
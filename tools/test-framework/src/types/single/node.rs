@@ -129,9 +129,14 @@ impl FullNode {
             rpc_addr: Url::from_str(&self.chain_driver.rpc_address())?,
             websocket_addr: Url::from_str(&self.chain_driver.websocket_address())?,
             grpc_addr: Url::from_str(&self.chain_driver.grpc_address())?,
+            witness_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            fallback_grpc_addrs: Vec::new(),
+            fallback_websocket_addrs: Vec::new(),
             rpc_timeout: Duration::from_secs(10),
             account_prefix: self.chain_driver.account_prefix.clone(),
             key_name: self.wallets.relayer.id.0.clone(),
+            extra_key_names: Vec::new(),
 
             // By default we use in-memory key store to avoid polluting
             // ~/.hermes/keys. See
@@ -151,8 +156,13 @@ impl FullNode {
             trusting_period: Some(Duration::from_secs(14 * 24 * 3600)),
             trust_threshold: Default::default(),
             gas_price: config::GasPrice::new(0.001, "stake".to_string()),
+            dynamic_gas_price: Default::default(),
+            gas_estimation: Default::default(),
             packet_filter: Default::default(),
+            fee_filter: None,
+            excluded_sequences: Default::default(),
             address_type: chain_type.address_type(),
+            event_source: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
             extension_options: Default::default(),